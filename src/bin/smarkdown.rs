@@ -0,0 +1,190 @@
+//! Converts markdown from files or stdin, writing the result to stdout.
+//!
+//! ```sh
+//! smarkdown file.md > file.html
+//! cat file.md | smarkdown --to json
+//! ```
+
+use std::{
+  env, fs,
+  io::{self, BufWriter, Read, Write},
+  process::ExitCode,
+};
+
+use smarkdown::{
+  parse_with_options, CommonMarkRenderer, Document, HtmlRenderer, ParseOptions, RenderOptions,
+  Sanitiser, SoftBreak, TextRenderer, XmlRenderer,
+};
+
+const USAGE: &str = "Usage: smarkdown [OPTIONS] [FILE]...
+
+Converts markdown to another format. The files are joined together, each starting on a new line,
+and read as one document, with stdin being read if none are given or a file is `-`.
+
+Options:
+  -t, --to <FORMAT>          The format to write: html (default), text, xml, json or markdown
+      --safe                 Replace raw HTML and dangerous link destinations in html output
+      --sanitise             Clean up raw HTML in html output, keeping only common formatting tags
+      --smart                Use curly quotes, dashes and ellipses in place of their plain forms
+      --sourcepos            Add the source position of each element to html and xml output
+      --tables               Enable GFM pipe tables
+      --tasklists            Enable GFM task list items, shown with a checkbox
+      --soft-break <BREAK>   How soft line breaks are written in html: newline (default), space or br
+      --urls                 Follow links with their destination in text output
+  -h, --help                 Show this message";
+
+#[derive(Debug, PartialEq, Clone, Copy, Default)]
+enum Format {
+  #[default]
+  Html,
+  Text,
+  Xml,
+  Json,
+  Markdown,
+}
+
+#[derive(Debug, PartialEq, Default)]
+struct Args {
+  to: Format,
+  parse_options: ParseOptions,
+  render_options: RenderOptions,
+  show_urls: bool,
+  files: Vec<String>,
+  help: bool,
+}
+
+/// Reads the arguments, not including the name of the program
+fn parse_args(args: impl IntoIterator<Item = String>) -> Result<Args, String> {
+  let mut parsed = Args::default();
+  let mut args = args.into_iter();
+  while let Some(arg) = args.next() {
+    // Values can be given either as the next argument or after an `=`
+    let (flag, inline_value) = match arg.split_once('=') {
+      Some((flag, value)) if flag.starts_with("--") => (flag.to_string(), Some(value.to_string())),
+      _ => (arg.clone(), None),
+    };
+    let mut value = || {
+      inline_value
+        .clone()
+        .or_else(|| args.next())
+        .ok_or_else(|| format!("{flag} needs a value"))
+    };
+
+    match flag.as_str() {
+      "-t" | "--to" => {
+        parsed.to = match value()?.as_str() {
+          "html" => Format::Html,
+          "text" | "plain" => Format::Text,
+          "xml" => Format::Xml,
+          "json" => Format::Json,
+          "markdown" | "commonmark" => Format::Markdown,
+          other => return Err(format!("unknown format `{other}`")),
+        }
+      }
+      "--soft-break" => {
+        parsed.render_options.soft_break = match value()?.as_str() {
+          "newline" => SoftBreak::Newline,
+          "space" => SoftBreak::Space,
+          "br" => SoftBreak::LineBreak,
+          other => return Err(format!("unknown soft break `{other}`")),
+        }
+      }
+      "--safe" => parsed.render_options.safe = true,
+      "--sanitise" => parsed.render_options.sanitiser = Some(Sanitiser::default()),
+      "--smart" => parsed.parse_options.smart = true,
+      "--tables" => parsed.parse_options.tables = true,
+      "--tasklists" => parsed.parse_options.tasklists = true,
+      "--sourcepos" => parsed.render_options.sourcepos = true,
+      "--urls" => parsed.show_urls = true,
+      "-h" | "--help" => parsed.help = true,
+      "-" => parsed.files.push(arg),
+      flag if flag.starts_with('-') => return Err(format!("unknown option `{flag}`")),
+      _ => parsed.files.push(arg),
+    }
+  }
+  Ok(parsed)
+}
+
+/// Reads every file in `files` one after the other, or stdin if there are none.
+///
+/// Each file starts on a new line, so that the last line of one can't run into the first line of the next
+fn read_input(files: &[String]) -> Result<String, String> {
+  let stdin = ["-".to_string()];
+  let files = if files.is_empty() { &stdin[..] } else { files };
+  let mut input = String::new();
+  for file in files {
+    if !input.is_empty() && !input.ends_with('\n') {
+      input.push('\n');
+    }
+    if file == "-" {
+      io::stdin()
+        .read_to_string(&mut input)
+        .map_err(|error| format!("couldn't read stdin: {error}"))?;
+    } else {
+      let contents =
+        fs::read_to_string(file).map_err(|error| format!("couldn't read {file}: {error}"))?;
+      input.push_str(&contents);
+    }
+  }
+  Ok(input)
+}
+
+/// Writes `document` in the chosen format, ending it with a line ending
+fn write_output(args: Args, document: &Document, w: &mut impl Write) -> io::Result<()> {
+  match args.to {
+    Format::Html => {
+      document.render_io(&mut HtmlRenderer::new(args.render_options), w)?;
+      if !document.blocks().is_empty() {
+        writeln!(w)?;
+      }
+    }
+    Format::Text => {
+      document.render_io(&mut TextRenderer::new(args.show_urls), w)?;
+      if !document.blocks().is_empty() {
+        writeln!(w)?;
+      }
+    }
+    Format::Xml => document.render_io(&mut XmlRenderer::new(args.render_options), w)?,
+    Format::Json => writeln!(w, "{}", document.to_json())?,
+    Format::Markdown => document.render_io(&mut CommonMarkRenderer::default(), w)?,
+  }
+  w.flush()
+}
+
+fn main() -> ExitCode {
+  let args = match parse_args(env::args().skip(1)) {
+    Ok(args) => args,
+    Err(error) => {
+      eprintln!("smarkdown: {error}\n\n{USAGE}");
+      return ExitCode::from(2);
+    }
+  };
+  if args.help {
+    println!("{USAGE}");
+    return ExitCode::SUCCESS;
+  }
+
+  let input = match read_input(&args.files) {
+    Ok(input) => input,
+    Err(error) => {
+      eprintln!("smarkdown: {error}");
+      return ExitCode::FAILURE;
+    }
+  };
+  let document = parse_with_options(&input, &args.parse_options);
+
+  let stdout = io::stdout();
+  let mut w = BufWriter::new(stdout.lock());
+  match write_output(args, &document, &mut w) {
+    // The reader going away, such as when piped into `head`, isn't an error
+    Err(error) if error.kind() != io::ErrorKind::BrokenPipe => {
+      eprintln!("smarkdown: couldn't write output: {error}");
+      ExitCode::FAILURE
+    }
+    _ => ExitCode::SUCCESS,
+  }
+}
+
+#[cfg(test)]
+#[path = "smarkdown/tests.rs"]
+mod tests;
@@ -0,0 +1,74 @@
+use smarkdown::{parse, Sanitiser, SoftBreak};
+
+use super::{parse_args, read_input, write_output, Args, Format};
+
+fn args(args: &[&str]) -> Result<Args, String> {
+  parse_args(args.iter().map(|arg| arg.to_string()))
+}
+
+#[test]
+fn reads_flags_and_files() {
+  let parsed = args(&["--to", "xml", "a.md", "--sourcepos", "-", "--soft-break=br"]).unwrap();
+  assert_eq!(parsed.to, Format::Xml);
+  assert!(parsed.render_options.sourcepos);
+  assert_eq!(parsed.render_options.soft_break, SoftBreak::LineBreak);
+  assert_eq!(parsed.files, ["a.md", "-"]);
+  assert!(!parsed.parse_options.smart);
+  assert!(args(&["--smart"]).unwrap().parse_options.smart);
+  assert!(args(&["--tables"]).unwrap().parse_options.tables);
+  assert!(args(&["--tasklists"]).unwrap().parse_options.tasklists);
+  assert!(args(&["--safe"]).unwrap().render_options.safe);
+  assert_eq!(
+    args(&["--sanitise"]).unwrap().render_options.sanitiser,
+    Some(Sanitiser::default())
+  );
+
+  assert_eq!(
+    args(&["-t=json"]),
+    Err("unknown option `-t=json`".to_string())
+  );
+  assert_eq!(args(&["-t", "markdown"]).unwrap().to, Format::Markdown);
+  assert_eq!(args(&["--to"]), Err("--to needs a value".to_string()));
+  assert_eq!(
+    args(&["--to", "pdf"]),
+    Err("unknown format `pdf`".to_string())
+  );
+  assert_eq!(
+    args(&["--unsafe"]),
+    Err("unknown option `--unsafe`".to_string())
+  );
+}
+
+#[test]
+fn reads_files_onto_separate_lines() {
+  let directory = std::env::temp_dir().join(format!("smarkdown-{}", std::process::id()));
+  std::fs::create_dir_all(&directory).unwrap();
+  let first = directory.join("first.md");
+  let second = directory.join("second.md");
+  std::fs::write(&first, "# First").unwrap();
+  std::fs::write(&second, "Second\n").unwrap();
+
+  let parsed = args(&[first.to_str().unwrap(), second.to_str().unwrap()]).unwrap();
+  let input = read_input(&parsed.files);
+  std::fs::remove_dir_all(&directory).unwrap();
+  assert_eq!(input, Ok("# First\nSecond\n".to_string()));
+  assert_eq!(
+    parse(&input.unwrap()).as_html(),
+    "<h1>First</h1>\n<p>Second</p>"
+  );
+}
+
+#[test]
+fn writes_each_format() {
+  let document = parse("# Hi\n\n[a](/b)");
+  let output = |to: &[&str]| {
+    let mut output = vec![];
+    write_output(args(to).unwrap(), &document, &mut output).unwrap();
+    String::from_utf8(output).unwrap()
+  };
+  assert_eq!(output(&[]), "<h1>Hi</h1>\n<p><a href=\"/b\">a</a></p>\n");
+  assert_eq!(output(&["--to", "text", "--urls"]), "Hi\n\na (/b)\n");
+  assert_eq!(output(&["--to", "markdown"]), "# Hi\n\n[a](/b)\n");
+  assert!(output(&["--to", "xml"]).ends_with("</document>\n"));
+  assert!(output(&["--to", "json"]).starts_with("{\"type\":\"document\""));
+}
@@ -0,0 +1,482 @@
+//! Converts documents to and from JSON, so that a parsed tree can be handed to something outside of Rust.
+//!
+//! # Schema
+//!
+//! Every node is an object with a `"type"` and a `"span"`, along with fields depending on its type.
+//! A document is written as `{"type":"document","blocks":[...]}`. Link reference definitions aren't included,
+//! as they have already been resolved into the links using them.
+//!
+//! A span gives the start and end of the source the node came from, each with a byte `offset`, a `line` and a byte `column`.
+//! Lines and columns start from 1, and the end is exclusive:
+//!
+//! ```json
+//! {"start":{"offset":0,"line":1,"column":1},"end":{"offset":5,"line":1,"column":6}}
+//! ```
+//!
+//! Blocks:
+//!
+//! | `type`                | Fields                                                                             |
+//! |-----------------------|------------------------------------------------------------------------------------|
+//! | `paragraph`           | `children`: inlines                                                                |
+//! | `block_quote`         | `children`: blocks                                                                 |
+//! | `list`                | `list_type`, `start`, `loose`: boolean, `children`: `item` blocks                  |
+//! | `item`                | `checked`: boolean or `null`, `children`: blocks                                   |
+//! | `thematic_break`      |                                                                                    |
+//! | `heading`             | `level`: 1 to 6, `children`: inlines                                               |
+//! | `indented_code_block` | `content`: string, without its final line ending                                   |
+//! | `fenced_code_block`   | `info`: string, `content`: string, including its final line ending                 |
+//! | `html_block`          | `content`: string                                                                  |
+//! | `table`               | `alignments`: array of strings, `header`: a `table_row`, `rows`: `table_row`s      |
+//! | `table_row`           | `children`: `table_cell`s, one for each column                                     |
+//! | `table_cell`          | `children`: inlines                                                                |
+//!
+//! `list_type` is one of `"number"` (`1.`), `"bracketed_number"` (`1)`), `"dash"`, `"star"` or `"plus"`.
+//! `start` is the number of the first item of an ordered list, and is `null` for bullet lists.
+//! `checked` is only set for task list items.
+//! Each of a table's `alignments` is one of `"none"`, `"left"`, `"center"` or `"right"`.
+//!
+//! Inlines:
+//!
+//! | `type`       | Fields                                                               |
+//! |--------------|----------------------------------------------------------------------|
+//! | `text`       | `content`: string                                                    |
+//! | `emphasis`   | `children`: inlines                                                  |
+//! | `strong`     | `children`: inlines                                                  |
+//! | `code`       | `content`: string                                                    |
+//! | `link`       | `dest`: string, `title`: string or `null`, `children`: inlines       |
+//! | `image`      | `dest`: string, `title`: string or `null`, `children`: inlines       |
+//! | `autolink`   | `url`: string, `email`: boolean                                      |
+//! | `raw_html`   | `content`: string                                                    |
+//! | `soft_break` |                                                                      |
+//! | `hard_break` |                                                                      |
+
+mod value;
+
+use std::fmt::{self, Write};
+
+use crate::{
+  parse::{Alignment, Block, Document, Inline, ListType, TableRow},
+  span::{Position, Span, Spanned},
+};
+
+use value::{Object, Value};
+
+/// Why a document couldn't be read from JSON
+#[derive(Debug, PartialEq, Clone)]
+pub enum JsonError {
+  /// The input isn't valid JSON. `offset` is the byte offset the problem was found at
+  Syntax { offset: usize, message: String },
+  /// The input is valid JSON but doesn't follow the schema
+  Schema(String),
+}
+
+impl fmt::Display for JsonError {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    match self {
+      JsonError::Syntax { offset, message } => {
+        write!(f, "invalid JSON at byte {offset}: {message}")
+      }
+      JsonError::Schema(message) => write!(f, "unexpected JSON: {message}"),
+    }
+  }
+}
+
+impl std::error::Error for JsonError {}
+
+/// Writes `document` as compact JSON following the schema above
+pub fn write_document(w: &mut dyn Write, document: &Document) -> fmt::Result {
+  w.write_str("{\"type\":\"document\",\"blocks\":")?;
+  write_list(w, document.blocks(), write_block)?;
+  w.write_char('}')
+}
+
+/// Reads a document written by [`write_document`]
+pub fn read_document(json: &str) -> Result<Document, JsonError> {
+  let mut object = value::parse(json)?.into_object("the document")?;
+  object.expect_type("document")?;
+  let mut document = Document::new();
+  *document.blocks_mut() = read_list(object.take("blocks")?, read_block)?;
+  Ok(document)
+}
+
+fn write_list<T>(
+  w: &mut dyn Write,
+  items: &[T],
+  write_item: fn(&mut dyn Write, &T) -> fmt::Result,
+) -> fmt::Result {
+  w.write_char('[')?;
+  for (i, item) in items.iter().enumerate() {
+    if i > 0 {
+      w.write_char(',')?;
+    }
+    write_item(w, item)?;
+  }
+  w.write_char(']')
+}
+
+/// Writes `text` as a JSON string, escaping quotes, backslashes and control characters
+fn write_string(w: &mut dyn Write, text: &str) -> fmt::Result {
+  w.write_char('"')?;
+  for char in text.chars() {
+    match char {
+      '"' => w.write_str("\\\"")?,
+      '\\' => w.write_str("\\\\")?,
+      '\n' => w.write_str("\\n")?,
+      '\r' => w.write_str("\\r")?,
+      '\t' => w.write_str("\\t")?,
+      char if char.is_control() => write!(w, "\\u{:04x}", char as u32)?,
+      char => w.write_char(char)?,
+    }
+  }
+  w.write_char('"')
+}
+
+fn write_position(w: &mut dyn Write, position: &Position) -> fmt::Result {
+  write!(
+    w,
+    "{{\"offset\":{},\"line\":{},\"column\":{}}}",
+    position.offset, position.line, position.column
+  )
+}
+
+/// Writes the opening of a node's object, up to the end of its span
+fn write_node_start(w: &mut dyn Write, node_type: &str, span: &Span) -> fmt::Result {
+  write!(w, "{{\"type\":\"{node_type}\",\"span\":{{\"start\":")?;
+  write_position(w, &span.start)?;
+  w.write_str(",\"end\":")?;
+  write_position(w, &span.end)?;
+  w.write_str("}")
+}
+
+fn write_block(w: &mut dyn Write, block: &Spanned<Block>) -> fmt::Result {
+  let span = &block.span;
+  match &block.value {
+    Block::Paragraph(inlines) => {
+      write_node_start(w, "paragraph", span)?;
+      w.write_str(",\"children\":")?;
+      write_list(w, inlines, write_inline)?;
+    }
+    Block::BlockQuote(blocks) => {
+      write_node_start(w, "block_quote", span)?;
+      w.write_str(",\"children\":")?;
+      write_list(w, blocks, write_block)?;
+    }
+    Block::List {
+      list_type,
+      inner,
+      loose,
+    } => {
+      let (name, start) = match list_type {
+        ListType::Number(start) => ("number", Some(start)),
+        ListType::BracketedNumber(start) => ("bracketed_number", Some(start)),
+        ListType::Dash => ("dash", None),
+        ListType::Star => ("star", None),
+        ListType::Plus => ("plus", None),
+      };
+      write_node_start(w, "list", span)?;
+      write!(w, ",\"list_type\":\"{name}\",\"start\":")?;
+      match start {
+        Some(start) => write!(w, "{start}")?,
+        None => w.write_str("null")?,
+      }
+      write!(w, ",\"loose\":{loose},\"children\":")?;
+      write_list(w, inner, write_block)?;
+    }
+    Block::LineItem { inner, checked } => {
+      write_node_start(w, "item", span)?;
+      w.write_str(",\"checked\":")?;
+      match checked {
+        Some(checked) => write!(w, "{checked}")?,
+        None => w.write_str("null")?,
+      }
+      w.write_str(",\"children\":")?;
+      write_list(w, inner, write_block)?;
+    }
+    Block::ThematicBreak => write_node_start(w, "thematic_break", span)?,
+    Block::Header(level, inlines) => {
+      write_node_start(w, "heading", span)?;
+      write!(w, ",\"level\":{level},\"children\":")?;
+      write_list(w, inlines, write_inline)?;
+    }
+    Block::IndentedCodeBlock(content) => {
+      write_node_start(w, "indented_code_block", span)?;
+      w.write_str(",\"content\":")?;
+      write_string(w, content)?;
+    }
+    Block::FencedCodeBlock { info, content } => {
+      write_node_start(w, "fenced_code_block", span)?;
+      w.write_str(",\"info\":")?;
+      write_string(w, info)?;
+      w.write_str(",\"content\":")?;
+      write_string(w, content)?;
+    }
+    Block::HtmlBlock(content) => {
+      write_node_start(w, "html_block", span)?;
+      w.write_str(",\"content\":")?;
+      write_string(w, content)?;
+    }
+    Block::Table {
+      alignments,
+      header,
+      rows,
+    } => {
+      write_node_start(w, "table", span)?;
+      w.write_str(",\"alignments\":")?;
+      write_list(w, alignments, write_alignment)?;
+      w.write_str(",\"header\":")?;
+      write_table_row(w, header)?;
+      w.write_str(",\"rows\":")?;
+      write_list(w, rows, write_table_row)?;
+    }
+  }
+  w.write_char('}')
+}
+
+fn write_alignment(w: &mut dyn Write, alignment: &Alignment) -> fmt::Result {
+  let name = match alignment {
+    Alignment::None => "none",
+    Alignment::Left => "left",
+    Alignment::Center => "center",
+    Alignment::Right => "right",
+  };
+  write_string(w, name)
+}
+
+fn write_table_row(w: &mut dyn Write, row: &Spanned<TableRow>) -> fmt::Result {
+  write_node_start(w, "table_row", &row.span)?;
+  w.write_str(",\"children\":")?;
+  write_list(w, &row.value, |w, cell| {
+    write_node_start(w, "table_cell", &cell.span)?;
+    w.write_str(",\"children\":")?;
+    write_list(w, &cell.value, write_inline)?;
+    w.write_char('}')
+  })?;
+  w.write_char('}')
+}
+
+fn write_inline(w: &mut dyn Write, inline: &Spanned<Inline>) -> fmt::Result {
+  let span = &inline.span;
+  match &inline.value {
+    Inline::Text(content) | Inline::Code(content) | Inline::RawHtml(content) => {
+      let name = match inline.value {
+        Inline::Text(..) => "text",
+        Inline::Code(..) => "code",
+        _ => "raw_html",
+      };
+      write_node_start(w, name, span)?;
+      w.write_str(",\"content\":")?;
+      write_string(w, content)?;
+    }
+    Inline::Emphasis(children) | Inline::Strong(children) => {
+      let name = match inline.value {
+        Inline::Emphasis(..) => "emphasis",
+        _ => "strong",
+      };
+      write_node_start(w, name, span)?;
+      w.write_str(",\"children\":")?;
+      write_list(w, children, write_inline)?;
+    }
+    Inline::Link {
+      dest,
+      title,
+      children,
+    }
+    | Inline::Image {
+      dest,
+      title,
+      children,
+    } => {
+      let name = match inline.value {
+        Inline::Link { .. } => "link",
+        _ => "image",
+      };
+      write_node_start(w, name, span)?;
+      w.write_str(",\"dest\":")?;
+      write_string(w, dest)?;
+      w.write_str(",\"title\":")?;
+      match title {
+        Some(title) => write_string(w, title)?,
+        None => w.write_str("null")?,
+      }
+      w.write_str(",\"children\":")?;
+      write_list(w, children, write_inline)?;
+    }
+    Inline::Autolink { url, email } => {
+      write_node_start(w, "autolink", span)?;
+      w.write_str(",\"url\":")?;
+      write_string(w, url)?;
+      write!(w, ",\"email\":{email}")?;
+    }
+    Inline::SoftBreak => write_node_start(w, "soft_break", span)?,
+    Inline::HardBreak => write_node_start(w, "hard_break", span)?,
+  }
+  w.write_char('}')
+}
+
+fn read_list<T>(
+  value: Value,
+  read_item: fn(Value) -> Result<T, JsonError>,
+) -> Result<Vec<T>, JsonError> {
+  match value {
+    Value::Array(items) => items.into_iter().map(read_item).collect(),
+    _ => Err(JsonError::Schema("expected an array of nodes".to_string())),
+  }
+}
+
+fn read_position(value: Value) -> Result<Position, JsonError> {
+  let mut object = value.into_object("a position")?;
+  Ok(Position {
+    offset: object.take_number("offset")?,
+    line: object.take_number("line")?,
+    column: object.take_number("column")?,
+  })
+}
+
+/// Reads the span of a node, which may be left out to give it an empty one
+fn read_span(object: &mut Object) -> Result<Span, JsonError> {
+  match object.take_optional("span") {
+    Some(value) => {
+      let mut span = value.into_object("a span")?;
+      Ok(Span::new(
+        read_position(span.take("start")?)?,
+        read_position(span.take("end")?)?,
+      ))
+    }
+    None => Ok(Span::default()),
+  }
+}
+
+fn read_block(value: Value) -> Result<Spanned<Block>, JsonError> {
+  let mut object = value.into_object("a block")?;
+  let span = read_span(&mut object)?;
+  let block = match object.take_string("type")?.as_str() {
+    "paragraph" => Block::Paragraph(read_list(object.take("children")?, read_inline)?),
+    "block_quote" => Block::BlockQuote(read_list(object.take("children")?, read_block)?),
+    "list" => {
+      let start = object.take_optional_number("start")?;
+      let list_type = match (object.take_string("list_type")?.as_str(), start) {
+        ("number", Some(start)) => ListType::Number(start),
+        ("bracketed_number", Some(start)) => ListType::BracketedNumber(start),
+        ("dash", _) => ListType::Dash,
+        ("star", _) => ListType::Star,
+        ("plus", _) => ListType::Plus,
+        ("number" | "bracketed_number", None) => {
+          return Err(JsonError::Schema(
+            "ordered lists need a `start`".to_string(),
+          ))
+        }
+        (other, _) => return Err(JsonError::Schema(format!("unknown list type `{other}`"))),
+      };
+      Block::List {
+        list_type,
+        loose: object.take_bool("loose")?,
+        inner: read_list(object.take("children")?, read_block)?,
+      }
+    }
+    "item" => Block::LineItem {
+      inner: read_list(object.take("children")?, read_block)?,
+      checked: object.take_optional_bool("checked")?,
+    },
+    "thematic_break" => Block::ThematicBreak,
+    "heading" => {
+      let level = object.take_number("level")?;
+      if !(1..=6).contains(&level) {
+        return Err(JsonError::Schema(format!(
+          "heading level {level} isn't from 1 to 6"
+        )));
+      }
+      Block::Header(
+        level as u8,
+        read_list(object.take("children")?, read_inline)?,
+      )
+    }
+    "indented_code_block" => Block::IndentedCodeBlock(object.take_string("content")?),
+    "fenced_code_block" => Block::FencedCodeBlock {
+      info: object.take_string("info")?,
+      content: object.take_string("content")?,
+    },
+    "html_block" => Block::HtmlBlock(object.take_string("content")?),
+    "table" => {
+      let alignments = read_list(object.take("alignments")?, read_alignment)?;
+      let header = read_table_row(object.take("header")?)?;
+      let rows = read_list(object.take("rows")?, read_table_row)?;
+      if std::iter::once(&header)
+        .chain(&rows)
+        .any(|row| row.value.len() != alignments.len())
+      {
+        return Err(JsonError::Schema(
+          "table rows need one cell for each alignment".to_string(),
+        ));
+      }
+      Block::Table {
+        alignments,
+        header,
+        rows,
+      }
+    }
+    other => return Err(JsonError::Schema(format!("unknown block type `{other}`"))),
+  };
+  Ok(Spanned::new(block, span))
+}
+
+fn read_alignment(value: Value) -> Result<Alignment, JsonError> {
+  match value {
+    Value::String(name) => match name.as_str() {
+      "none" => Ok(Alignment::None),
+      "left" => Ok(Alignment::Left),
+      "center" => Ok(Alignment::Center),
+      "right" => Ok(Alignment::Right),
+      other => Err(JsonError::Schema(format!("unknown alignment `{other}`"))),
+    },
+    _ => Err(JsonError::Schema(
+      "expected an alignment to be a string".to_string(),
+    )),
+  }
+}
+
+fn read_table_row(value: Value) -> Result<Spanned<TableRow>, JsonError> {
+  let mut object = value.into_object("a table row")?;
+  object.expect_type("table_row")?;
+  let span = read_span(&mut object)?;
+  let cells = read_list(object.take("children")?, |value| {
+    let mut object = value.into_object("a table cell")?;
+    object.expect_type("table_cell")?;
+    let span = read_span(&mut object)?;
+    let inlines = read_list(object.take("children")?, read_inline)?;
+    Ok(Spanned::new(inlines, span))
+  })?;
+  Ok(Spanned::new(cells, span))
+}
+
+fn read_inline(value: Value) -> Result<Spanned<Inline>, JsonError> {
+  let mut object = value.into_object("an inline")?;
+  let span = read_span(&mut object)?;
+  let inline = match object.take_string("type")?.as_str() {
+    "text" => Inline::Text(object.take_string("content")?),
+    "emphasis" => Inline::Emphasis(read_list(object.take("children")?, read_inline)?),
+    "strong" => Inline::Strong(read_list(object.take("children")?, read_inline)?),
+    "code" => Inline::Code(object.take_string("content")?),
+    "link" => Inline::Link {
+      dest: object.take_string("dest")?,
+      title: object.take_optional_string("title")?,
+      children: read_list(object.take("children")?, read_inline)?,
+    },
+    "image" => Inline::Image {
+      dest: object.take_string("dest")?,
+      title: object.take_optional_string("title")?,
+      children: read_list(object.take("children")?, read_inline)?,
+    },
+    "autolink" => Inline::Autolink {
+      url: object.take_string("url")?,
+      email: object.take_bool("email")?,
+    },
+    "raw_html" => Inline::RawHtml(object.take_string("content")?),
+    "soft_break" => Inline::SoftBreak,
+    "hard_break" => Inline::HardBreak,
+    other => return Err(JsonError::Schema(format!("unknown inline type `{other}`"))),
+  };
+  Ok(Spanned::new(inline, span))
+}
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,138 @@
+use crate::parse::{parse, parse_with_options, Document, ParseOptions};
+
+use super::{value, JsonError};
+
+#[test]
+fn writes_the_schema() {
+  let document = parse("## *a*\n\n2) b");
+  assert_eq!(
+    document.to_json(),
+    concat!(
+      r#"{"type":"document","blocks":["#,
+      r#"{"type":"heading","span":{"start":{"offset":0,"line":1,"column":1},"end":{"offset":6,"line":1,"column":7}},"level":2,"children":["#,
+      r#"{"type":"emphasis","span":{"start":{"offset":3,"line":1,"column":4},"end":{"offset":6,"line":1,"column":7}},"children":["#,
+      r#"{"type":"text","span":{"start":{"offset":4,"line":1,"column":5},"end":{"offset":5,"line":1,"column":6}},"content":"a"}]}]},"#,
+      r#"{"type":"list","span":{"start":{"offset":8,"line":3,"column":1},"end":{"offset":12,"line":3,"column":5}},"list_type":"bracketed_number","start":2,"loose":false,"children":["#,
+      r#"{"type":"item","span":{"start":{"offset":8,"line":3,"column":1},"end":{"offset":12,"line":3,"column":5}},"checked":null,"children":["#,
+      r#"{"type":"paragraph","span":{"start":{"offset":11,"line":3,"column":4},"end":{"offset":12,"line":3,"column":5}},"children":["#,
+      r#"{"type":"text","span":{"start":{"offset":11,"line":3,"column":4},"end":{"offset":12,"line":3,"column":5}},"content":"b"}]}]}]}]}"#,
+    )
+  );
+}
+
+#[test]
+fn round_trips() {
+  let document = parse(
+    "# Title\n\n> quote \"with\" \\\\ slashes\n\n- [link](/url 'title') ![img](/i.png)\n- <a@b.c> `code` <b>\n\n---\n\n    indented\n\n```rust\nfn main() {}\n```\n\n<div>\nhtml\n</div>\n\nsoft\nhard  \nbreaks **strong** é \u{1F600}\t",
+  );
+  let json = document.to_json();
+  assert_eq!(Document::from_json(&json), Ok(document));
+}
+
+#[test]
+fn round_trips_tables() {
+  let document = parse_with_options(
+    "| a | *b* |\n| :- | -: |\n| c |\n| d | e | f |",
+    &ParseOptions::default().tables(true),
+  );
+  let json = document.to_json();
+  assert!(json.contains(r#""alignments":["left","right"]"#));
+  assert_eq!(Document::from_json(&json), Ok(document));
+  assert_eq!(
+    Document::from_json(
+      r#"{"type": "document", "blocks": [{"type": "table", "alignments": ["none"], "header": {"type": "table_row", "children": []}, "rows": []}]}"#
+    ),
+    Err(JsonError::Schema(
+      "table rows need one cell for each alignment".to_string()
+    ))
+  );
+}
+
+#[test]
+fn round_trips_task_list_items() {
+  let document = parse_with_options(
+    "- [x] done\n- [ ] to do\n- not a task",
+    &ParseOptions::default().tasklists(true),
+  );
+  let json = document.to_json();
+  assert!(json.contains(r#""checked":true"#) && json.contains(r#""checked":false"#));
+  assert_eq!(Document::from_json(&json), Ok(document));
+}
+
+#[test]
+fn reads_handwritten_json() {
+  // Spans can be left out, and whitespace and escapes are allowed
+  let json = r#"
+    {
+      "type": "document",
+      "blocks": [
+        {"type": "paragraph", "children": [{"type": "text", "content": "café 😀 \"quoted\""}]},
+        {"type": "list", "list_type": "star", "start": null, "loose": true, "children": []}
+      ]
+    }
+  "#;
+  assert_eq!(
+    Document::from_json(json).unwrap().as_html(),
+    "<p>café 😀 &quot;quoted&quot;</p>\n<ul>\n\n</ul>"
+  );
+}
+
+#[test]
+fn reports_errors() {
+  assert_eq!(
+    Document::from_json(r#"{"type": "document", "blocks": [}"#),
+    Err(JsonError::Syntax {
+      offset: 32,
+      message: "expected a value".to_string()
+    })
+  );
+  assert_eq!(
+    Document::from_json(r#"{"type": "document", "blocks": [{"type": "footnote"}]}"#),
+    Err(JsonError::Schema(
+      "unknown block type `footnote`".to_string()
+    ))
+  );
+  assert_eq!(
+    Document::from_json(r#"{"type": "document", "blocks": [{"type": "heading", "level": 7}]}"#),
+    Err(JsonError::Schema(
+      "heading level 7 isn't from 1 to 6".to_string()
+    ))
+  );
+  assert_eq!(
+    Document::from_json(r#"{"type": "document"}"#),
+    Err(JsonError::Schema("missing `blocks`".to_string()))
+  );
+}
+
+#[test]
+fn rejects_trailing_input() {
+  assert!(value::parse("[] []").is_err());
+  assert!(value::parse("\"unterminated").is_err());
+}
+
+#[test]
+fn rejects_unsupported_numbers() {
+  for number in ["1.5", "-1", "1e3"] {
+    assert_eq!(
+      value::parse(number),
+      Err(JsonError::Syntax {
+        offset: number.len() - 2,
+        message: "only whole, positive numbers are supported".to_string()
+      })
+    );
+  }
+}
+
+#[test]
+fn rejects_deep_nesting() {
+  let json = "[".repeat(100_000);
+  assert_eq!(
+    value::parse(&json),
+    Err(JsonError::Syntax {
+      offset: 256,
+      message: "arrays and objects are nested too deeply".to_string()
+    })
+  );
+  assert!(Document::from_json(&json).is_err());
+  assert!(value::parse(&format!("{}{}", "[".repeat(256), "]".repeat(256))).is_ok());
+}
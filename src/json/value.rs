@@ -0,0 +1,329 @@
+use super::JsonError;
+
+/// A parsed JSON value. Numbers are only ever used for spans, levels and list starts, so only whole numbers are accepted
+#[derive(Debug, PartialEq, Clone)]
+pub enum Value {
+  Null,
+  Bool(bool),
+  Number(usize),
+  String(String),
+  Array(Vec<Value>),
+  Object(Object),
+}
+
+impl Value {
+  fn kind(&self) -> &'static str {
+    match self {
+      Value::Null => "null",
+      Value::Bool(..) => "a boolean",
+      Value::Number(..) => "a number",
+      Value::String(..) => "a string",
+      Value::Array(..) => "an array",
+      Value::Object(..) => "an object",
+    }
+  }
+
+  /// Unwraps an object, with `expected` describing what it should have been in the error if it isn't one
+  pub fn into_object(self, expected: &str) -> Result<Object, JsonError> {
+    match self {
+      Value::Object(object) => Ok(object),
+      other => Err(JsonError::Schema(format!(
+        "expected {expected} to be an object, found {}",
+        other.kind()
+      ))),
+    }
+  }
+}
+
+/// The fields of a JSON object, which are taken out one by one as a node is read
+#[derive(Debug, PartialEq, Clone, Default)]
+pub struct Object(Vec<(String, Value)>);
+
+impl Object {
+  pub fn take_optional(&mut self, key: &str) -> Option<Value> {
+    let index = self.0.iter().position(|(name, _)| name == key)?;
+    Some(self.0.remove(index).1)
+  }
+
+  pub fn take(&mut self, key: &str) -> Result<Value, JsonError> {
+    self
+      .take_optional(key)
+      .ok_or_else(|| JsonError::Schema(format!("missing `{key}`")))
+  }
+
+  fn wrong_type(key: &str, expected: &str, found: &Value) -> JsonError {
+    JsonError::Schema(format!(
+      "expected `{key}` to be {expected}, found {}",
+      found.kind()
+    ))
+  }
+
+  pub fn take_string(&mut self, key: &str) -> Result<String, JsonError> {
+    match self.take(key)? {
+      Value::String(string) => Ok(string),
+      other => Err(Self::wrong_type(key, "a string", &other)),
+    }
+  }
+
+  /// Takes a string which can be `null` or left out
+  pub fn take_optional_string(&mut self, key: &str) -> Result<Option<String>, JsonError> {
+    match self.take_optional(key) {
+      Some(Value::String(string)) => Ok(Some(string)),
+      Some(Value::Null) | None => Ok(None),
+      Some(other) => Err(Self::wrong_type(key, "a string or null", &other)),
+    }
+  }
+
+  pub fn take_number(&mut self, key: &str) -> Result<usize, JsonError> {
+    match self.take(key)? {
+      Value::Number(number) => Ok(number),
+      other => Err(Self::wrong_type(key, "a number", &other)),
+    }
+  }
+
+  /// Takes a number which can be `null` or left out
+  pub fn take_optional_number(&mut self, key: &str) -> Result<Option<usize>, JsonError> {
+    match self.take_optional(key) {
+      Some(Value::Number(number)) => Ok(Some(number)),
+      Some(Value::Null) | None => Ok(None),
+      Some(other) => Err(Self::wrong_type(key, "a number or null", &other)),
+    }
+  }
+
+  pub fn take_bool(&mut self, key: &str) -> Result<bool, JsonError> {
+    match self.take(key)? {
+      Value::Bool(bool) => Ok(bool),
+      other => Err(Self::wrong_type(key, "a boolean", &other)),
+    }
+  }
+
+  /// Takes a boolean which can be `null` or left out
+  pub fn take_optional_bool(&mut self, key: &str) -> Result<Option<bool>, JsonError> {
+    match self.take_optional(key) {
+      Some(Value::Bool(bool)) => Ok(Some(bool)),
+      Some(Value::Null) | None => Ok(None),
+      Some(other) => Err(Self::wrong_type(key, "a boolean or null", &other)),
+    }
+  }
+
+  /// Checks the object's `type` is `expected`
+  pub fn expect_type(&mut self, expected: &str) -> Result<(), JsonError> {
+    match self.take_string("type")? {
+      found if found == expected => Ok(()),
+      found => Err(JsonError::Schema(format!(
+        "expected a `{expected}`, found a `{found}`"
+      ))),
+    }
+  }
+}
+
+/// How deeply arrays and objects can be nested. Values are parsed recursively, so anything deeper is rejected rather
+/// than risking overflowing the stack
+const MAX_DEPTH: usize = 256;
+
+/// Parses `json` as a single value, allowing whitespace around it
+pub fn parse(json: &str) -> Result<Value, JsonError> {
+  let mut parser = Parser {
+    json,
+    position: 0,
+    depth: 0,
+  };
+  let value = parser.value()?;
+  parser.skip_whitespace();
+  match parser.position < json.len() {
+    true => Err(parser.error("unexpected characters after the value")),
+    false => Ok(value),
+  }
+}
+
+struct Parser<'a> {
+  json: &'a str,
+  position: usize,
+  /// The number of arrays and objects that have been opened but not closed
+  depth: usize,
+}
+
+impl Parser<'_> {
+  fn error(&self, message: &str) -> JsonError {
+    JsonError::Syntax {
+      offset: self.position,
+      message: message.to_string(),
+    }
+  }
+
+  fn peek(&self) -> Option<u8> {
+    self.json.as_bytes().get(self.position).copied()
+  }
+
+  fn skip_whitespace(&mut self) {
+    while let Some(b' ' | b'\t' | b'\n' | b'\r') = self.peek() {
+      self.position += 1;
+    }
+  }
+
+  /// Moves past `expected` if it comes next
+  fn eat(&mut self, expected: u8) -> bool {
+    self.skip_whitespace();
+    let found = self.peek() == Some(expected);
+    if found {
+      self.position += 1;
+    }
+    found
+  }
+
+  fn expect(&mut self, expected: u8, message: &str) -> Result<(), JsonError> {
+    match self.eat(expected) {
+      true => Ok(()),
+      false => Err(self.error(message)),
+    }
+  }
+
+  fn keyword(&mut self, keyword: &str, value: Value) -> Result<Value, JsonError> {
+    match self.json[self.position..].starts_with(keyword) {
+      true => {
+        self.position += keyword.len();
+        Ok(value)
+      }
+      false => Err(self.error("expected a value")),
+    }
+  }
+
+  fn value(&mut self) -> Result<Value, JsonError> {
+    self.skip_whitespace();
+    match self.peek() {
+      Some(b'{' | b'[') => {
+        if self.depth == MAX_DEPTH {
+          return Err(self.error("arrays and objects are nested too deeply"));
+        }
+        self.depth += 1;
+        let value = match self.peek() {
+          Some(b'{') => self.object(),
+          _ => self.array(),
+        };
+        self.depth -= 1;
+        value
+      }
+      Some(b'"') => Ok(Value::String(self.string()?)),
+      Some(b'0'..=b'9' | b'-') => self.number(),
+      Some(b't') => self.keyword("true", Value::Bool(true)),
+      Some(b'f') => self.keyword("false", Value::Bool(false)),
+      Some(b'n') => self.keyword("null", Value::Null),
+      Some(_) => Err(self.error("expected a value")),
+      None => Err(self.error("unexpected end of input")),
+    }
+  }
+
+  fn object(&mut self) -> Result<Value, JsonError> {
+    self.position += 1;
+    let mut fields = vec![];
+    if self.eat(b'}') {
+      return Ok(Value::Object(Object(fields)));
+    }
+    loop {
+      self.skip_whitespace();
+      if self.peek() != Some(b'"') {
+        return Err(self.error("expected a key"));
+      }
+      let key = self.string()?;
+      self.expect(b':', "expected `:` after a key")?;
+      fields.push((key, self.value()?));
+      if self.eat(b'}') {
+        return Ok(Value::Object(Object(fields)));
+      }
+      self.expect(b',', "expected `,` or `}`")?;
+    }
+  }
+
+  fn array(&mut self) -> Result<Value, JsonError> {
+    self.position += 1;
+    let mut items = vec![];
+    if self.eat(b']') {
+      return Ok(Value::Array(items));
+    }
+    loop {
+      items.push(self.value()?);
+      if self.eat(b']') {
+        return Ok(Value::Array(items));
+      }
+      self.expect(b',', "expected `,` or `]`")?;
+    }
+  }
+
+  /// Only non-negative whole numbers are needed, so anything else is rejected
+  fn number(&mut self) -> Result<Value, JsonError> {
+    let start = self.position;
+    while let Some(b'0'..=b'9') = self.peek() {
+      self.position += 1;
+    }
+    if let Some(b'-' | b'.' | b'e' | b'E') = self.peek() {
+      return Err(self.error("only whole, positive numbers are supported"));
+    }
+    self.json[start..self.position]
+      .parse()
+      .map(Value::Number)
+      .map_err(|_| JsonError::Syntax {
+        offset: start,
+        message: "number is too large".to_string(),
+      })
+  }
+
+  fn string(&mut self) -> Result<String, JsonError> {
+    self.position += 1;
+    let mut string = String::new();
+    loop {
+      let Some(char) = self.json[self.position..].chars().next() else {
+        return Err(self.error("unterminated string"));
+      };
+      self.position += char.len_utf8();
+      match char {
+        '"' => return Ok(string),
+        '\\' => string.push(self.escape()?),
+        char if char.is_control() => return Err(self.error("control characters must be escaped")),
+        char => string.push(char),
+      }
+    }
+  }
+
+  fn escape(&mut self) -> Result<char, JsonError> {
+    let Some(char) = self.peek() else {
+      return Err(self.error("unterminated string"));
+    };
+    self.position += 1;
+    Ok(match char {
+      b'"' => '"',
+      b'\\' => '\\',
+      b'/' => '/',
+      b'b' => '\u{8}',
+      b'f' => '\u{c}',
+      b'n' => '\n',
+      b'r' => '\r',
+      b't' => '\t',
+      b'u' => {
+        let high = self.hex_escape()?;
+        // Characters outside the basic plane are written as a pair of surrogates
+        if (0xD800..0xDC00).contains(&high) && self.json[self.position..].starts_with("\\u") {
+          self.position += 2;
+          let low = self.hex_escape()?;
+          match (0xDC00..0xE000).contains(&low) {
+            true => char::from_u32(0x10000 + ((high - 0xD800) << 10) + (low - 0xDC00)),
+            false => None,
+          }
+          .unwrap_or(char::REPLACEMENT_CHARACTER)
+        } else {
+          char::from_u32(high).unwrap_or(char::REPLACEMENT_CHARACTER)
+        }
+      }
+      _ => return Err(self.error("unknown escape")),
+    })
+  }
+
+  fn hex_escape(&mut self) -> Result<u32, JsonError> {
+    let hex = self
+      .json
+      .get(self.position..self.position + 4)
+      .filter(|hex| hex.chars().all(|char| char.is_ascii_hexdigit()))
+      .ok_or_else(|| self.error("expected 4 hex digits"))?;
+    self.position += 4;
+    Ok(u32::from_str_radix(hex, 16).unwrap_or_default())
+  }
+}
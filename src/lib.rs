@@ -1,3 +1,16 @@
+pub mod json;
 mod parse;
+mod render;
+mod span;
 mod tokeniser;
-pub use parse::parse;
+pub mod visit;
+pub use json::JsonError;
+pub use parse::{
+  parse, parse_with_options, Alignment, Block, BlockType, DocContext, Document, Inline, ListType,
+  ParseOptions, RenderOptions, SoftBreak, TableRow,
+};
+pub use render::{
+  CommonMarkRenderer, HtmlRenderer, Renderer, Sanitiser, TextRenderer, XmlRenderer,
+};
+pub use span::{Position, Span, Spanned};
+pub use visit::{Fold, Visitor};
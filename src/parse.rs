@@ -1,31 +1,58 @@
 mod blocks;
 mod document;
-mod helpers;
+pub(crate) mod entities;
+pub(crate) mod helpers;
+mod html;
 mod inlines;
 mod line;
 mod list;
+mod options;
+mod references;
+mod table;
 
 use crate::{
-  parse::list::list_item_content_start,
+  parse::list::{list_item_content_start, list_item_content_width},
+  span::{Span, Spanned},
   tokeniser::{tokenise, Token},
 };
 
 use self::{
+  helpers::{new_line_after, should_recognise_blank_lines},
+  html::html_block_ends,
+  inlines::InlineParser,
+  line::Line,
+};
+
+pub use self::{
   blocks::{Block, BlockType},
   document::{DocContext, Document},
-  helpers::should_recognise_blank_lines,
   inlines::Inline,
-  line::Line,
   list::ListType,
+  options::{ParseOptions, RenderOptions, SoftBreak},
+  table::{Alignment, TableRow},
 };
 
+/// Parses a markdown string into a [`Document`]
 pub fn parse(md: &str) -> Document {
+  parse_with_options(md, &ParseOptions::default())
+}
+
+/// Parses a markdown string into a [`Document`], with `options` enabling anything beyond plain CommonMark
+pub fn parse_with_options(md: &str, options: &ParseOptions) -> Document {
   let tokens = tokenise(md);
-  parse_tokens(tokens)
+  parse_tokens(tokens, options)
 }
 
-pub fn parse_tokens(tokens: Vec<Token>) -> Document {
+pub(crate) fn parse_tokens(tokens: Vec<Spanned<Token>>, options: &ParseOptions) -> Document {
   let mut document = Document::new();
+  document.context.options = options.clone();
+
+  // Link reference definitions can come after the links that use them,
+  // so the document is first scanned to collect every definition
+  document.context.collecting_references = true;
+  parse_tokens_with_context(&tokens, &mut document.context);
+  document.context.collecting_references = false;
+
   for block in parse_tokens_with_context(&tokens, &mut document.context) {
     document.add_block(block);
   }
@@ -33,50 +60,79 @@ pub fn parse_tokens(tokens: Vec<Token>) -> Document {
 }
 
 pub(crate) fn parse_tokens_with_context(
-  tokens: &Vec<Token>,
+  tokens: &[Spanned<Token>],
   context: &mut DocContext,
-) -> Vec<Block> {
+) -> Vec<Spanned<Block>> {
   let mut current_block_type: Option<BlockType> = None;
   let lines: &mut Vec<Line> = &mut tokens_to_lines(tokens);
-  let mut blocks: Vec<Block> = vec![];
-  let mut current_block: Vec<Token> = vec![];
+  let mut blocks: Vec<Spanned<Block>> = vec![];
+  let mut current_block: Vec<Spanned<Token>> = vec![];
+  // The lines making up the current block, including the markers that are removed from its tokens
+  let mut block_span: Option<Span> = None;
   let mut continued_content_start = 0;
+  // The column the content of the current list item starts at, which may differ from its start in tokens
+  let mut continued_content_width = 0;
   let mut last_line_empty = false;
+  // The last line of the current paragraph, which becomes the header row if the line after it is a table delimiter row
+  let mut table_header: Option<Line> = None;
 
   // Needed for lists
   let mut count = 0;
 
-  for (_i, line) in lines.iter_mut().enumerate() {
+  for line in lines.iter_mut() {
     // Reset Block Type
-    if current_block_type == Some(BlockType::Paragraph) && current_block.len() == 0 {
+    if current_block_type == Some(BlockType::Paragraph) && current_block.is_empty() {
       current_block_type = None;
       continued_content_start = 0;
+      continued_content_width = 0;
+      block_span = None;
     }
 
     let currently_in_list = matches!(current_block_type, Some(BlockType::List(..)));
     let blank_space = line.unindented_leading_spaces();
-    line.trim_line_start(blank_space); // Remove up to 3 leading spaces before we grab the line_type
-    let mut new_block_type = line.line_type(current_block_type);
+    // Remove up to 3 leading spaces before we grab the line_type, keeping them for blocks that need them back
+    let mut indentation: Vec<Spanned<Token>> = line.0.drain(..blank_space).collect();
+
+    // A closing fence ends a fenced code block without being part of it
+    if current_block_type.is_some_and(|block_type| line.is_closing_fence(&block_type)) {
+      block_span = join_spans(block_span, line.span());
+      blocks.push(finish_block(
+        current_block_type.unwrap(),
+        current_block,
+        block_span.take(),
+        context,
+      ));
+      current_block = vec![];
+      current_block_type = None;
+      continue;
+    }
 
+    let mut new_block_type = line.line_type(current_block_type, table_header.take().as_ref());
+
+    // Lines indented as far as the content of the current list item carry on the item, whatever block they start
+    let continues_item =
+      currently_in_list && !line.is_empty() && blank_space >= continued_content_width;
+    if continues_item {
+      new_block_type = current_block_type.unwrap();
+    }
     // if this line is still a list without the previous line, then that proves a new line item
     // and we should grab the point where the content starts due to how lists are handled
-    if matches!(line.line_type(None), BlockType::List(..)) {
+    else if matches!(line.line_type(None, None), BlockType::List(..)) {
       continued_content_start = list_item_content_start(line) + blank_space;
+      continued_content_width = list_item_content_width(line) + blank_space;
     }
 
     let mut handle_as_empty = false;
 
-    if current_block_type.is_some() {
-      let current_block_type = current_block_type.unwrap();
-
+    if let Some(current_block_type) = current_block_type {
       // Some special list based handling
       if current_block_type == new_block_type && currently_in_list {
         // if the line doesn't have enough indentation and this is a list, don't include it as part of the line item if it is preceded by a new line
-        if line.is_empty() == false
+        if !line.is_empty()
           && last_line_empty
           && list_item_content_start(line) + blank_space < continued_content_start
         {
-          new_block_type = line.line_type(None);
+          new_block_type = line.line_type(None, None);
         }
         // if this is a numbered list, the count needs to be checked as otherwise it should become 2 lists
         else if is_num_list_continuation(&current_block_type, &new_block_type, count) {
@@ -87,9 +143,9 @@ pub(crate) fn parse_tokens_with_context(
 
       // Has the previous block concluded
       handle_as_empty = should_recognise_blank_lines(current_block_type) && line.is_empty();
-      let doesent_match = new_block_type.allow_takeover(current_block_type) == false
-        && new_block_type != current_block_type;
-      let has_enough_to_push = current_block_type.allow_no_content() || current_block.len() > 0;
+      let doesent_match =
+        !new_block_type.allow_takeover(current_block_type) && new_block_type != current_block_type;
+      let has_enough_to_push = current_block_type.allow_no_content() || !current_block.is_empty();
       let should_terminate = handle_as_empty || doesent_match;
 
       // track if this line was empty for stacking purposes
@@ -97,15 +153,39 @@ pub(crate) fn parse_tokens_with_context(
 
       // terminate existing block
       if has_enough_to_push && should_terminate {
-        blocks.push(Block::new(current_block_type, current_block, context));
-        new_block_type = line.line_type(None); // This isn't a continuation, rethink the line type
+        blocks.push(finish_block(
+          current_block_type,
+          current_block,
+          block_span.take(),
+          context,
+        ));
+        new_block_type = line.line_type(None, None); // This isn't a continuation, rethink the line type
         current_block = vec![];
         count = 0;
       }
     }
 
+    // The header row of a table is only the last line of the paragraph before it, so any lines above it stay a paragraph
+    if new_block_type == BlockType::Table && current_block_type == Some(BlockType::Paragraph) {
+      let header_start = current_block
+        .iter()
+        .rposition(|token| token == &Token::NewLine)
+        .map_or(0, |end| end + 1);
+      let header = current_block.split_off(header_start);
+      if current_block.pop().is_some() {
+        blocks.push(finish_block(
+          BlockType::Paragraph,
+          current_block,
+          None,
+          context,
+        ));
+      }
+      block_span = Span::join_all(header.iter().map(|token| &token.span));
+      current_block = header;
+    }
+
     // Special handling for Setext Headers if the line before is empty
-    if current_block.len() == 0 {
+    if current_block.is_empty() {
       new_block_type = match new_block_type {
         BlockType::SetextHeader(1) => BlockType::Paragraph,
         BlockType::SetextHeader(2) => BlockType::ThematicBreak,
@@ -114,48 +194,124 @@ pub(crate) fn parse_tokens_with_context(
     }
 
     // Now that we're sure of the block type, we can remove the line type indicators
+    if !handle_as_empty && !line.is_empty() {
+      block_span = join_spans(block_span, line.span());
+    }
     line.remove_type_chars(&new_block_type);
 
     // Begin building new block
     if Some(new_block_type) != current_block_type {
+      // Fenced code blocks need to know the indentation of their opening fence to remove it from their content
+      if let BlockType::FencedCodeBlock { indent, .. } = &mut new_block_type {
+        *indent = blank_space;
+      }
       current_block_type = Some(new_block_type)
     }
 
-    if handle_as_empty == false {
+    if !handle_as_empty {
       // If this is not the first line within this block, add a new line
-      if current_block.len() > 0 {
-        current_block.push(Token::NewLine);
+      if !current_block.is_empty() {
+        current_block.push(new_line_after(&current_block));
       }
 
       // If this is a list we want to add our indentation back in
       if matches!(current_block_type, Some(BlockType::List(..))) {
-        let mut fixed_line = vec![Token::Space; blank_space];
-        fixed_line.append(&mut line.0);
-        line.0 = fixed_line;
+        line.0.splice(0..0, indentation.drain(..));
+      }
+
+      // Lines of fenced code keep any indentation beyond that of the opening fence
+      if let Some(BlockType::FencedCodeBlock { indent, .. }) = current_block_type {
+        if !current_block.is_empty() {
+          indentation.extend(line.0.drain(..line.leading_spaces()));
+          let removed = indent.min(indentation.len());
+          line.0.splice(0..0, indentation.drain(removed..));
+        }
+      }
+
+      // HTML keeps its indentation as it is passed through verbatim
+      if matches!(current_block_type, Some(BlockType::HtmlBlock(..))) {
+        line.0.splice(0..0, indentation.drain(..));
+      }
+
+      // HTML blocks of type 1-5 end on the line which contains their end condition
+      let ends_html_block = match current_block_type {
+        Some(BlockType::HtmlBlock(kind)) => html_block_ends(kind, &line.source()),
+        _ => false,
+      };
+
+      if context.options.tables && current_block_type == Some(BlockType::Paragraph) {
+        table_header = Some(line.clone());
       }
 
       // Add the line to our current block
       current_block.append(&mut line.0);
+
+      if ends_html_block {
+        blocks.push(finish_block(
+          current_block_type.unwrap(),
+          current_block,
+          block_span.take(),
+          context,
+        ));
+        current_block = vec![];
+        current_block_type = None;
+      }
     }
   }
 
+  // An unclosed fence (or HTML block) runs to the end of its container, which leaves the line ending of the final line behind
+  if matches!(
+    current_block_type,
+    Some(BlockType::FencedCodeBlock { .. } | BlockType::HtmlBlock(..))
+  ) && current_block
+    .last()
+    .is_some_and(|token| token == &Token::NewLine)
+  {
+    current_block.pop();
+  }
+
   // If this block type can be completely empty and you need to, push the empty block
-  if current_block_type.is_some_and(|bt| bt.allow_no_content()) || current_block.len() > 0 {
-    blocks.push(Block::new(
+  if current_block_type.is_some_and(|bt| bt.allow_no_content()) || !current_block.is_empty() {
+    blocks.push(finish_block(
       current_block_type.unwrap(),
       current_block,
+      block_span,
       context,
     ));
   }
 
+  // Paragraphs made up entirely of link reference definitions produce nothing. While references are being collected
+  // every paragraph is empty, so they are kept for task list items to find their first paragraph
+  if !context.collecting_references {
+    blocks.retain(|block| !matches!(&block.value, Block::Paragraph(inlines) if inlines.is_empty()));
+  }
   blocks
 }
 
-pub fn tokens_to_lines(tokens: &Vec<Token>) -> Vec<Line> {
+/// Creates a block from its tokens. `span` covers the lines that made up the block, and
+/// is used over the span of the tokens as it includes any markers removed from the lines
+fn finish_block(
+  block_type: BlockType,
+  tokens: Vec<Spanned<Token>>,
+  span: Option<Span>,
+  context: &mut DocContext,
+) -> Spanned<Block> {
+  let mut block = Block::new(block_type, tokens, context);
+  if let Some(span) = span {
+    block.span = span;
+  }
+  block
+}
+
+fn join_spans(span: Option<Span>, other: Option<Span>) -> Option<Span> {
+  Span::join_all(span.iter().chain(other.iter()))
+}
+
+pub fn tokens_to_lines(tokens: &[Spanned<Token>]) -> Vec<Line> {
   let mut lines: Vec<Line> = vec![];
   let mut latest_line = vec![];
   for token in tokens.iter() {
-    match token {
+    match token.value {
       Token::NewLine => {
         lines.push(Line(latest_line.clone()));
         latest_line = vec![];
@@ -167,31 +323,29 @@ pub fn tokens_to_lines(tokens: &Vec<Token>) -> Vec<Line> {
     }
   }
   // If the current line length is greater than 0 then the line was never ended and needs to be pushed to the line list
-  if latest_line.len() > 0 || lines.last().is_some_and(|line| line.is_empty() == false) {
+  if !latest_line.is_empty() || lines.last().is_some_and(|line| !line.is_empty()) {
     lines.push(Line(latest_line.clone()));
   }
   lines
 }
 
-pub fn parse_inlines(tokens: &Vec<Token>, context: &mut DocContext) -> Vec<Inline> {
-  return tokens
-    .iter()
-    .map(|token| Inline::Text(token.clone().into()))
-    .collect();
+pub fn parse_inlines(tokens: &[Spanned<Token>], context: &mut DocContext) -> Vec<Spanned<Inline>> {
+  if context.collecting_references {
+    return vec![];
+  }
+  InlineParser::new(tokens, context).parse()
 }
 
 fn is_num_list_continuation(old_type: &BlockType, new_type: &BlockType, count: usize) -> bool {
   // if this is a list then co what's needed. its kinda gross but is what it is
   match old_type {
     BlockType::List(ListType::Number(start)) => match new_type {
-      BlockType::List(ListType::Number(expected_count)) => {
-        return *expected_count == start + count + 1;
-      }
+      BlockType::List(ListType::Number(expected_count)) => *expected_count == start + count + 1,
       _ => false,
     },
     BlockType::List(ListType::BracketedNumber(start)) => match new_type {
       BlockType::List(ListType::BracketedNumber(expected_count)) => {
-        return *expected_count == start + count + 1;
+        *expected_count == start + count + 1
       }
       _ => false,
     },
@@ -1,18 +1,22 @@
-use std::usize;
-
-use crate::{parse::tokens_to_lines, tokeniser::Token};
+use crate::{
+  parse::tokens_to_lines,
+  span::{Span, Spanned},
+  tokeniser::Token,
+};
 
 use super::{
   document::DocContext,
-  helpers::trim_empty_lines,
-  inlines::Inline,
-  list::{parse_line_items, ListType},
+  helpers::{end_position, trim_empty_lines},
+  inlines::{unescape, Inline},
+  list::{parse_line_items, strip_task_marker, ListType},
   parse_inlines, parse_tokens_with_context,
+  references::extract_link_references,
+  table::{parse_table_row, Alignment, TableRow},
 };
 
-/// Types of block that appear in the stack
+/// Types of block that appear in the stack while parsing
 #[derive(Debug, PartialEq, Copy, Clone)]
-pub(crate) enum BlockType {
+pub enum BlockType {
   Paragraph,
   BlockQuote,
   List(ListType),
@@ -25,50 +29,110 @@ pub(crate) enum BlockType {
   /// Setext Header is a special case as it becomes a regular header once generated
   SetextHeader(u8),
   IndentedCodeBlock,
+  /// A code block opened by a run of at least 3 `fence` chars (`` ` `` or `~`). `indent` is the indentation of the opening fence
+  FencedCodeBlock {
+    fence: char,
+    length: usize,
+    indent: usize,
+  },
+  /// An [HTML block](https://spec.commonmark.org/0.31.2/#html-blocks) of the given kind (1-7), which decides how it ends
+  HtmlBlock(u8),
+  /// A [GFM table](https://github.github.com/gfm/#tables-extension-), only found when [`ParseOptions::tables`] is set.
+  /// It starts with the delimiter row, which turns the paragraph line before it into the header row
+  ///
+  /// [`ParseOptions::tables`]: crate::ParseOptions::tables
+  Table,
 }
 
 impl BlockType {
   /// Can this block have no content?
-  pub fn allow_no_content(&self) -> bool {
+  pub(crate) fn allow_no_content(&self) -> bool {
     match self {
       Self::Header(_) => true,
+      Self::BlockQuote => true,
       Self::SetextHeader(_) => true, // In block creation we'll switch this into a ThematicBreak
       _ => false,
     }
   }
 
   /// Can this block take over the passed block if after it in the stack
-  pub fn allow_takeover(&self, block_type: BlockType) -> bool {
+  pub(crate) fn allow_takeover(&self, block_type: BlockType) -> bool {
     match self {
-      Self::SetextHeader(_) => block_type == BlockType::Paragraph,
+      Self::SetextHeader(_) | Self::Table => block_type == BlockType::Paragraph,
       _ => false,
     }
   }
 }
 
 /// A renderable Block of content
-#[derive(Debug, PartialEq)]
-pub(crate) enum Block {
-  Paragraph(Vec<Inline>),
-  BlockQuote(Vec<Block>),
+#[derive(Debug, PartialEq, Clone)]
+#[allow(clippy::enum_variant_names)]
+pub enum Block {
+  /// A paragraph of inline content
+  Paragraph(Vec<Spanned<Inline>>),
+  /// A block quote, which can hold any other blocks
+  BlockQuote(Vec<Spanned<Block>>),
+  /// A list, where `inner` is made up of [`Block::LineItem`]s.
+  ///
+  /// Items of a `loose` list have their paragraphs wrapped in `<p>` tags
   List {
     list_type: ListType,
-    inner: Vec<Block>,
+    inner: Vec<Spanned<Block>>,
     loose: bool,
   },
+  /// An item in a list. `checked` is set for [task list items](crate::ParseOptions::tasklists),
+  /// which are shown with a checkbox
   LineItem {
-    inner: Vec<Block>,
+    inner: Vec<Spanned<Block>>,
+    checked: Option<bool>,
   },
+  /// An `<hr>` tag
   ThematicBreak,
-  Header(u8, Vec<Inline>),
+  /// A header with its level from 1-6. Setext headers become regular headers
+  Header(u8, Vec<Spanned<Inline>>),
+  /// A code block indented by 4 spaces, holding its literal content
   IndentedCodeBlock(String),
+  /// A fenced code block. `info` is the text following the opening fence, `content` includes the final line ending
+  FencedCodeBlock { info: String, content: String },
+  /// Raw HTML which is passed through as is
+  HtmlBlock(String),
+  /// A table with the alignment of each of its columns. Every row has one cell for each column
+  Table {
+    alignments: Vec<Alignment>,
+    header: Spanned<TableRow>,
+    rows: Vec<Spanned<TableRow>>,
+  },
 }
 
 impl Block {
-  /// Creates a new renderable block after calling the correct parse method on inner tokens for that block
-  pub fn new(block_type: BlockType, inner: Vec<Token>, context: &mut DocContext) -> Block {
+  /// Creates a new renderable block after calling the correct parse method on inner tokens for that block.
+  ///
+  /// The block is given the span of its inner tokens, which won't include any markers already removed from them
+  pub(crate) fn new(
+    block_type: BlockType,
+    inner: Vec<Spanned<Token>>,
+    context: &mut DocContext,
+  ) -> Spanned<Block> {
+    let span = Span::join_all(
+      inner
+        .iter()
+        .map(|token| &token.span)
+        .filter(|span| !span.is_empty()),
+    )
+    .unwrap_or_else(|| Span::empty(end_position(&inner)));
+    Spanned::new(Block::from_tokens(block_type, inner, context), span)
+  }
+
+  fn from_tokens(
+    block_type: BlockType,
+    inner: Vec<Spanned<Token>>,
+    context: &mut DocContext,
+  ) -> Block {
     match block_type {
-      BlockType::Paragraph => Block::Paragraph(parse_inlines(&inner, context)),
+      BlockType::Paragraph => {
+        let inner = extract_link_references(&inner, context);
+        Block::Paragraph(parse_inlines(&inner, context))
+      }
       BlockType::BlockQuote => Block::BlockQuote(parse_tokens_with_context(&inner, context)),
       BlockType::List(list_type) => {
         let inner_blocks = parse_line_items(&inner, context, list_type);
@@ -77,29 +141,45 @@ impl Block {
           .iter()
           .position(|line| line.is_empty())
           .is_some_and(|pos| pos + 1 != lines.len()); // there is an edge case where the last line is accidentally a new line, this is not a loose paragraph
-        println!("line: \"{inner:?}\" is looks - {loose}");
         Block::List {
           list_type,
           inner: inner_blocks,
           loose,
         }
       }
-      BlockType::LineItem => Block::LineItem {
-        inner: parse_tokens_with_context(&inner, context),
-      },
+      BlockType::LineItem => {
+        let mut blocks = parse_tokens_with_context(&inner, context);
+        let checked = match context.options.tasklists {
+          true => strip_task_marker(&inner, &mut blocks, context),
+          false => None,
+        };
+        Block::LineItem {
+          inner: blocks,
+          checked,
+        }
+      }
       BlockType::ThematicBreak => Block::ThematicBreak,
       BlockType::Header(level) => Block::Header(level, parse_inlines(&inner, context)),
       BlockType::SetextHeader(level) => {
         // Due to weirdness with parsing where until it hit the Setext Line, this was a paragraph,
         // there is a chance that a new line token made it through. It should be nipped in the bud here
         let mut true_inner = inner.clone();
-        if true_inner.last() == Some(&Token::NewLine) {
+        if true_inner
+          .last()
+          .is_some_and(|token| token == &Token::NewLine)
+        {
           true_inner.pop();
         }
 
         // This is a catch for when a thematic break is wrongly parsed as a setext header
-        if true_inner.len() == 0 {
-          return Block::new(BlockType::ThematicBreak, true_inner, context);
+        if true_inner.is_empty() {
+          return Block::from_tokens(BlockType::ThematicBreak, true_inner, context);
+        }
+
+        // The header's content may begin with link reference definitions, which can leave nothing behind
+        let true_inner = extract_link_references(&true_inner, context);
+        if true_inner.is_empty() {
+          return Block::Paragraph(vec![]);
         }
         Block::Header(level, parse_inlines(&true_inner, context))
       }
@@ -110,69 +190,51 @@ impl Block {
           .collect::<Vec<String>>()
           .join(""),
       ),
-    }
-  }
-
-  /// Convert a Renderable Block to HTML
-  ///
-  /// `loose_mode` should almost always be `true` as it determins if `<p>` tags
-  /// should be rendered or just spat out as plain text (as is required in lists at times)
-  pub fn as_html(&self, loose_mode: bool) -> String {
-    match self {
-      Block::Paragraph(inlines) => match loose_mode {
-        true => format!("<p>{}</p>", Inline::vec_as_html(inlines)),
-        false => Inline::vec_as_html(inlines),
-      },
-      Block::BlockQuote(blocks) => {
-        format!(
-          "<blockquote>\n{}\n</blockquote>",
-          Block::vec_as_html(blocks, true)
-        )
-      }
-      Block::List {
-        list_type,
-        inner,
-        loose,
-      } => {
-        let mut start = String::new();
-        let list_name = match list_type {
-          ListType::Number(start_num) => {
-            if *start_num != 1 {
-              start = format!(" start=\"{start_num}\"");
-            }
-            "ol"
-          }
-          _ => "ul",
-        };
-        format!(
-          "<{list_name}{start}>\n{}\n</{list_name}>",
-          Block::vec_as_html(inner, *loose)
-        )
+      BlockType::FencedCodeBlock { length, .. } => {
+        // The first line is always the opening fence, which leaves the info string after the fence chars
+        let lines = tokens_to_lines(&inner);
+        let info = lines.first().map_or(String::new(), |line| {
+          let fence: String = line.0[length..]
+            .iter()
+            .map(|token| String::from(token.clone()))
+            .collect();
+          unescape(fence.trim())
+        });
+        let content = lines
+          .iter()
+          .skip(1)
+          .map(|line| {
+            let mut text: String = line
+              .0
+              .iter()
+              .map(|token| String::from(token.clone()))
+              .collect();
+            text.push('\n');
+            text
+          })
+          .collect();
+        Block::FencedCodeBlock { info, content }
       }
-      Block::LineItem { inner, .. } => {
-        let mut blocks = Block::vec_as_html(inner, loose_mode);
-        if loose_mode == false && matches!(inner[0], Block::Paragraph(..)) {
-          if matches!(inner.last(), Some(Block::List { .. })) {
-            blocks += "\n";
-          }
-          return format!("<li>{blocks}</li>");
+      BlockType::Table => {
+        // The first line is the header and the second is the delimiter row, with every other line being a row
+        let lines = tokens_to_lines(&inner);
+        let columns = lines[0].table_cells().len();
+        let alignments = lines[1].table_alignments(columns).unwrap_or_default();
+        Block::Table {
+          alignments,
+          header: parse_table_row(&lines[0], columns, context),
+          rows: lines[2..]
+            .iter()
+            .map(|line| parse_table_row(line, columns, context))
+            .collect(),
         }
-        return format!("<li>\n{blocks}\n</li>");
       }
-      Block::ThematicBreak => "<hr />".to_string(),
-      Block::Header(level, inner) => {
-        format!("<h{level}>{}</h{level}>", Inline::vec_as_html(inner))
-      }
-      Block::IndentedCodeBlock(inner) => format!("<pre><code>{inner}\n</code></pre>"),
-    }
-  }
-
-  /// Converts an array of Renderable Blocks into a single HTML String
-  pub fn vec_as_html(blocks: &Vec<Self>, loose_mode: bool) -> String {
-    let mut html: Vec<String> = vec![];
-    for block in blocks {
-      html.push(block.as_html(loose_mode))
+      BlockType::HtmlBlock(_) => Block::HtmlBlock(
+        inner
+          .iter()
+          .map(|token| String::from(token.clone()))
+          .collect(),
+      ),
     }
-    html.join("\n")
   }
 }
@@ -1,29 +1,164 @@
-use super::{blocks::Block, inlines::Inline};
+use super::{
+  blocks::Block,
+  options::{ParseOptions, RenderOptions},
+};
+use crate::{
+  json::{self, JsonError},
+  render::{CommonMarkRenderer, HtmlRenderer, IoWriter, Renderer, TextRenderer, XmlRenderer},
+  span::Spanned,
+  visit::{fold_blocks, walk_blocks, Fold, Visitor},
+};
 
-pub struct DocContext {}
+use std::{collections::HashMap, fmt, io};
+
+/// Information gathered while parsing that is needed across the whole document
+#[derive(Debug, Default, PartialEq, Clone)]
+pub struct DocContext {
+  /// Link reference definitions, keyed by their normalised label
+  pub(crate) link_references: HashMap<String, LinkReference>,
+  /// Set while the document is first scanned for link reference definitions, as inlines aren't needed until after
+  pub(crate) collecting_references: bool,
+  pub(crate) options: ParseOptions,
+}
+
+impl DocContext {
+  /// The options the document was parsed with
+  pub fn options(&self) -> &ParseOptions {
+    &self.options
+  }
+}
+
+/// The destination and title given by a [link reference definition](https://spec.commonmark.org/0.31.2/#link-reference-definitions)
+#[derive(Debug, PartialEq, Clone)]
+pub(crate) struct LinkReference {
+  pub dest: String,
+  pub title: Option<String>,
+}
+
+/// A parsed markdown document, made up of a tree of [`Block`]s
+#[derive(Debug, Default, Clone)]
 pub struct Document {
-  blocks: Vec<Block>,
+  blocks: Vec<Spanned<Block>>,
   pub context: DocContext,
 }
 
+/// Documents are compared by their blocks alone. The context only records how they were parsed,
+/// so a document read back from JSON is equal to the one it was written from
+impl PartialEq for Document {
+  fn eq(&self, other: &Self) -> bool {
+    self.blocks == other.blocks
+  }
+}
+
 impl Document {
   pub fn new() -> Self {
     Self {
       blocks: vec![],
-      context: DocContext {},
+      context: DocContext::default(),
     }
   }
 
+  /// The top level blocks of the document
+  pub fn blocks(&self) -> &[Spanned<Block>] {
+    &self.blocks
+  }
+
+  /// Mutable access to the top level blocks, allowing the document to be transformed before it is rendered
+  pub fn blocks_mut(&mut self) -> &mut Vec<Spanned<Block>> {
+    &mut self.blocks
+  }
+
+  /// Takes the top level blocks out of the document
+  pub fn into_blocks(self) -> Vec<Spanned<Block>> {
+    self.blocks
+  }
+
+  /// Walks every block and inline in the document with `visitor`
+  pub fn visit(&self, visitor: &mut impl Visitor) {
+    walk_blocks(visitor, &self.blocks);
+  }
+
+  /// Rebuilds the document with `folder`, which may replace or remove any of its blocks and inlines
+  pub fn fold(&mut self, folder: &mut impl Fold) {
+    self.blocks = fold_blocks(folder, std::mem::take(&mut self.blocks));
+  }
+
   pub fn as_html(&self) -> String {
-    println!("final: {:?}", self.blocks);
-    let mut html: Vec<String> = vec![];
-    for block in self.blocks.iter() {
-      html.push(block.as_html(true))
-    }
-    html.join("\n")
+    self.as_html_with_options(&RenderOptions::default())
+  }
+
+  /// Converts the document to HTML, using `options` to decide how certain elements are written
+  pub fn as_html_with_options(&self, options: &RenderOptions) -> String {
+    self.render_to_string(&mut HtmlRenderer::new(options.clone()))
+  }
+
+  /// Writes the document back out as normalised markdown, which parses to the same blocks and inlines
+  pub fn as_commonmark(&self) -> String {
+    self.render_to_string(&mut CommonMarkRenderer::default())
+  }
+
+  /// Converts the document to plain text with all of its markup removed.
+  /// Use [`Document::render_to_string`] with a [`TextRenderer`] to keep the destinations of links
+  pub fn as_text(&self) -> String {
+    self.render_to_string(&mut TextRenderer::default())
+  }
+
+  /// Converts the document to CommonMark XML, which lays out its tree of blocks and inlines node by node
+  pub fn as_xml(&self) -> String {
+    self.as_xml_with_options(&RenderOptions::default())
+  }
+
+  /// Converts the document to CommonMark XML, adding `sourcepos` attributes if [`RenderOptions::sourcepos`] is set
+  pub fn as_xml_with_options(&self, options: &RenderOptions) -> String {
+    self.render_to_string(&mut XmlRenderer::new(options.clone()))
+  }
+
+  /// Converts the document to JSON following the schema given in [`crate::json`]
+  pub fn to_json(&self) -> String {
+    let mut json = String::new();
+    // Writing to a String can't fail
+    let _ = json::write_document(&mut json, self);
+    json
+  }
+
+  /// Reads a document back from JSON written by [`Document::to_json`]
+  pub fn from_json(json: &str) -> Result<Document, JsonError> {
+    json::read_document(json)
+  }
+
+  /// Streams the document as HTML to `w`, without building it up in memory first.
+  ///
+  /// Output is written a piece at a time, so `w` is best wrapped in an [`io::BufWriter`] if it isn't buffered already.
+  /// Use [`Document::render_io`] with an [`HtmlRenderer`] to choose the [`RenderOptions`]
+  pub fn write_html(&self, w: &mut impl io::Write) -> io::Result<()> {
+    self.render_io(&mut HtmlRenderer::default(), w)
+  }
+
+  /// Streams the document as HTML to a [`fmt::Write`], such as a [`fmt::Formatter`] or an existing `String`
+  pub fn write_html_fmt(&self, w: &mut impl fmt::Write) -> fmt::Result {
+    self.render(&mut HtmlRenderer::default(), w)
+  }
+
+  /// Writes the document out with `renderer`
+  pub fn render(&self, renderer: &mut impl Renderer, w: &mut impl fmt::Write) -> fmt::Result {
+    renderer.document(w, self)
+  }
+
+  /// Writes the document out with `renderer` to an [`io::Write`]
+  pub fn render_io(&self, renderer: &mut impl Renderer, w: &mut impl io::Write) -> io::Result<()> {
+    let mut writer = IoWriter::new(w);
+    let result = renderer.document(&mut writer, self);
+    writer.finish(result)
+  }
+
+  pub fn render_to_string(&self, renderer: &mut impl Renderer) -> String {
+    let mut output = String::new();
+    // Writing to a String can only fail if the renderer itself fails
+    let _ = self.render(renderer, &mut output);
+    output
   }
 
-  pub(crate) fn add_block(&mut self, block: Block) {
+  pub(crate) fn add_block(&mut self, block: Spanned<Block>) {
     self.blocks.push(block);
   }
 }
@@ -1,31 +1,35 @@
+use std::fmt::{self, Write};
+
 use super::{blocks::BlockType, line::Line, tokens_to_lines};
-use crate::parse::Token;
+use crate::{
+  span::{Position, Span, Spanned},
+  tokeniser::Token,
+};
 
 pub fn should_recognise_blank_lines(block_type: BlockType) -> bool {
-  match block_type {
-    BlockType::List { .. } => false,
-    BlockType::IndentedCodeBlock => false,
-    _ => true,
-  }
+  !matches!(
+    block_type,
+    BlockType::List { .. }
+      | BlockType::IndentedCodeBlock
+      | BlockType::FencedCodeBlock { .. }
+      | BlockType::HtmlBlock(1..=5)
+  )
 }
 
 /// Some multi line blocks need to remove internal blank lines at the start and end
 /// but need to allow them within the main body. This (rather inifficiant) method allows us to do so
-pub fn trim_empty_lines(tokens: Vec<Token>) -> Vec<Token> {
+pub fn trim_empty_lines(tokens: Vec<Spanned<Token>>) -> Vec<Spanned<Token>> {
   let mut lines: Vec<Line> = tokens_to_lines(&tokens);
 
   // get the position from the start
-  let from_start = lines
-    .iter()
-    .position(|line| line.is_empty() == false)
-    .unwrap_or(0);
+  let from_start = lines.iter().position(|line| !line.is_empty()).unwrap_or(0);
 
   // Get the position from the end
   let from_end = lines.len()
     - lines
       .iter()
       .rev()
-      .position(|line| line.is_empty() == false)
+      .position(|line| !line.is_empty())
       .unwrap_or(0);
 
   // clip the lines
@@ -33,10 +37,112 @@ pub fn trim_empty_lines(tokens: Vec<Token>) -> Vec<Token> {
 
   // Convert back into a straight token vec
   lines.iter_mut().fold(vec![], |mut col, line| {
-    if col.len() > 0 {
-      col.push(Token::NewLine);
+    if !col.is_empty() {
+      col.push(new_line_after(&col));
     }
     col.append(&mut line.0);
     col
   })
 }
+
+/// Whether `token` is a space or tab
+pub fn is_blank(token: &Spanned<Token>) -> bool {
+  matches!(token.value, Token::Space | Token::Tab)
+}
+
+/// The position just after the last of `tokens`
+pub fn end_position(tokens: &[Spanned<Token>]) -> Position {
+  tokens
+    .last()
+    .map(|token| token.span.end)
+    .unwrap_or_default()
+}
+
+/// A line ending to join lines that were split apart. It is given an empty span after the end of `tokens`
+pub fn new_line_after(tokens: &[Spanned<Token>]) -> Spanned<Token> {
+  Spanned::new(Token::NewLine, Span::empty(end_position(tokens)))
+}
+
+/// Writes `text` with the characters that have special meaning in HTML escaped
+pub fn write_escaped_html(w: &mut dyn Write, text: &str) -> fmt::Result {
+  let mut written = 0;
+  for (i, char) in text.char_indices() {
+    let escaped = match char {
+      '&' => "&amp;",
+      '<' => "&lt;",
+      '>' => "&gt;",
+      '"' => "&quot;",
+      _ => continue,
+    };
+    w.write_str(&text[written..i])?;
+    w.write_str(escaped)?;
+    written = i + 1;
+  }
+  w.write_str(&text[written..])
+}
+
+/// Splits `url` into its lowercased scheme and everything after the `:`, or `None` if it is relative.
+///
+/// Browsers ignore whitespace and control characters within a scheme, so they are removed here as well
+pub fn split_scheme(url: &str) -> Option<(String, String)> {
+  let url: String = url
+    .chars()
+    .filter(|char| !char.is_ascii_whitespace() && !char.is_control())
+    .collect();
+  // Anything before a `/`, `?` or `#` is part of the path, query or fragment of a relative URL instead
+  let end = url
+    .find([':', '/', '?', '#'])
+    .filter(|end| url[*end..].starts_with(':'))?;
+  Some((url[..end].to_ascii_lowercase(), url[end + 1..].to_string()))
+}
+
+/// Whether `url` uses a scheme that can run code or read local files when followed, as cmark's `--safe` checks for
+pub fn is_dangerous_url(url: &str) -> bool {
+  const SAFE_DATA: [&str; 4] = ["image/png", "image/gif", "image/jpeg", "image/webp"];
+  let Some((scheme, rest)) = split_scheme(url) else {
+    return false;
+  };
+  match scheme.as_str() {
+    "data" => !SAFE_DATA.iter().any(|kind| {
+      rest
+        .get(..kind.len())
+        .is_some_and(|start| start.eq_ignore_ascii_case(kind))
+    }),
+    "javascript" | "vbscript" | "file" => true,
+    _ => false,
+  }
+}
+
+/// Writes `url` percent-encoding the characters that aren't allowed to appear in one as is, ready to be used as an attribute.
+///
+/// Existing percent-encoded sequences are left alone so that URLs aren't encoded twice
+pub fn write_url(w: &mut dyn Write, url: &str) -> fmt::Result {
+  for (i, char) in url.char_indices() {
+    let is_encoded_sequence = char == '%'
+      && url
+        .get(i + 1..i + 3)
+        .is_some_and(|hex| hex.chars().all(|char| char.is_ascii_hexdigit()));
+    if char == '&' {
+      w.write_str("&amp;")?;
+    } else if char.is_ascii_alphanumeric()
+      || ";/?:@=+$,-_.!~*'()#".contains(char)
+      || is_encoded_sequence
+    {
+      w.write_char(char)?;
+    } else {
+      let mut bytes = [0; 4];
+      for byte in char.encode_utf8(&mut bytes).bytes() {
+        write!(w, "%{byte:02X}")?;
+      }
+    }
+  }
+  Ok(())
+}
+
+/// The spec counts anything in the Unicode `P` (punctuation) or `S` (symbol) categories as punctuation.
+/// The standard library doesn't expose general categories, so anything that isn't a letter, number,
+/// whitespace or control character is treated as punctuation
+pub fn is_punctuation(char: char) -> bool {
+  char.is_ascii_punctuation()
+    || !(char.is_alphanumeric() || char.is_whitespace() || char.is_control())
+}
@@ -0,0 +1,258 @@
+/// Tags that start a type 1 HTML block, these may contain blank lines
+const RAW_TAGS: [&str; 4] = ["pre", "script", "style", "textarea"];
+
+/// Tags that start a type 6 HTML block
+const BLOCK_TAGS: [&str; 62] = [
+  "address",
+  "article",
+  "aside",
+  "base",
+  "basefont",
+  "blockquote",
+  "body",
+  "caption",
+  "center",
+  "col",
+  "colgroup",
+  "dd",
+  "details",
+  "dialog",
+  "dir",
+  "div",
+  "dl",
+  "dt",
+  "fieldset",
+  "figcaption",
+  "figure",
+  "footer",
+  "form",
+  "frame",
+  "frameset",
+  "h1",
+  "h2",
+  "h3",
+  "h4",
+  "h5",
+  "h6",
+  "head",
+  "header",
+  "hr",
+  "html",
+  "iframe",
+  "legend",
+  "li",
+  "link",
+  "main",
+  "menu",
+  "menuitem",
+  "nav",
+  "noframes",
+  "ol",
+  "optgroup",
+  "option",
+  "p",
+  "param",
+  "search",
+  "section",
+  "summary",
+  "table",
+  "tbody",
+  "td",
+  "tfoot",
+  "th",
+  "thead",
+  "title",
+  "tr",
+  "track",
+  "ul",
+];
+
+/// Gets the kind (1-7) of [HTML block](https://spec.commonmark.org/0.31.2/#html-blocks) started by `line`.
+///
+/// `line` should already have its leading indentation removed
+pub fn html_block_start(line: &str, can_interrupt_paragraph: bool) -> Option<u8> {
+  let rest = line.strip_prefix('<')?;
+  let lower = rest.to_ascii_lowercase();
+
+  if let Some(tag) = RAW_TAGS.iter().find(|tag| lower.starts_with(*tag)) {
+    if ends_tag_name(&rest[tag.len()..], false) {
+      return Some(1);
+    }
+  }
+  if rest.starts_with("!--") {
+    return Some(2);
+  }
+  if rest.starts_with('?') {
+    return Some(3);
+  }
+  if rest.starts_with('!') && rest[1..].starts_with(|char: char| char.is_ascii_alphabetic()) {
+    return Some(4);
+  }
+  if rest.starts_with("![CDATA[") {
+    return Some(5);
+  }
+
+  let name_start = usize::from(rest.starts_with('/'));
+  let name_end = tag_name_end(&rest[name_start..]) + name_start;
+  if BLOCK_TAGS.contains(&lower[name_start..name_end].as_ref())
+    && ends_tag_name(&rest[name_end..], true)
+  {
+    return Some(6);
+  }
+
+  // Type 7 blocks need a complete tag alone on the line and can't interrupt a paragraph
+  if !can_interrupt_paragraph {
+    return None;
+  }
+  let tag_length = scan_open_tag(line).or_else(|| scan_closing_tag(line))?;
+  let is_raw_tag = RAW_TAGS.contains(&lower[name_start..name_end].as_ref());
+  match !is_raw_tag && line[tag_length..].trim_matches([' ', '\t']).is_empty() {
+    true => Some(7),
+    false => None,
+  }
+}
+
+/// Checks if `line` satisfies the end condition of an HTML block of type `kind`.
+///
+/// Only types 1-5 can end on a line, types 6 and 7 end on a blank line
+pub fn html_block_ends(kind: u8, line: &str) -> bool {
+  let lower = line.to_ascii_lowercase();
+  match kind {
+    1 => RAW_TAGS
+      .iter()
+      .any(|tag| lower.contains(&format!("</{tag}>"))),
+    2 => line.contains("-->"),
+    3 => line.contains("?>"),
+    4 => line.contains('>'),
+    5 => line.contains("]]>"),
+    _ => false,
+  }
+}
+
+/// Scans any piece of [raw HTML](https://spec.commonmark.org/0.31.2/#raw-html) at the start of `text`, returning its length
+pub fn scan_raw_html(text: &str) -> Option<usize> {
+  match raw_html_terminator(text) {
+    Some((from, terminator)) => text[from..]
+      .find(terminator)
+      .map(|end| from + end + terminator.len()),
+    None => scan_open_tag(text).or_else(|| scan_closing_tag(text)),
+  }
+}
+
+/// Gets the terminator of the comment, processing instruction, CDATA section or declaration at the start of `text`,
+/// along with the offset to start searching for it from.
+///
+/// Unlike tags these can contain any character before their terminator, so finding their end means searching
+/// the rest of the text
+pub fn raw_html_terminator(text: &str) -> Option<(usize, &'static str)> {
+  let rest = text.strip_prefix('<')?;
+
+  // Searching for the end of a comment from its `--` also matches the empty comments `<!-->` and `<!--->`
+  if rest.starts_with("!--") {
+    return Some((2, "-->"));
+  }
+  if rest.starts_with('?') {
+    return Some((2, "?>"));
+  }
+  if rest.starts_with("![CDATA[") {
+    return Some((9, "]]>"));
+  }
+  if rest.starts_with('!') && rest[1..].starts_with(|char: char| char.is_ascii_alphabetic()) {
+    return Some((2, ">"));
+  }
+  None
+}
+
+/// Scans an [open tag](https://spec.commonmark.org/0.31.2/#open-tag) at the start of `text`, returning its length
+pub fn scan_open_tag(text: &str) -> Option<usize> {
+  let rest = text.strip_prefix('<')?;
+  let mut position = 1 + tag_name_length(rest)?;
+
+  // Attributes must each be preceded by whitespace
+  loop {
+    let whitespace = html_whitespace(&text[position..]);
+    let Some(attribute) = scan_attribute(&text[position + whitespace..]) else {
+      position += whitespace;
+      break;
+    };
+    if whitespace == 0 {
+      break;
+    }
+    position += whitespace + attribute;
+  }
+
+  let rest = &text[position..];
+  let rest_length = rest.len();
+  let rest = rest.strip_prefix('/').unwrap_or(rest);
+  rest.strip_prefix('>')?;
+  Some(position + rest_length - rest.len() + 1)
+}
+
+/// Scans a [closing tag](https://spec.commonmark.org/0.31.2/#closing-tag) at the start of `text`, returning its length
+pub fn scan_closing_tag(text: &str) -> Option<usize> {
+  let rest = text.strip_prefix("</")?;
+  let mut position = 2 + tag_name_length(rest)?;
+  position += html_whitespace(&text[position..]);
+  text[position..].strip_prefix('>')?;
+  Some(position + 1)
+}
+
+/// A tag name is an ASCII letter followed by any number of ASCII letters, digits or `-`
+fn tag_name_length(text: &str) -> Option<usize> {
+  if !text.starts_with(|char: char| char.is_ascii_alphabetic()) {
+    return None;
+  }
+  Some(tag_name_end(text))
+}
+
+fn tag_name_end(text: &str) -> usize {
+  text
+    .find(|char: char| !(char.is_ascii_alphanumeric() || char == '-'))
+    .unwrap_or(text.len())
+}
+
+/// Checks that a tag name is followed by whitespace, the end of the line, `>` or (if `allow_self_closing`) `/>`
+fn ends_tag_name(rest: &str, allow_self_closing: bool) -> bool {
+  rest.is_empty()
+    || rest.starts_with([' ', '\t', '>'])
+    || allow_self_closing && rest.starts_with("/>")
+}
+
+/// Scans an attribute name with an optional value specification, returning its length
+fn scan_attribute(text: &str) -> Option<usize> {
+  if !text.starts_with(|char: char| char.is_ascii_alphabetic() || char == '_' || char == ':') {
+    return None;
+  }
+  let name_length = text
+    .find(|char: char| !(char.is_ascii_alphanumeric() || "_.:-".contains(char)))
+    .unwrap_or(text.len());
+
+  let mut position = name_length + html_whitespace(&text[name_length..]);
+  if !text[position..].starts_with('=') {
+    return Some(name_length);
+  }
+  position += 1;
+  position += html_whitespace(&text[position..]);
+
+  let value = &text[position..];
+  let value_length = match value.chars().next()? {
+    quote @ ('"' | '\'') => value[1..].find(quote)? + 2,
+    _ => {
+      let length = value
+        .find(|char: char| char.is_ascii_whitespace() || "\"'=<>`".contains(char))
+        .unwrap_or(value.len());
+      if length == 0 {
+        return None;
+      }
+      length
+    }
+  };
+  Some(position + value_length)
+}
+
+/// Counts the spaces, tabs and line endings at the start of `text`
+fn html_whitespace(text: &str) -> usize {
+  text
+    .find(|char: char| !matches!(char, ' ' | '\t' | '\n'))
+    .unwrap_or(text.len())
+}
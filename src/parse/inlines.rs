@@ -1,20 +1,68 @@
-#[derive(Debug, PartialEq)]
-pub(crate) enum Inline {
+use crate::span::Spanned;
+
+mod code;
+mod emphasis;
+mod html;
+mod links;
+
+pub(crate) use links::{
+  scan_link_destination, scan_link_label, scan_link_title, skip_whitespace, unescape,
+};
+mod parser;
+mod smart;
+
+pub(crate) use parser::InlineParser;
+
+/// A piece of inline content within a block
+#[derive(Debug, PartialEq, Clone)]
+pub enum Inline {
+  /// Plain text, with escapes and entity references already decoded
   Text(String),
+  /// Emphasised content, rendered as `<em>`
+  Emphasis(Vec<Spanned<Inline>>),
+  /// Strongly emphasised content, rendered as `<strong>`
+  Strong(Vec<Spanned<Inline>>),
+  /// The literal contents of a code span, rendered as `<code>`
+  Code(String),
+  /// A link to `dest`, rendered as `<a>`
+  Link {
+    dest: String,
+    title: Option<String>,
+    children: Vec<Spanned<Inline>>,
+  },
+  /// An image sourced from `dest`, rendered as `<img>`. The children make up the alt text
+  Image {
+    dest: String,
+    title: Option<String>,
+    children: Vec<Spanned<Inline>>,
+  },
+  /// A link written directly between angle brackets, `email` links are given a `mailto:` scheme
+  Autolink { url: String, email: bool },
+  /// HTML which is passed through as is
+  RawHtml(String),
+  /// A line ending within a paragraph, which is rendered depending on [`SoftBreak`]
+  SoftBreak,
+  /// A line ending preceded by two or more spaces or a backslash, rendered as `<br />`
+  HardBreak,
 }
 
 impl Inline {
-  pub fn as_html(&self) -> String {
+  /// Flattens inlines down to their plain text content, as is needed for image alt text
+  pub fn as_text(&self) -> String {
     match self {
       Inline::Text(text) => text.clone(),
+      Inline::Code(code) => code.clone(),
+      Inline::Autolink { url, .. } => url.clone(),
+      Inline::RawHtml(html) => html.clone(),
+      Inline::SoftBreak | Inline::HardBreak => "\n".to_string(),
+      Inline::Emphasis(inner) | Inline::Strong(inner) => Inline::vec_as_text(inner),
+      Inline::Link { children, .. } | Inline::Image { children, .. } => {
+        Inline::vec_as_text(children)
+      }
     }
   }
 
-  pub fn vec_as_html(inlines: &Vec<Self>) -> String {
-    let mut html: Vec<String> = vec![];
-    for inline in inlines {
-      html.push(inline.as_html())
-    }
-    html.join("")
+  pub fn vec_as_text(inlines: &[Spanned<Self>]) -> String {
+    inlines.iter().map(|inline| inline.as_text()).collect()
   }
 }
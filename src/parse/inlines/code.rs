@@ -0,0 +1,102 @@
+use std::collections::HashMap;
+
+use crate::{span::Spanned, tokeniser::Token};
+
+use super::{parser::InlineParser, Inline};
+
+impl InlineParser<'_> {
+  /// Attempts to parse a [code span](https://spec.commonmark.org/0.31.2/#code-spans) starting from a run of backticks.
+  ///
+  /// If no closing run of the same length exists, the opening run is treated as literal text
+  pub(super) fn parse_code_span(&mut self) {
+    let start = self.position;
+    let length = count_backticks(&self.tokens[start..]);
+    let content_start = start + length;
+
+    // Every run in the block is found once, so that a run with no closer doesn't search the rest of the block for one
+    let tokens = self.tokens;
+    let last_runs = self
+      .last_backtick_runs
+      .get_or_insert_with(|| find_last_backtick_runs(tokens));
+    let has_closer = last_runs
+      .get(&length)
+      .is_some_and(|last| *last >= content_start);
+
+    let mut index = content_start;
+    while has_closer && index < self.tokens.len() {
+      let Some(run) = backtick_run(self.tokens, index) else {
+        index += 1;
+        continue;
+      };
+      if run == length {
+        // Backslash escapes don't work in code spans, so an escaped backtick is really a backslash
+        // followed by the start of a backtick run
+        let trailing = match self.tokens[index].value {
+          Token::Escaped('`') => "\\",
+          _ => "",
+        };
+        let content = self.code_span_content(content_start, index, trailing);
+        self.position = index + run;
+        self.push_node(Inline::Code(content), start);
+        return;
+      }
+      index += run;
+    }
+
+    // No closing run so the backticks are just text
+    self.position = content_start;
+    self.push_node(Inline::Text("`".repeat(length)), start);
+  }
+
+  /// Builds the text of a code span from the tokens between `start` and `end`, with `trailing` added to the end.
+  fn code_span_content(&self, start: usize, end: usize, trailing: &str) -> String {
+    let mut content: String = self.tokens[start..end]
+      .iter()
+      .map(|token| match token.value {
+        Token::NewLine => " ".to_string(),
+        _ => token.clone().into(),
+      })
+      .collect();
+    content.push_str(trailing);
+
+    // If the content both begins and ends with a space, but is not entirely spaces, a single space is removed from each side
+    if content.starts_with(' ') && content.ends_with(' ') && content.chars().any(|char| char != ' ')
+    {
+      content = content[1..content.len() - 1].to_string();
+    }
+    content
+  }
+}
+
+fn count_backticks(tokens: &[Spanned<Token>]) -> usize {
+  tokens
+    .iter()
+    .take_while(|token| **token == Token::BackTick)
+    .count()
+}
+
+/// The length of the run of backticks starting at `index`, if there is one.
+/// An escaped backtick starts a run, as the backslash is literal inside a code span
+fn backtick_run(tokens: &[Spanned<Token>], index: usize) -> Option<usize> {
+  match tokens[index].value {
+    Token::BackTick => Some(count_backticks(&tokens[index..])),
+    Token::Escaped('`') => Some(1 + count_backticks(&tokens[index + 1..])),
+    _ => None,
+  }
+}
+
+/// Maps each length of backtick run in `tokens` to the index the last run of that length starts at
+fn find_last_backtick_runs(tokens: &[Spanned<Token>]) -> HashMap<usize, usize> {
+  let mut last_runs = HashMap::new();
+  let mut index = 0;
+  while index < tokens.len() {
+    match backtick_run(tokens, index) {
+      Some(run) => {
+        last_runs.insert(run, index);
+        index += run;
+      }
+      None => index += 1,
+    }
+  }
+  last_runs
+}
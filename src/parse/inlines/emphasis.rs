@@ -0,0 +1,283 @@
+use crate::{
+  parse::helpers::is_punctuation,
+  span::{Position, Span, Spanned},
+  tokeniser::{Token, TokenSlice},
+};
+
+use super::{parser::InlineParser, Inline};
+
+/// An entry on the delimiter stack representing a run of `*` or `_` tokens
+#[derive(Debug)]
+pub(super) struct Delimiter {
+  /// The token the run is made up of
+  token: Token,
+  /// The index of the text node holding this run
+  node: usize,
+  /// How many delimiters in the run have not yet been used up by emphasis
+  count: usize,
+  /// The length of the run as it was in the source. Needed for the "rule of 3"
+  length: usize,
+  can_open: bool,
+  can_close: bool,
+}
+
+/// The number of kinds of closer, each with its own entry in `openers_bottom`. A closer's kind is laid out as
+/// `token * 6 + can_open * 3 + length % 3`, where `token` is 0 for `*` and 1 for `_`
+const CLOSER_KINDS: usize = 12;
+
+/// Emphasis nested deeper than this is left as literal text, so that walking the finished inlines can't overflow the stack
+const MAX_EMPHASIS_DEPTH: usize = 100;
+
+impl Delimiter {
+  /// Which of the spec's `openers_bottom` entries a closer uses. Whether an opener can match depends only on the
+  /// closer's token, whether it can also open and its length modulo 3, so closers sharing all three share a bottom
+  fn kind(&self) -> usize {
+    let token = usize::from(self.token == Token::Underscore);
+    token * 6 + usize::from(self.can_open) * 3 + self.length % 3
+  }
+}
+
+/// A pair of delimiter runs matched as emphasis, recorded so the nodes between them can be wrapped once matching is done
+struct Match {
+  /// The index of the opening run's text node
+  opener: usize,
+  /// The index of the closing run's text node
+  closer: usize,
+  token: Token,
+  strong: bool,
+  /// The span of the emphasis, including the delimiters used from each run
+  span: Span,
+}
+
+impl Match {
+  /// The delimiters used from one end of the emphasis as text, for when it is nested too deeply to be kept
+  fn literal(&self, start: Position) -> Spanned<Inline> {
+    let used = if self.strong { 2 } else { 1 };
+    let end = Position {
+      offset: start.offset + used,
+      column: start.column + used,
+      ..start
+    };
+    let run: String = self.token.clone().into();
+    Spanned::new(Inline::Text(run.repeat(used)), Span::new(start, end))
+  }
+}
+
+impl InlineParser<'_> {
+  /// Consumes a run of `*` or `_` tokens, adding it to the node list as text and to the delimiter stack
+  pub(super) fn parse_delimiter_run(&mut self) {
+    let token = self.tokens[self.position].value.clone();
+    let start = self.position;
+    while self.tokens.token(self.position) == Some(&token) {
+      self.position += 1;
+    }
+    let length = self.position - start;
+
+    // The start and end of a block count as whitespace
+    let before = match start {
+      0 => ' ',
+      _ => last_char(&self.tokens[start - 1]),
+    };
+    let after = self.tokens.token(self.position).map_or(' ', first_char);
+
+    // As per [spec](https://spec.commonmark.org/0.31.2/#left-flanking-delimiter-run)
+    let left_flanking = !after.is_whitespace()
+      && (!is_punctuation(after) || before.is_whitespace() || is_punctuation(before));
+    let right_flanking = !before.is_whitespace()
+      && (!is_punctuation(before) || after.is_whitespace() || is_punctuation(after));
+
+    // Underscores have extra restrictions to prevent emphasis from appearing inside words like snake_case
+    let (can_open, can_close) = match token {
+      Token::Underscore => (
+        left_flanking && (!right_flanking || is_punctuation(before)),
+        right_flanking && (!left_flanking || is_punctuation(after)),
+      ),
+      _ => (left_flanking, right_flanking),
+    };
+
+    let run: String = token.clone().into();
+    self.push_node(Inline::Text(run.repeat(length)), start);
+    self.delimiters.push(Delimiter {
+      token,
+      node: self.nodes.len() - 1,
+      count: length,
+      length,
+      can_open,
+      can_close,
+    });
+  }
+
+  /// Matches openers and closers on the delimiter stack above `stack_bottom`, wrapping the nodes between them in emphasis.
+  ///
+  /// Follows the [process emphasis](https://spec.commonmark.org/0.31.2/#process-emphasis) procedure from the spec.
+  /// All delimiters above `stack_bottom` are removed from the stack once complete.
+  pub(super) fn process_emphasis(&mut self, stack_bottom: usize) {
+    let delimiters = &mut self.delimiters[stack_bottom..];
+    // The stack is walked as a linked list, so that delimiters can be taken out of it without moving those after them
+    let mut previous: Vec<Option<usize>> = (0..delimiters.len())
+      .map(|index| index.checked_sub(1))
+      .collect();
+    // The lowest index an opener could be at for each kind of closer, as the spec's `openers_bottom`.
+    // Without it every closer that can't be matched would search the whole stack again
+    let mut openers_bottom = [0; CLOSER_KINDS];
+    let mut matches = vec![];
+    let mut closer = 0;
+    while closer < delimiters.len() {
+      if !delimiters[closer].can_close {
+        closer += 1;
+        continue;
+      }
+
+      let kind = delimiters[closer].kind();
+      let Some(opener) = find_opener(delimiters, &previous, openers_bottom[kind], closer) else {
+        openers_bottom[kind] = closer;
+        closer += 1;
+        continue;
+      };
+
+      // Strong emphasis is used whenever both sides have at least 2 delimiters to spare
+      let strong = delimiters[opener].count >= 2 && delimiters[closer].count >= 2;
+      let used = if strong { 2 } else { 1 };
+      for index in [opener, closer] {
+        let delimiter = &mut delimiters[index];
+        delimiter.count -= used;
+        if let Inline::Text(text) = &mut self.nodes[delimiter.node].value {
+          text.truncate(delimiter.count);
+        }
+      }
+
+      // The delimiters used are those closest to the content, so the opener loses its end and the closer its start.
+      // Delimiters are always a single byte so the spans can be moved directly
+      let opener_span = &mut self.nodes[delimiters[opener].node].span;
+      opener_span.end.offset -= used;
+      opener_span.end.column -= used;
+      let emphasis_start = opener_span.end;
+      let closer_span = &mut self.nodes[delimiters[closer].node].span;
+      closer_span.start.offset += used;
+      closer_span.start.column += used;
+      matches.push(Match {
+        opener: delimiters[opener].node,
+        closer: delimiters[closer].node,
+        token: delimiters[closer].token.clone(),
+        strong,
+        span: Span::new(emphasis_start, closer_span.start),
+      });
+
+      // Any delimiters between the two can no longer match anything, and neither can exhausted runs
+      previous[closer] = match delimiters[opener].count {
+        0 => previous[opener],
+        _ => Some(opener),
+      };
+      if delimiters[closer].count == 0 {
+        let before = previous[closer];
+        if let Some(next) = previous.get_mut(closer + 1) {
+          *next = before;
+        }
+        closer += 1;
+      }
+    }
+
+    self.delimiters.truncate(stack_bottom);
+    if let Some(first) = matches.iter().map(|matched| matched.opener).min() {
+      let nodes: Vec<Spanned<Inline>> = self.nodes.drain(first..).collect();
+      let nested = nest_emphasis(nodes, first, &matches);
+      self.nodes.extend(nested);
+    }
+  }
+}
+
+/// Looks back down the stack, no further than `bottom`, for the closest delimiter that can open emphasis for `closer`
+fn find_opener(
+  delimiters: &[Delimiter],
+  previous: &[Option<usize>],
+  bottom: usize,
+  closer: usize,
+) -> Option<usize> {
+  let closer_delimiter = &delimiters[closer];
+  let mut candidate = previous[closer];
+  while let Some(index) = candidate.filter(|index| *index >= bottom) {
+    let opener = &delimiters[index];
+    candidate = previous[index];
+    if opener.token != closer_delimiter.token || !opener.can_open {
+      continue;
+    }
+
+    // The "rule of 3": If one of the delimiters can both open and close emphasis, then the sum of the lengths
+    // of the delimiter runs containing the opening and closing delimiters must not be a multiple of 3
+    // unless both lengths are multiples of 3
+    let either_both = opener.can_close || closer_delimiter.can_open;
+    let sum = opener.length + closer_delimiter.length;
+    if !(either_both
+      && sum.is_multiple_of(3)
+      && !(opener.length.is_multiple_of(3) && closer_delimiter.length.is_multiple_of(3)))
+    {
+      return Some(index);
+    }
+  }
+  None
+}
+
+/// Wraps the nodes between each matched pair of runs in emphasis, in a single pass over `nodes`.
+///
+/// `nodes` starts at the node with index `first`. Matches never cross one another, and those sharing a run were
+/// matched from the innermost outwards, so each node closes the emphasis ending at it before it and opens those
+/// starting at it after it, outermost first
+fn nest_emphasis(
+  nodes: Vec<Spanned<Inline>>,
+  first: usize,
+  matches: &[Match],
+) -> Vec<Spanned<Inline>> {
+  // Closers are matched in order, so the matches are already sorted by where they close
+  let mut closing = matches.iter().peekable();
+  let mut opening: Vec<&Match> = matches.iter().rev().collect();
+  opening.sort_by_key(|matched| matched.opener);
+  let mut opening = opening.into_iter().peekable();
+
+  let mut parents: Vec<Vec<Spanned<Inline>>> = vec![];
+  let mut current = vec![];
+  // How many of the innermost open emphasis were too deep, and so are being written out as text
+  let mut too_deep = 0;
+  for (index, node) in (first..).zip(nodes) {
+    while let Some(matched) = closing.next_if(|matched| matched.closer == index) {
+      if too_deep > 0 {
+        too_deep -= 1;
+        let used = if matched.strong { 2 } else { 1 };
+        let start = Position {
+          offset: matched.span.end.offset - used,
+          column: matched.span.end.column - used,
+          ..matched.span.end
+        };
+        current.push(matched.literal(start));
+        continue;
+      }
+
+      let children = std::mem::replace(&mut current, parents.pop().unwrap_or_default());
+      let emphasis = match matched.strong {
+        true => Inline::Strong(children),
+        false => Inline::Emphasis(children),
+      };
+      current.push(Spanned::new(emphasis, matched.span));
+    }
+
+    current.push(node);
+
+    while let Some(matched) = opening.next_if(|matched| matched.opener == index) {
+      match parents.len() == MAX_EMPHASIS_DEPTH {
+        true => {
+          too_deep += 1;
+          current.push(matched.literal(matched.span.start));
+        }
+        false => parents.push(std::mem::take(&mut current)),
+      }
+    }
+  }
+  current
+}
+
+pub(super) fn first_char(token: &Token) -> char {
+  String::from(token.clone()).chars().next().unwrap_or(' ')
+}
+
+pub(super) fn last_char(token: &Token) -> char {
+  String::from(token.clone()).chars().last().unwrap_or(' ')
+}
@@ -0,0 +1,105 @@
+use crate::parse::html::{raw_html_terminator, scan_raw_html};
+
+use super::{parser::InlineParser, Inline};
+
+impl InlineParser<'_> {
+  /// Handles a `<`, which may start an [autolink](https://spec.commonmark.org/0.31.2/#autolinks)
+  /// or a piece of [raw HTML](https://spec.commonmark.org/0.31.2/#raw-html)
+  pub(super) fn parse_angle_bracket(&mut self) {
+    let start = self.position;
+    let text = self.source_from(start);
+    // Comments and the like can only end on a terminator, which once missing will be missing from the rest of the block
+    let terminator = raw_html_terminator(text).map(|(_, terminator)| terminator);
+    let unclosed = terminator.is_some_and(|terminator| self.unclosed_html.contains(&terminator));
+
+    let found = if let Some(length) = scan_uri_autolink(text) {
+      Some((
+        Inline::Autolink {
+          url: text[1..length - 1].to_string(),
+          email: false,
+        },
+        length,
+      ))
+    } else if let Some(length) = scan_email_autolink(text) {
+      Some((
+        Inline::Autolink {
+          url: text[1..length - 1].to_string(),
+          email: true,
+        },
+        length,
+      ))
+    } else if unclosed {
+      None
+    } else {
+      scan_raw_html(text).map(|length| (Inline::RawHtml(text[..length].to_string()), length))
+    };
+    if let (None, Some(terminator), false) = (&found, terminator, unclosed) {
+      self.unclosed_html.push(terminator);
+    }
+
+    // Everything scanned ends with a `>`, which is always its own token
+    match found.and_then(|(inline, length)| Some((inline, self.token_after(start, length)?))) {
+      Some((inline, end)) => {
+        self.position = end;
+        self.push_node(inline, start);
+      }
+      None => {
+        self.position += 1;
+        self.push_node(Inline::Text("<".to_string()), start);
+      }
+    }
+  }
+}
+
+/// Scans a URI autolink such as `<https://example.com>`, returning its length
+fn scan_uri_autolink(text: &str) -> Option<usize> {
+  let rest = text.strip_prefix('<')?;
+  let scheme_length = rest
+    .find(|char: char| !(char.is_ascii_alphanumeric() || "+.-".contains(char)))
+    .unwrap_or(rest.len());
+  if !(2..=32).contains(&scheme_length)
+    || !rest.starts_with(|char: char| char.is_ascii_alphabetic())
+    || !rest[scheme_length..].starts_with(':')
+  {
+    return None;
+  }
+
+  // The rest of the URI can't contain spaces, control characters or angle brackets
+  let end =
+    rest.find(|char: char| char == '>' || char == '<' || char == ' ' || char.is_ascii_control())?;
+  match rest[end..].starts_with('>') {
+    true => Some(end + 2),
+    false => None,
+  }
+}
+
+/// Scans an email autolink such as `<foo@example.com>`, returning its length
+fn scan_email_autolink(text: &str) -> Option<usize> {
+  let rest = text.strip_prefix('<')?;
+  let at = rest
+    .find(|char: char| !(char.is_ascii_alphanumeric() || ".!#$%&'*+/=?^_`{|}~-".contains(char)))
+    .unwrap_or(rest.len());
+  if at == 0 || !rest[at..].starts_with('@') {
+    return None;
+  }
+
+  let domain_length = rest[at + 1..]
+    .find(|char: char| !(char.is_ascii_alphanumeric() || ".-".contains(char)))
+    .unwrap_or(rest.len() - at - 1);
+  if !rest[at + 1 + domain_length..].starts_with('>') {
+    return None;
+  }
+  let domain = &rest[at + 1..at + 1 + domain_length];
+  let valid_label = |label: &str| {
+    (1..=63).contains(&label.len())
+      && label
+        .chars()
+        .all(|char| char.is_ascii_alphanumeric() || char == '-')
+      && !label.starts_with('-')
+      && !label.ends_with('-')
+  };
+  match domain.split('.').all(valid_label) {
+    true => Some(at + domain_length + 3),
+    false => None,
+  }
+}
@@ -0,0 +1,310 @@
+use crate::{
+  span::Spanned,
+  tokeniser::{Bracket, Token, TokenSlice},
+};
+
+use super::{parser::InlineParser, Inline};
+use crate::parse::{entities::scan_entity, references::normalise_label};
+
+/// An entry on the bracket stack, recording where a `[` or `![` that could begin a link was found
+#[derive(Debug)]
+pub(super) struct LinkOpener {
+  /// The index of the text node holding the opening bracket
+  node: usize,
+  /// The index of the first token after the opening bracket
+  token: usize,
+  /// Is this the start of an image
+  image: bool,
+  /// Links may not contain other links, so once a link is found all openers before it are deactivated
+  active: bool,
+  /// The height of the delimiter stack when this opener was found
+  delimiter_bottom: usize,
+  /// Has another bracket been opened after this one. If so, the link text can't be used as a reference label
+  bracket_after: bool,
+}
+
+impl InlineParser<'_> {
+  /// Handles a `[` or `![`, pushing it to the bracket stack
+  pub(super) fn parse_open_bracket(&mut self, image: bool) {
+    let (text, length) = match image {
+      true => ("![", 2),
+      false => ("[", 1),
+    };
+    let start = self.position;
+    self.position += length;
+    self.push_node(Inline::Text(text.to_string()), start);
+    if let Some(previous) = self.brackets.last_mut() {
+      previous.bracket_after = true;
+    }
+    self.brackets.push(LinkOpener {
+      node: self.nodes.len() - 1,
+      token: self.position,
+      image,
+      active: true,
+      delimiter_bottom: self.delimiters.len(),
+      bracket_after: false,
+    });
+  }
+
+  /// Handles a `]`, attempting to close the most recent opener on the bracket stack as
+  /// described in [look for link or image](https://spec.commonmark.org/0.31.2/#look-for-link-or-image)
+  pub(super) fn parse_close_bracket(&mut self) {
+    let close = self.position;
+    self.position += 1;
+    let Some(opener) = self.brackets.pop() else {
+      self.push_node(Inline::Text("]".to_string()), close);
+      return;
+    };
+
+    let link = match opener.active {
+      true => self
+        .parse_inline_link(self.position)
+        .or_else(|| self.parse_reference_link(&opener, close)),
+      false => None,
+    };
+    let Some((dest, title, end)) = link else {
+      self.push_node(Inline::Text("]".to_string()), close);
+      return;
+    };
+    self.position = end;
+
+    // Emphasis within the link text has to be resolved before it is moved into the link
+    self.process_emphasis(opener.delimiter_bottom);
+    let children: Vec<Spanned<Inline>> = self.nodes.drain(opener.node + 1..).collect();
+    let opening_bracket = self.nodes.pop().unwrap();
+    let span = opening_bracket.span.join(&self.span_of(close, end));
+    let link = match opener.image {
+      true => Inline::Image {
+        dest,
+        title,
+        children,
+      },
+      false => Inline::Link {
+        dest,
+        title,
+        children,
+      },
+    };
+    self.nodes.push(Spanned::new(link, span));
+
+    if !opener.image {
+      for opener in self.brackets.iter_mut().filter(|opener| !opener.image) {
+        opener.active = false;
+      }
+    }
+  }
+
+  /// Attempts to parse the `(destination "title")` part of an
+  /// [inline link](https://spec.commonmark.org/0.31.2/#inline-link) beginning at the token `start`.
+  ///
+  /// Returns the destination, title and the index of the token after the closing `)`
+  fn parse_inline_link(&self, start: usize) -> Option<(String, Option<String>, usize)> {
+    if self.tokens.token(start) != Some(&Token::OpenBracket(Bracket::Parenthesis)) {
+      return None;
+    }
+    let text = self.source_from(start + 1);
+
+    let mut position = skip_whitespace(text);
+    let (dest, length) = match text[position..].starts_with(')') {
+      true => (String::new(), 0),
+      false => scan_link_destination(&text[position..])?,
+    };
+    position += length;
+
+    let spacing = skip_whitespace(&text[position..]);
+    position += spacing;
+    let mut title = None;
+    if spacing > 0 || length == 0 {
+      if let Some((scanned, length)) = scan_link_title(&text[position..]) {
+        title = Some(scanned);
+        position += length;
+        position += skip_whitespace(&text[position..]);
+      }
+    }
+
+    if !text[position..].starts_with(')') {
+      return None;
+    }
+    let end = self.token_after(start + 1, position)?;
+    Some((dest, title.filter(|title| !title.is_empty()), end + 1))
+  }
+
+  /// Attempts to match a full, collapsed or shortcut [reference link](https://spec.commonmark.org/0.31.2/#reference-link)
+  /// against the link reference definitions found in the document. `close` is the index of the `]` token.
+  ///
+  /// Returns the destination, title and the index of the token after the link
+  fn parse_reference_link(
+    &self,
+    opener: &LinkOpener,
+    close: usize,
+  ) -> Option<(String, Option<String>, usize)> {
+    let text = self.source_from(close + 1);
+
+    let (label, end) = match scan_link_label(text) {
+      // A full reference link uses the label that follows it
+      Some((label, length)) => (label, self.token_after(close + 1, length)?),
+      // Collapsed and shortcut reference links use their own text as the label
+      None if !opener.bracket_after => {
+        let label: String = self.tokens[opener.token..close]
+          .iter()
+          .map(|token| String::from(token.clone()))
+          .collect();
+        match text.starts_with("[]") {
+          true => (label, close + 3),
+          false => (label, close + 1),
+        }
+      }
+      None => return None,
+    };
+
+    let reference = self.context.link_references.get(&normalise_label(&label))?;
+    Some((reference.dest.clone(), reference.title.clone(), end))
+  }
+}
+
+/// How deeply parentheses can be nested within a link destination
+const MAX_PAREN_DEPTH: usize = 32;
+
+/// Skips spaces and tabs, including up to one line ending, returning the number of bytes skipped
+pub(crate) fn skip_whitespace(text: &str) -> usize {
+  let mut seen_newline = false;
+  for (i, char) in text.char_indices() {
+    match char {
+      ' ' | '\t' => {}
+      '\n' if !seen_newline => seen_newline = true,
+      _ => return i,
+    }
+  }
+  text.len()
+}
+
+/// Scans a [link destination](https://spec.commonmark.org/0.31.2/#link-destination) from the start of `text`.
+///
+/// Returns the destination with escapes removed and the number of bytes it took up
+pub(crate) fn scan_link_destination(text: &str) -> Option<(String, usize)> {
+  let mut chars = text.char_indices().peekable();
+  let mut escaped = false;
+
+  // A destination in pointy brackets may contain spaces but not line endings or unescaped brackets
+  if text.starts_with('<') {
+    chars.next();
+    for (i, char) in chars {
+      match char {
+        _ if escaped => escaped = false,
+        '\\' => escaped = true,
+        '\n' | '<' => return None,
+        '>' => return Some((unescape(&text[1..i]), i + 1)),
+        _ => {}
+      }
+    }
+    return None;
+  }
+
+  // Otherwise it can't contain spaces or control chars, and any parentheses must be balanced.
+  // Like cmark, nesting is limited so that an unclosed run of `(` can't be scanned over again for every link
+  let mut depth = 0;
+  let mut end = text.len();
+  for (i, char) in chars {
+    match char {
+      _ if escaped => escaped = false,
+      '\\' => escaped = true,
+      '(' if depth == MAX_PAREN_DEPTH => return None,
+      '(' => depth += 1,
+      ')' if depth == 0 => {
+        end = i;
+        break;
+      }
+      ')' => depth -= 1,
+      _ if char == ' ' || char.is_ascii_control() => {
+        end = i;
+        break;
+      }
+      _ => {}
+    }
+  }
+
+  match end == 0 || depth != 0 {
+    true => None,
+    false => Some((unescape(&text[..end]), end)),
+  }
+}
+
+/// Scans a [link title](https://spec.commonmark.org/0.31.2/#link-title) from the start of `text`.
+///
+/// Returns the title with escapes removed and the number of bytes it took up
+pub(crate) fn scan_link_title(text: &str) -> Option<(String, usize)> {
+  let closing = match text.chars().next()? {
+    '"' => '"',
+    '\'' => '\'',
+    '(' => ')',
+    _ => return None,
+  };
+
+  let mut escaped = false;
+  for (i, char) in text.char_indices().skip(1) {
+    match char {
+      _ if escaped => escaped = false,
+      '\\' => escaped = true,
+      _ if char == closing => return Some((unescape(&text[1..i]), i + 1)),
+      '(' if closing == ')' => return None,
+      _ => {}
+    }
+  }
+  None
+}
+
+/// Scans a [link label](https://spec.commonmark.org/0.31.2/#link-label) from the start of `text`.
+///
+/// Returns the raw contents of the label along with the number of bytes it took up
+pub(crate) fn scan_link_label(text: &str) -> Option<(String, usize)> {
+  if !text.starts_with('[') {
+    return None;
+  }
+
+  let mut escaped = false;
+  for (i, char) in text.char_indices().skip(1) {
+    match char {
+      _ if i > 1000 => return None, // Labels are limited to 999 characters
+      _ if escaped => escaped = false,
+      '\\' => escaped = true,
+      '[' => return None,
+      ']' => {
+        let label = &text[1..i];
+        return match label.trim().is_empty() {
+          true => None,
+          false => Some((label.to_string(), i + 1)),
+        };
+      }
+      _ => {}
+    }
+  }
+  None
+}
+
+/// Removes the backslash from any [backslash escapes](https://spec.commonmark.org/0.31.2/#backslash-escapes)
+/// and decodes any [entity references](https://spec.commonmark.org/0.31.2/#entity-and-numeric-character-references)
+pub(crate) fn unescape(text: &str) -> String {
+  let mut unescaped = String::with_capacity(text.len());
+  let mut chars = text.char_indices().peekable();
+  while let Some((i, char)) = chars.next() {
+    match char {
+      // An escaped char is always taken literally, even if it begins an entity
+      '\\'
+        if chars
+          .peek()
+          .is_some_and(|(_, next)| next.is_ascii_punctuation()) =>
+      {
+        unescaped.push(chars.next().unwrap().1);
+      }
+      '&' => match scan_entity(&text[i..]) {
+        Some((entity, length)) => {
+          unescaped.push_str(&entity);
+          while chars.next_if(|(next, _)| *next < i + length).is_some() {}
+        }
+        None => unescaped.push(char),
+      },
+      _ => unescaped.push(char),
+    }
+  }
+  unescaped
+}
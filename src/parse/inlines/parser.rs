@@ -0,0 +1,234 @@
+use std::collections::HashMap;
+
+use crate::{
+  parse::document::DocContext,
+  span::{Span, Spanned},
+  tokeniser::{Bracket, Token, TokenSlice},
+};
+
+use crate::parse::{entities::decode_entities, helpers::end_position};
+
+use super::{emphasis::Delimiter, links::LinkOpener, smart::OpenQuotes, Inline};
+
+/// Turns the tokens of a single block into inlines.
+///
+/// Tokens are first converted into a flat list of nodes, with code spans being taken as soon as they are found as they
+/// bind tighter than anything else. Delimiter runs (`*` and `_`) are kept as
+/// plain text nodes with an entry on the delimiter stack so that once every token has been consumed,
+/// the stack can be processed to fold the nodes between matching runs into emphasis.
+/// Link openers are tracked on a separate bracket stack, with links being resolved as soon as their `]` is found.
+pub(crate) struct InlineParser<'a> {
+  pub(super) tokens: &'a [Spanned<Token>],
+  /// The source text of every token, built once so that constructs spanning several tokens can be scanned as text
+  source: String,
+  /// The byte offset in `source` that each token begins at, followed by the length of `source`
+  offsets: Vec<usize>,
+  pub(super) context: &'a DocContext,
+  pub(super) position: usize,
+  pub(super) nodes: Vec<Spanned<Inline>>,
+  pub(super) delimiters: Vec<Delimiter>,
+  pub(super) brackets: Vec<LinkOpener>,
+  /// Which kinds of quote have been opened but not yet closed, used by smart punctuation
+  pub(super) open_quotes: OpenQuotes,
+  /// Raw HTML terminators that are known not to appear in the rest of the block, so aren't searched for again
+  pub(super) unclosed_html: Vec<&'static str>,
+  /// The index that the last run of backticks of each length starts at, found when the first code span is parsed
+  pub(super) last_backtick_runs: Option<HashMap<usize, usize>>,
+}
+
+impl<'a> InlineParser<'a> {
+  pub fn new(tokens: &'a [Spanned<Token>], context: &'a DocContext) -> Self {
+    let mut source = String::new();
+    let mut offsets = Vec::with_capacity(tokens.len() + 1);
+    for token in tokens {
+      offsets.push(source.len());
+      source.push_str(&String::from(token.clone()));
+    }
+    offsets.push(source.len());
+    Self {
+      tokens,
+      source,
+      offsets,
+      context,
+      position: 0,
+      nodes: vec![],
+      delimiters: vec![],
+      brackets: vec![],
+      open_quotes: OpenQuotes::default(),
+      unclosed_html: vec![],
+      last_backtick_runs: None,
+    }
+  }
+
+  /// Consumes the parser, returning the finished inlines
+  pub fn parse(mut self) -> Vec<Spanned<Inline>> {
+    while let Some(token) = self.tokens.get(self.position) {
+      let start = self.position;
+      match &token.value {
+        Token::Star | Token::Underscore => self.parse_delimiter_run(),
+        Token::BackTick => self.parse_code_span(),
+        Token::OpenBracket(Bracket::Square) => self.parse_open_bracket(false),
+        Token::Bang
+          if self.tokens.token(self.position + 1) == Some(&Token::OpenBracket(Bracket::Square)) =>
+        {
+          self.parse_open_bracket(true)
+        }
+        Token::CloseBracket(Bracket::Square) => self.parse_close_bracket(),
+        Token::OpenBracket(Bracket::Angle) => self.parse_angle_bracket(),
+        Token::Text(..) | Token::Number(..) | Token::Hash => self.parse_text(),
+        Token::Space | Token::Tab => self.parse_blank_run(),
+        Token::Dash if self.context.options.smart => self.parse_dashes(),
+        Token::Dot if self.context.options.smart => self.parse_ellipsis(),
+        Token::Escape if self.tokens.token(self.position + 1) == Some(&Token::NewLine) => {
+          self.position += 2;
+          self.push_node(Inline::HardBreak, start);
+        }
+        Token::NewLine => {
+          self.position += 1;
+          self.push_node(Inline::SoftBreak, start);
+        }
+        Token::Escaped(char) => {
+          self.position += 1;
+          self.push_node(Inline::Text(char.to_string()), start);
+        }
+        _ => {
+          self.position += 1;
+          self.push_node(Inline::Text(token.clone().into()), start);
+        }
+      }
+    }
+
+    self.process_emphasis(0);
+    merge_text(self.nodes)
+  }
+
+  /// Takes a run of spaces and tabs, which become a line break if they come right before a line ending.
+  ///
+  /// Two or more spaces before the line ending make a hard break, and blanks at the end of the block are dropped
+  fn parse_blank_run(&mut self) {
+    let start = self.position;
+    while let Some(Token::Space | Token::Tab) = self.tokens.token(self.position) {
+      self.position += 1;
+    }
+    let blanks = &self.tokens[start..self.position];
+
+    match self.tokens.token(self.position) {
+      Some(Token::NewLine) => {
+        let spaces = blanks
+          .iter()
+          .filter(|token| **token == Token::Space)
+          .count();
+        self.position += 1;
+        let line_break = match spaces >= 2 {
+          true => Inline::HardBreak,
+          false => Inline::SoftBreak,
+        };
+        self.push_node(line_break, start);
+      }
+      None => {}
+      Some(_) => {
+        let text = blanks
+          .iter()
+          .map(|token| String::from(token.clone()))
+          .collect();
+        self.push_node(Inline::Text(text), start);
+      }
+    }
+  }
+
+  /// Takes a run of text tokens as a single text node, decoding any entity references within it.
+  ///
+  /// Entity references are only made up of these tokens, so escaped chars will never be decoded.
+  /// Quotes are always part of these tokens, so they are made smart here when the option is set
+  fn parse_text(&mut self) {
+    let start = self.position;
+    let mut text = String::new();
+    while let Some(token @ (Token::Text(..) | Token::Number(..) | Token::Hash)) =
+      self.tokens.token(self.position)
+    {
+      text.push_str(&String::from(token.clone()));
+      self.position += 1;
+    }
+    if self.context.options.smart {
+      text = self.replace_quotes(&text, start);
+    }
+    self.push_node(Inline::Text(decode_entities(&text)), start);
+  }
+
+  /// The source text of the tokens from `start` to the end of the block
+  pub(super) fn source_from(&self, start: usize) -> &str {
+    &self.source[self.offsets[start.min(self.tokens.len())]..]
+  }
+
+  /// The index of the token beginning `length` bytes into the source from the token `start`.
+  /// This is `None` if that point is partway through a token
+  pub(super) fn token_after(&self, start: usize, length: usize) -> Option<usize> {
+    let offset = self.offsets[start.min(self.tokens.len())] + length;
+    let index = self
+      .offsets
+      .partition_point(|token_offset| *token_offset < offset);
+    (self.offsets.get(index) == Some(&offset)).then_some(index)
+  }
+
+  /// Adds a node made from the tokens between `start` and the current position
+  pub(super) fn push_node(&mut self, inline: Inline, start: usize) {
+    let span = self.span_of(start, self.position);
+    self.nodes.push(Spanned::new(inline, span));
+  }
+
+  /// The span covering the tokens from `start` up to `end`. If there are none, an empty span is placed where they would be
+  pub(super) fn span_of(&self, start: usize, end: usize) -> Span {
+    Span::join_all(self.tokens[start..end].iter().map(|token| &token.span))
+      .unwrap_or_else(|| Span::empty(end_position(&self.tokens[..start])))
+  }
+}
+
+/// Joins neighbouring text nodes and drops any that have been emptied by the delimiter stack
+fn merge_text(nodes: Vec<Spanned<Inline>>) -> Vec<Spanned<Inline>> {
+  let mut merged: Vec<Spanned<Inline>> = vec![];
+  for Spanned { value: node, span } in nodes {
+    let node = match (merged.last_mut(), node) {
+      (_, Inline::Text(text)) if text.is_empty() => continue,
+      (
+        Some(Spanned {
+          value: Inline::Text(last),
+          span: last_span,
+        }),
+        Inline::Text(text),
+      ) => {
+        last.push_str(&text);
+        *last_span = last_span.join(&span);
+        continue;
+      }
+      (_, Inline::Emphasis(inner)) => Inline::Emphasis(merge_text(inner)),
+      (_, Inline::Strong(inner)) => Inline::Strong(merge_text(inner)),
+      (
+        _,
+        Inline::Link {
+          dest,
+          title,
+          children,
+        },
+      ) => Inline::Link {
+        dest,
+        title,
+        children: merge_text(children),
+      },
+      (
+        _,
+        Inline::Image {
+          dest,
+          title,
+          children,
+        },
+      ) => Inline::Image {
+        dest,
+        title,
+        children: merge_text(children),
+      },
+      (_, node) => node,
+    };
+    merged.push(Spanned::new(node, span));
+  }
+  merged
+}
@@ -0,0 +1,106 @@
+use crate::{
+  parse::helpers::is_punctuation,
+  tokeniser::{Token, TokenSlice},
+};
+
+use super::{
+  emphasis::{first_char, last_char},
+  parser::InlineParser,
+  Inline,
+};
+
+/// Tracks quotes which have been opened, so that the next quote of the same kind which can close becomes a closer
+#[derive(Debug, Default)]
+pub(crate) struct OpenQuotes {
+  single: bool,
+  double: bool,
+}
+
+impl InlineParser<'_> {
+  /// Consumes a run of `-` tokens. Two or more become en and em dashes, following the same rules as cmark so that
+  /// runs are split as evenly as possible, preferring em dashes
+  pub(super) fn parse_dashes(&mut self) {
+    let start = self.position;
+    while self.tokens.token(self.position) == Some(&Token::Dash) {
+      self.position += 1;
+    }
+    let length = self.position - start;
+
+    let (em, en) = match length {
+      1 => {
+        self.push_node(Inline::Text("-".to_string()), start);
+        return;
+      }
+      length if length % 3 == 0 => (length / 3, 0),
+      length if length % 2 == 0 => (0, length / 2),
+      length if length % 3 == 2 => ((length - 2) / 3, 1),
+      length => ((length - 4) / 3, 2),
+    };
+    let dashes = "—".repeat(em) + &"–".repeat(en);
+    self.push_node(Inline::Text(dashes), start);
+  }
+
+  /// Consumes `...` as an ellipsis, or a single `.` as is
+  pub(super) fn parse_ellipsis(&mut self) {
+    let start = self.position;
+    let is_ellipsis = (0..3).all(|offset| self.tokens.token(start + offset) == Some(&Token::Dot));
+    let (text, length) = match is_ellipsis {
+      true => ("…", 3),
+      false => (".", 1),
+    };
+    self.position += length;
+    self.push_node(Inline::Text(text.to_string()), start);
+  }
+
+  /// Replaces the straight quotes in `text`, which was taken from the tokens between `start` and the current position,
+  /// with curly ones.
+  ///
+  /// Quotes which could open (left flanking but not right flanking) become opening quotes, while any which could
+  /// close a quote that was opened before become closing quotes. A lone `'` is treated as an apostrophe
+  pub(super) fn replace_quotes(&mut self, text: &str, start: usize) -> String {
+    // The start and end of a block count as whitespace
+    let before = match start {
+      0 => ' ',
+      _ => last_char(&self.tokens[start - 1]),
+    };
+    let after = self.tokens.token(self.position).map_or(' ', first_char);
+
+    let chars: Vec<char> = text.chars().collect();
+    let mut replaced = String::with_capacity(text.len());
+    for (index, &char) in chars.iter().enumerate() {
+      if char != '"' && char != '\'' {
+        replaced.push(char);
+        continue;
+      }
+      let previous = index.checked_sub(1).map_or(before, |index| chars[index]);
+      let next = chars.get(index + 1).copied().unwrap_or(after);
+
+      // A link or parenthesis ends like a word would, so a quote after one can close, as the apostrophe in `[a]'s` does
+      let previous_is_word = !is_punctuation(previous) || matches!(previous, ']' | ')');
+
+      let left_flanking = !next.is_whitespace()
+        && (!is_punctuation(next) || previous.is_whitespace() || !previous_is_word);
+      let right_flanking = !previous.is_whitespace()
+        && (previous_is_word || next.is_whitespace() || is_punctuation(next));
+      let can_open = left_flanking && !right_flanking;
+
+      let (open, quotes) = match char {
+        '"' => (&mut self.open_quotes.double, ('“', '”')),
+        _ => (&mut self.open_quotes.single, ('‘', '’')),
+      };
+      let quote = if right_flanking && *open {
+        *open = false;
+        quotes.1
+      } else if can_open {
+        *open = true;
+        quotes.0
+      } else if char == '"' && !right_flanking {
+        quotes.0
+      } else {
+        quotes.1
+      };
+      replaced.push(quote);
+    }
+    replaced
+  }
+}
@@ -1,14 +1,17 @@
-use super::{blocks::BlockType, list::ListType};
-use crate::tokeniser::{Bracket, Token, TokenType, VecNum};
+use super::{blocks::BlockType, html::html_block_start, list::ListType};
+use crate::{
+  span::{Span, Spanned},
+  tokeniser::{Bracket, Token, TokenSlice, TokenType, VecNum},
+};
 
 const TAB_SIZE: usize = 4;
 
 #[derive(Debug, PartialEq, Clone)]
-pub struct Line(pub Vec<Token>);
+pub struct Line(pub Vec<Spanned<Token>>);
 impl Line {
   // Returns true if the Line is empty
   pub fn is_empty(&self) -> bool {
-    self.0.iter().fold(true, |empty, token| match token {
+    self.0.iter().fold(true, |empty, token| match token.value {
       Token::Space => empty,
       Token::Tab => empty,
       _ => false,
@@ -24,7 +27,7 @@ impl Line {
     }
 
     for (i, token) in self.0.iter().enumerate() {
-      match token {
+      match token.value {
         Token::Space => space_count += 1,
         Token::Tab => space_count += TAB_SIZE,
         _ => {
@@ -58,12 +61,26 @@ impl Line {
 
   /// Gets the block type for a line so that it can be parsed
   ///
-  /// None Lazy fallbacks will be ignored
-  pub fn line_type(&self, previous_block: Option<BlockType>) -> BlockType {
+  /// None Lazy fallbacks will be ignored.
+  /// `table_header` is the line before this one if it was part of a paragraph and tables are enabled,
+  /// in which case this line can turn it into the header of a table
+  pub fn line_type(
+    &self,
+    previous_block: Option<BlockType>,
+    table_header: Option<&Line>,
+  ) -> BlockType {
     // We should only overwrite the fallback if the type is lazy
     let fallback = match previous_block {
+      // Everything is content until the fence is closed
+      Some(BlockType::FencedCodeBlock { .. }) => return previous_block.unwrap(),
+      // HTML blocks of type 6 and 7 end on a blank line, which is handled with the rest of the blank lines
+      Some(BlockType::HtmlBlock(kind)) if kind < 6 || !self.is_empty() => {
+        return previous_block.unwrap()
+      }
       Some(BlockType::List(..)) => previous_block.unwrap(),
       Some(BlockType::BlockQuote) => previous_block.unwrap(),
+      // Any line that doesn't start another block is another row
+      Some(BlockType::Table) => BlockType::Table,
       Some(BlockType::IndentedCodeBlock) => {
         if self.is_empty() {
           return previous_block.unwrap();
@@ -74,6 +91,13 @@ impl Line {
       _ => BlockType::Paragraph,
     };
 
+    // A delimiter row comes before anything else, as it could otherwise be taken for a list or a setext header
+    if let Some(header) = table_header.filter(|_| previous_block == Some(BlockType::Paragraph)) {
+      if self.table_alignments(header.table_cells().len()).is_some() {
+        return BlockType::Table;
+      }
+    }
+
     let mut current: Vec<TokenType> = vec![];
     for (i, token) in self.0.iter().enumerate() {
       current.push(token.clone().into());
@@ -86,67 +110,69 @@ impl Line {
             return BlockType::ThematicBreak;
           }
         }
-        [TokenType::Underscore] => {
-          if self.is_all(&TokenType::Underscore, 3, true, true) {
-            return BlockType::ThematicBreak;
-          }
+        [TokenType::Underscore] if self.is_all(&TokenType::Underscore, 3, true, true) => {
+          return BlockType::ThematicBreak;
         }
-        [TokenType::Star] => {
-          if self.is_all(&TokenType::Star, 3, true, true) {
-            return BlockType::ThematicBreak;
-          }
+        [TokenType::Star] if self.is_all(&TokenType::Star, 3, true, true) => {
+          return BlockType::ThematicBreak;
         }
-        [TokenType::Hash] => {
-          if self.is_space(i + 1) {
-            return BlockType::Header(1);
-          }
+        [TokenType::Hash] if self.is_space(i + 1) => {
+          return BlockType::Header(1);
         }
-        [TokenType::Hash, TokenType::Hash] => {
-          if self.is_space(i + 1) {
-            return BlockType::Header(2);
-          }
+        [TokenType::Hash, TokenType::Hash] if self.is_space(i + 1) => {
+          return BlockType::Header(2);
         }
-        [TokenType::Hash, TokenType::Hash, TokenType::Hash] => {
-          if self.is_space(i + 1) {
-            return BlockType::Header(3);
-          }
+        [TokenType::Hash, TokenType::Hash, TokenType::Hash] if self.is_space(i + 1) => {
+          return BlockType::Header(3);
         }
-        [TokenType::Hash, TokenType::Hash, TokenType::Hash, TokenType::Hash] => {
-          if self.is_space(i + 1) {
-            return BlockType::Header(4);
-          }
+        [TokenType::Hash, TokenType::Hash, TokenType::Hash, TokenType::Hash]
+          if self.is_space(i + 1) =>
+        {
+          return BlockType::Header(4);
         }
-        [TokenType::Hash, TokenType::Hash, TokenType::Hash, TokenType::Hash, TokenType::Hash] => {
-          if self.is_space(i + 1) {
-            return BlockType::Header(5);
-          }
+        [TokenType::Hash, TokenType::Hash, TokenType::Hash, TokenType::Hash, TokenType::Hash]
+          if self.is_space(i + 1) =>
+        {
+          return BlockType::Header(5);
         }
-        [TokenType::Hash, TokenType::Hash, TokenType::Hash, TokenType::Hash, TokenType::Hash, TokenType::Hash] => {
-          if self.is_space(i + 1) {
-            return BlockType::Header(6);
-          }
+        [TokenType::Hash, TokenType::Hash, TokenType::Hash, TokenType::Hash, TokenType::Hash, TokenType::Hash]
+          if self.is_space(i + 1) =>
+        {
+          return BlockType::Header(6);
         }
-        [TokenType::Equals] => {
-          if fallback == BlockType::Paragraph && self.is_all(&TokenType::Equals, 1, false, true) {
-            return BlockType::SetextHeader(1);
-          }
+        [TokenType::Equals]
+          if fallback == BlockType::Paragraph
+            && self.is_all(&TokenType::Equals, 1, false, true) =>
+        {
+          return BlockType::SetextHeader(1);
         }
         [TokenType::Space, TokenType::Space, TokenType::Space, TokenType::Space] => {
           match previous_block {
             Some(BlockType::List(..)) => continue,
-            Some(BlockType::Paragraph) => continue,
+            Some(BlockType::Paragraph | BlockType::Table) => continue,
             _ => return BlockType::IndentedCodeBlock,
           };
         }
+        [TokenType::BackTick] | [TokenType::Tilde] => {
+          if let Some(fenced) = self.opening_fence() {
+            return fenced;
+          }
+        }
+        [TokenType::OpenBracket(Bracket::Angle)] => {
+          let can_interrupt = previous_block != Some(BlockType::Paragraph);
+          if let Some(kind) = html_block_start(&self.source(), can_interrupt) {
+            return BlockType::HtmlBlock(kind);
+          }
+        }
         [TokenType::CloseBracket(Bracket::Angle)] => return BlockType::BlockQuote,
         [TokenType::Dash, TokenType::Space] => return BlockType::List(ListType::Dash),
         [TokenType::Plus, TokenType::Space] => return BlockType::List(ListType::Plus),
         [TokenType::Star, TokenType::Space] => return BlockType::List(ListType::Star),
         [TokenType::Number, TokenType::CloseBracket(Bracket::Parenthesis), TokenType::Space] => {
-          return BlockType::List(ListType::BracketedNumber(grab_number(&self, 0).to_usize()))
+          return BlockType::List(ListType::BracketedNumber(grab_number(self, 0).to_usize()))
         }
         [TokenType::Number, TokenType::Dot, TokenType::Space] => {
-          let num = grab_number(&self, 0);
+          let num = grab_number(self, 0);
           // Note that ordered list start numbers must be nine digits or less:
           if num.0.len() > 9 {
             continue;
@@ -160,9 +186,62 @@ impl Line {
     fallback
   }
 
+  /// Checks if the line opens a [fenced code block](https://spec.commonmark.org/0.31.2/#fenced-code-blocks)
+  fn opening_fence(&self) -> Option<BlockType> {
+    let fence = match self.0.token(0)? {
+      Token::BackTick => '`',
+      Token::Tilde => '~',
+      _ => return None,
+    };
+    let length = self.fence_length();
+    if length < 3 {
+      return None;
+    }
+
+    // The info string of a backtick fence can't contain backticks, otherwise it could be mistaken for inline code
+    if fence == '`'
+      && self.0[length..]
+        .iter()
+        .any(|token| matches!(token.value, Token::BackTick | Token::Escaped('`')))
+    {
+      return None;
+    }
+
+    Some(BlockType::FencedCodeBlock {
+      fence,
+      length,
+      indent: 0,
+    })
+  }
+
+  /// Checks if the line closes the fenced code block `block_type`.
+  ///
+  /// The closing fence must be made of the same char and be at least as long as the opening fence
+  pub fn is_closing_fence(&self, block_type: &BlockType) -> bool {
+    let BlockType::FencedCodeBlock { fence, length, .. } = block_type else {
+      return false;
+    };
+    let token = match fence {
+      '`' => Token::BackTick,
+      _ => Token::Tilde,
+    };
+    let fence_length = self.fence_length();
+    self.0.token(0) == Some(&token)
+      && fence_length >= *length
+      && Line(self.0[fence_length..].to_vec()).is_empty()
+  }
+
+  /// Counts how many of the starting tokens match the first token
+  fn fence_length(&self) -> usize {
+    let Some(first) = self.0.token(0) else {
+      return 0;
+    };
+    self.0.iter().take_while(|token| *token == first).count()
+  }
+
   fn is_space(&self, i: usize) -> bool {
-    let token = self.0.get(i);
-    token == Some(&Token::Space) || token == Some(&Token::Tab) || token == None
+    let token = self.0.token(i);
+    token == Some(&Token::Space) || token == Some(&Token::Tab) || token.is_none()
   }
 
   fn to_token_types(&self) -> Vec<TokenType> {
@@ -206,8 +285,8 @@ impl Line {
           return false;
         }
       }
-      return token == needle
-        || allow_inline_blanks && (token == &TokenType::Space || token == &TokenType::Tab);
+      token == needle
+        || allow_inline_blanks && (token == &TokenType::Space || token == &TokenType::Tab)
     }) && count >= min_number
   }
 
@@ -219,28 +298,30 @@ impl Line {
         self.remove_all_indentation();
       }
       BlockType::BlockQuote => {
-        if self.0.get(leading_spaces) == Some(&Token::CloseBracket(Bracket::Angle)) {
-          self.trim_line_start(leading_spaces + 1);
+        if self.0.token(leading_spaces) == Some(&Token::CloseBracket(Bracket::Angle)) {
+          // The block quote marker includes an optional following space
+          let marker_space = usize::from(self.0.token(leading_spaces + 1) == Some(&Token::Space));
+          self.trim_line_start(leading_spaces + 1 + marker_space);
         } else {
           // as this is a continuation, we need to do some weirdness to stop certain types from being converted when parsed within a blockquote
-          if matches!(self.line_type(None), BlockType::SetextHeader(..)) {
+          if matches!(self.line_type(None, None), BlockType::SetextHeader(..)) {
             self.stringify_line();
           }
         }
       }
       BlockType::Header(level) => {
         let end = leading_spaces + *level as usize;
-        if self.0.get(end - 1) == Some(&Token::Hash) {
+        if self.0.token(end - 1) == Some(&Token::Hash) {
           // This is horrible but the only way to handle blank headers right now
           self.trim_line_start(end);
         }
 
         self.remove_ending_blanks();
-        if self.0.len() == 0 {
+        if self.0.is_empty() {
           return;
         }
         let mut end_to_remove = self.0.len();
-        while end_to_remove > 1 && self.0.get(end_to_remove - 1) == Some(&Token::Hash) {
+        while end_to_remove > 1 && self.0.token(end_to_remove - 1) == Some(&Token::Hash) {
           end_to_remove -= 1;
         }
         if self.is_space(end_to_remove - 1) {
@@ -257,7 +338,7 @@ impl Line {
         }
       }
       BlockType::IndentedCodeBlock => {
-        if matches!(self.line_type(None), BlockType::IndentedCodeBlock) {
+        if matches!(self.line_type(None, None), BlockType::IndentedCodeBlock) {
           self.trim_line_start(4);
         }
       }
@@ -267,7 +348,7 @@ impl Line {
 
   pub fn remove_ending_blanks(&mut self) {
     let mut end = self.0.len();
-    if self.0.len() == 0 {
+    if self.0.is_empty() {
       return;
     }
     while self.is_space(end - 1) && end > 1 {
@@ -276,48 +357,45 @@ impl Line {
     self.0.drain(end..);
   }
 
-  /// Removed 1 level of indentation from a line (1 Tab or 4 Spaces)
-  pub fn unindent(&mut self) {
-    if self.0.get(0) == Some(&Token::Tab) {
-      self.0.drain(0..1);
-      return;
-    }
-    let space_count = self
-      .0
-      .iter()
-      .position(|token| token != &Token::Space)
-      .unwrap_or(0);
-    if space_count >= 4 {
-      self.0.drain(0..4);
-    }
-  }
-
   /// Removes a number of characters from the start of a line
   pub fn trim_line_start(&mut self, chars: usize) {
     self.0.drain(..chars);
   }
 
-  pub fn find_first(&self, needle: TokenType) -> Option<usize> {
-    self
-      .0
-      .iter()
-      .position(|token| Into::<TokenType>::into(token.clone()) == needle)
+  pub fn stringify_line(&mut self) {
+    let Some(span) = self.span() else {
+      return;
+    };
+    self.0 = vec![Spanned::new(Token::Text(self.source()), span)];
   }
 
-  pub fn stringify_line(&mut self) {
-    self.0 = vec![Token::Text(
-      self
-        .0
+  /// The span of source covered by the tokens in this line, if it has any
+  pub fn span(&self) -> Option<Span> {
+    self.span_from(0)
+  }
+
+  /// The span of source covered by the tokens in this line from the `start`th token onwards
+  pub fn span_from(&self, start: usize) -> Option<Span> {
+    Span::join_all(
+      self.0[start.min(self.0.len())..]
         .iter()
-        .map(|i| Into::<String>::into(i.clone()))
-        .collect::<Vec<String>>()
-        .join(""),
-    )];
+        .map(|token| &token.span),
+    )
+  }
+
+  /// Gets the line as it was written in the source
+  pub fn source(&self) -> String {
+    self
+      .0
+      .iter()
+      .map(|i| Into::<String>::into(i.clone()))
+      .collect::<Vec<String>>()
+      .join("")
   }
 }
 
 fn grab_number(line: &Line, index: usize) -> VecNum {
-  match line.0.get(index) {
+  match line.0.token(index) {
     Some(Token::Number(num)) => num.clone(),
     _ => VecNum(vec![0]),
   }
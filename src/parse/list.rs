@@ -1,56 +1,66 @@
 use crate::{
-  parse::list,
-  tokeniser::{Token, TokenType},
+  span::{Position, Spanned},
+  tokeniser::{Bracket, Token},
 };
 
 use self::super::{
   blocks::{Block, BlockType},
   document::DocContext,
+  helpers::{is_blank, new_line_after},
   line::Line,
 };
 
 use super::tokens_to_lines;
 
+/// The marker used by a list. Ordered lists hold the number they start from
 #[derive(Debug, PartialEq, Copy, Clone)]
-pub(crate) enum ListType {
+pub enum ListType {
+  /// An ordered list using `1.` style markers
   Number(usize),
+  /// An ordered list using `1)` style markers
   BracketedNumber(usize),
+  /// A bullet list using `-` markers
   Dash,
+  /// A bullet list using `*` markers
   Star,
+  /// A bullet list using `+` markers
   Plus,
 }
 
 /// Special parsing for line items within a list. Shouldn't be called directly
 pub fn parse_line_items(
-  tokens: &Vec<Token>,
+  tokens: &[Spanned<Token>],
   context: &mut DocContext,
   list_type: ListType,
-) -> Vec<Block> {
+) -> Vec<Spanned<Block>> {
   let mut lines = tokens_to_lines(tokens);
-  let mut blocks: Vec<Block> = vec![];
+  let mut blocks: Vec<Spanned<Block>> = vec![];
   let mut current_block = vec![];
+  // Items start from their marker rather than the content left in their tokens
+  let mut item_start = None;
   let mut count = 0;
   let mut last_line_start = 0;
 
   for line in lines.iter_mut() {
     let new_line_item = is_new_list_item(line, list_type, count) || last_line_start == 0;
-    if new_line_item && !is_indented_past_start(&line, last_line_start) {
-      last_line_start = list_item_content_start(&line);
+    if new_line_item && !is_indented_past_start(line, last_line_start) {
+      last_line_start = list_item_content_start(line);
       count += 1;
-      if current_block.len() > 0 {
-        blocks.push(Block::new(BlockType::LineItem, current_block, context));
+      if !current_block.is_empty() {
+        blocks.push(line_item(current_block, item_start, context));
       }
+      item_start = line.span_from(line.leading_spaces()).map(|span| span.start);
       line.trim_line_start(last_line_start);
       current_block = line.0.clone();
     } else {
-      if current_block.len() > 0 {
-        current_block.push(Token::NewLine);
+      if !current_block.is_empty() {
+        current_block.push(new_line_after(&current_block));
       }
 
-      if line.is_empty() == false {
+      if !line.is_empty() {
         // If this line has been indented to the item start,
         // remove that indentation only to allow for indented code blocks
-        if is_indented_past_start(&line, last_line_start) {
+        if is_indented_past_start(line, last_line_start) {
           line.trim_line_start(last_line_start);
         }
 
@@ -59,13 +69,25 @@ pub fn parse_line_items(
       }
     }
   }
-  if current_block.len() > 0 {
-    blocks.push(Block::new(BlockType::LineItem, current_block, context));
+  if !current_block.is_empty() {
+    blocks.push(line_item(current_block, item_start, context));
   }
 
   blocks
 }
 
+fn line_item(
+  tokens: Vec<Spanned<Token>>,
+  start: Option<Position>,
+  context: &mut DocContext,
+) -> Spanned<Block> {
+  let mut item = Block::new(BlockType::LineItem, tokens, context);
+  if let Some(start) = start {
+    item.span.start = start;
+  }
+  item
+}
+
 /// Returns true if first none space char is after `last_line_start`
 ///
 /// If the `last_line_start` value is 0, will always return false
@@ -79,15 +101,15 @@ fn is_indented_past_start(line: &Line, last_line_start: usize) -> bool {
 /// Determins if a line should be rendered as a new list item
 fn is_new_list_item(line: &Line, list_type: ListType, count: usize) -> bool {
   match list_type {
-    ListType::BracketedNumber(start) => match line.line_type(None) {
+    ListType::BracketedNumber(start) => match line.line_type(None, None) {
       BlockType::List(ListType::BracketedNumber(cur_count)) => cur_count == start + count,
       _ => false,
     },
-    ListType::Number(start) => match line.line_type(None) {
+    ListType::Number(start) => match line.line_type(None, None) {
       BlockType::List(ListType::Number(cur_count)) => cur_count == start + count,
       _ => false,
     },
-    _ => match line.line_type(None) {
+    _ => match line.line_type(None, None) {
       BlockType::List(line_list_type) => line_list_type == list_type,
       _ => false,
     },
@@ -101,7 +123,7 @@ fn first_char_after_list_indicator(line: &Line) -> usize {
       return i + 1;
     }
   }
-  return 0;
+  0
 }
 
 pub fn list_item_content_start(line: &Line) -> usize {
@@ -112,3 +134,84 @@ pub fn list_item_content_start(line: &Line) -> usize {
     .unwrap_or(line.0.len())
     + first_char_after_indicator
 }
+
+/// The width in columns of everything before the content of the list item starting `line`, its marker included.
+///
+/// This can be wider than [`list_item_content_start`] as the number of an ordered list marker is a single token
+pub fn list_item_content_width(line: &Line) -> usize {
+  Line(line.0[..list_item_content_start(line)].to_vec())
+    .source()
+    .len()
+}
+
+/// Checks if an item begins with a [task list item marker](https://github.github.com/gfm/#task-list-items-extension-),
+/// returning whether it is checked. `blocks` are the item's blocks parsed from `tokens` with the marker left in, so
+/// that what follows the marker stays part of the first paragraph rather than starting a block of its own.
+/// That paragraph is parsed again without the marker.
+///
+/// The marker has to start a paragraph, as on GitHub
+pub(crate) fn strip_task_marker(
+  tokens: &[Spanned<Token>],
+  blocks: &mut Vec<Spanned<Block>>,
+  context: &mut DocContext,
+) -> Option<bool> {
+  let (checked, length) = task_marker(tokens)?;
+  let paragraph = blocks
+    .first()
+    .filter(|block| matches!(block.value, Block::Paragraph(..)))?;
+  // The marker is made up of the four tokens before `length`
+  if paragraph.span.start != tokens[length - 4].span.start {
+    return None;
+  }
+
+  // The paragraph's lines have their indentation removed, as they would when parsing it the first time
+  let end = tokens.partition_point(|token| token.span.start.offset < paragraph.span.end.offset);
+  let content =
+    tokens_to_lines(&tokens[length..end])
+      .into_iter()
+      .fold(vec![], |mut content, mut line| {
+        line.remove_all_indentation();
+        if !content.is_empty() {
+          content.push(new_line_after(&content));
+        }
+        content.append(&mut line.0);
+        content
+      });
+  let paragraph = Block::new(BlockType::Paragraph, content, context);
+  match &paragraph.value {
+    // What's left may only be link reference definitions
+    Block::Paragraph(inlines) if inlines.is_empty() => {
+      blocks.remove(0);
+    }
+    _ => blocks[0] = paragraph,
+  }
+  Some(checked)
+}
+
+/// Checks if `tokens` begin with a task list item marker, returning whether it is checked and how many tokens
+/// the marker takes up, including the blank after it.
+///
+/// As on GitHub, the marker has to be followed by some content on the same line
+fn task_marker(tokens: &[Spanned<Token>]) -> Option<(bool, usize)> {
+  let start = tokens.iter().position(|token| !is_blank(token))?;
+  let [open, state, close, blank, rest @ ..] = &tokens[start..] else {
+    return None;
+  };
+  let has_content = rest
+    .iter()
+    .take_while(|token| token != &&Token::NewLine)
+    .any(|token| !is_blank(token));
+  if open != &Token::OpenBracket(Bracket::Square)
+    || close != &Token::CloseBracket(Bracket::Square)
+    || !is_blank(blank)
+    || !has_content
+  {
+    return None;
+  }
+  let checked = match &state.value {
+    Token::Space | Token::Tab => false,
+    Token::Text(text) if text == "x" || text == "X" => true,
+    _ => return None,
+  };
+  Some((checked, start + 4))
+}
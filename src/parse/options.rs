@@ -0,0 +1,85 @@
+use crate::render::Sanitiser;
+
+/// How a [soft line break](https://spec.commonmark.org/0.31.2/#soft-line-breaks) is written out
+#[derive(Debug, PartialEq, Clone, Copy, Default)]
+pub enum SoftBreak {
+  /// Keep the line ending as it was written
+  #[default]
+  Newline,
+  /// Join the lines with a single space
+  Space,
+  /// Render the break the same as a hard line break, `<br />`
+  LineBreak,
+}
+
+/// Options which change how a document is parsed. The defaults follow CommonMark strictly, as [`crate::parse`] does.
+///
+/// ```
+/// use smarkdown::{parse_with_options, ParseOptions};
+///
+/// let document = parse_with_options("\"Wait...\" -- she said", &ParseOptions::default().smart(true));
+/// assert_eq!(document.as_html(), "<p>“Wait…” – she said</p>");
+/// ```
+#[derive(Debug, PartialEq, Clone, Default)]
+pub struct ParseOptions {
+  /// Turns straight quotes into curly quotes, `--` and `---` into en and em dashes and `...` into an ellipsis
+  pub smart: bool,
+  /// Enables [GFM tables](https://github.github.com/gfm/#tables-extension-)
+  pub tables: bool,
+  /// Enables [GFM task list items](https://github.github.com/gfm/#task-list-items-extension-), which begin with `[ ]` or `[x]`
+  pub tasklists: bool,
+}
+
+impl ParseOptions {
+  pub fn smart(mut self, smart: bool) -> Self {
+    self.smart = smart;
+    self
+  }
+
+  pub fn tables(mut self, tables: bool) -> Self {
+    self.tables = tables;
+    self
+  }
+
+  pub fn tasklists(mut self, tasklists: bool) -> Self {
+    self.tasklists = tasklists;
+    self
+  }
+}
+
+/// Options which change how a document is rendered
+#[derive(Debug, PartialEq, Clone, Default)]
+pub struct RenderOptions {
+  pub soft_break: SoftBreak,
+  /// Adds a cmark style `data-sourcepos` attribute to block level elements, giving the lines and columns they came from.
+  /// XML output gives every node a `sourcepos` attribute instead
+  pub sourcepos: bool,
+  /// Makes HTML output safe to show when the markdown can't be trusted, as cmark's `--safe` does.
+  /// Raw HTML is replaced with a comment, and `javascript:`, `vbscript:`, `file:` and `data:` URLs (other than
+  /// those for png, gif, jpeg and webp images) are written as empty links
+  pub safe: bool,
+  /// Cleans up raw HTML in HTML output using an allow-list, rather than passing it through as is. `safe` takes priority
+  pub sanitiser: Option<Sanitiser>,
+}
+
+impl RenderOptions {
+  pub fn soft_break(mut self, soft_break: SoftBreak) -> Self {
+    self.soft_break = soft_break;
+    self
+  }
+
+  pub fn sourcepos(mut self, sourcepos: bool) -> Self {
+    self.sourcepos = sourcepos;
+    self
+  }
+
+  pub fn safe(mut self, safe: bool) -> Self {
+    self.safe = safe;
+    self
+  }
+
+  pub fn sanitiser(mut self, sanitiser: Sanitiser) -> Self {
+    self.sanitiser = Some(sanitiser);
+    self
+  }
+}
@@ -0,0 +1,114 @@
+use crate::{span::Spanned, tokeniser::Token};
+
+use super::{
+  document::{DocContext, LinkReference},
+  helpers::new_line_after,
+  inlines::{scan_link_destination, scan_link_label, scan_link_title, skip_whitespace},
+  tokens_to_lines,
+};
+
+/// Removes any [link reference definitions](https://spec.commonmark.org/0.31.2/#link-reference-definitions)
+/// from the start of a paragraph's tokens, storing them on the context.
+///
+/// Returns the tokens left over once all definitions have been removed
+pub fn extract_link_references(
+  tokens: &[Spanned<Token>],
+  context: &mut DocContext,
+) -> Vec<Spanned<Token>> {
+  let lines = tokens_to_lines(tokens);
+  // The paragraph's text is built once, with definitions being scanned from a moving offset into it
+  let mut text = String::new();
+  let mut line_starts = Vec::with_capacity(lines.len());
+  for line in &lines {
+    if !line_starts.is_empty() {
+      text.push('\n');
+    }
+    line_starts.push(text.len());
+    text.extend(line.0.iter().map(|token| String::from(token.clone())));
+  }
+
+  let mut offset = 0;
+  while let Some((label, reference, length)) = scan_link_reference(&text[offset..]) {
+    // The first definition for a label takes precedence
+    context
+      .link_references
+      .entry(normalise_label(&label))
+      .or_insert(reference);
+    offset += length;
+    if offset == text.len() {
+      break;
+    }
+  }
+
+  // Definitions always end at the end of a line, so only whole lines are removed
+  let used_lines = match offset == text.len() && offset > 0 {
+    true => lines.len(),
+    false => line_starts.partition_point(|start| *start < offset),
+  };
+  let remaining = &lines[used_lines..];
+
+  remaining.iter().fold(vec![], |mut tokens, line| {
+    if !tokens.is_empty() {
+      tokens.push(new_line_after(&tokens));
+    }
+    tokens.extend(line.0.iter().cloned());
+    tokens
+  })
+}
+
+/// Scans a single definition from the start of `text`, returning its label, the reference and the number of bytes used
+fn scan_link_reference(text: &str) -> Option<(String, LinkReference, usize)> {
+  let (label, mut position) = scan_link_label(text)?;
+  if !text[position..].starts_with(':') {
+    return None;
+  }
+  position += 1;
+  position += skip_whitespace(&text[position..]);
+
+  let (dest, length) = scan_link_destination(&text[position..])?;
+  position += length;
+
+  // A title must be separated from the destination by whitespace and be followed only by spaces or tabs
+  let spacing = skip_whitespace(&text[position..]);
+  if spacing > 0 {
+    let title_start = position + spacing;
+    if let Some((title, length)) = scan_link_title(&text[title_start..]) {
+      if let Some(end) = line_end(text, title_start + length) {
+        let reference = LinkReference {
+          dest,
+          title: Some(title).filter(|title| !title.is_empty()),
+        };
+        return Some((label, reference, end));
+      }
+    }
+  }
+
+  // Otherwise there is no title, so the line has to end after the destination
+  let end = line_end(text, position)?;
+  Some((label, LinkReference { dest, title: None }, end))
+}
+
+/// If only spaces or tabs exist from `position` to the end of the line, returns the position after the line ending
+fn line_end(text: &str, position: usize) -> Option<usize> {
+  let rest = &text[position..];
+  let blanks = rest
+    .find(|char| char != ' ' && char != '\t')
+    .unwrap_or(rest.len());
+  match rest[blanks..].chars().next() {
+    None => Some(text.len()),
+    Some('\n') => Some(position + blanks + 1),
+    _ => None,
+  }
+}
+
+/// Normalises a link label so they can be matched case insensitively and without caring about whitespace
+pub fn normalise_label(label: &str) -> String {
+  // Going through uppercase folds characters such as `ẞ` the same way as `SS`
+  label
+    .split_whitespace()
+    .collect::<Vec<&str>>()
+    .join(" ")
+    .to_lowercase()
+    .to_uppercase()
+    .to_lowercase()
+}
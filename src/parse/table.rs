@@ -0,0 +1,154 @@
+use crate::{
+  span::{Position, Span, Spanned},
+  tokeniser::Token,
+};
+
+use super::{
+  document::DocContext,
+  helpers::{end_position, is_blank},
+  line::Line,
+  parse_inlines, Inline,
+};
+
+/// How the cells of a table column are aligned, set by colons in the delimiter row
+#[derive(Debug, PartialEq, Copy, Clone, Default)]
+pub enum Alignment {
+  /// `---`, which leaves the alignment to the browser
+  #[default]
+  None,
+  /// `:---`
+  Left,
+  /// `:---:`
+  Center,
+  /// `---:`
+  Right,
+}
+
+/// A row of a [`Block::Table`](super::Block::Table), holding the inline content of each cell
+pub type TableRow = Vec<Spanned<Vec<Spanned<Inline>>>>;
+
+impl Line {
+  /// Splits a table row into the tokens of each cell, with the blanks around each cell removed.
+  ///
+  /// Cells are separated by `|`, with the pipes at the start and end of the row being optional.
+  /// Escaped pipes become part of the cell's text, even within code spans, as they would in GFM
+  pub(crate) fn table_cells(&self) -> Vec<Vec<Spanned<Token>>> {
+    let mut cells = vec![vec![]];
+    for token in &self.0 {
+      match &token.value {
+        Token::Text(text) if text.contains('|') => {
+          for (i, part) in text.split('|').enumerate() {
+            if i > 0 {
+              cells.push(vec![]);
+            }
+            if !part.is_empty() {
+              let start = part.as_ptr() as usize - text.as_ptr() as usize;
+              let span = sub_span(&token.span, start, start + part.len());
+              cells
+                .last_mut()
+                .unwrap()
+                .push(Spanned::new(Token::Text(part.to_string()), span));
+            }
+          }
+        }
+        Token::Escaped('|') => {
+          let pipe = Spanned::new(Token::Text("|".to_string()), token.span);
+          cells.last_mut().unwrap().push(pipe);
+        }
+        _ => cells.last_mut().unwrap().push(token.clone()),
+      }
+    }
+
+    let mut cells: Vec<Vec<Spanned<Token>>> = cells.into_iter().map(trim_blanks).collect();
+    // The pipes at either end of the row leave an empty cell outside of them
+    if cells.len() > 1 && cells.last().is_some_and(Vec::is_empty) {
+      cells.pop();
+    }
+    if cells.len() > 1 && cells[0].is_empty() && self.source().trim_start().starts_with('|') {
+      cells.remove(0);
+    }
+    cells
+  }
+
+  /// Reads the alignment of each column if this is a [delimiter row](https://github.github.com/gfm/#delimiter-row)
+  /// for a table with `columns` columns.
+  ///
+  /// The row needs at least one pipe so that a single column can't be mistaken for a setext header
+  pub(crate) fn table_alignments(&self, columns: usize) -> Option<Vec<Alignment>> {
+    if !self.source().contains('|') {
+      return None;
+    }
+    let cells = self.table_cells();
+    if cells.len() != columns {
+      return None;
+    }
+    cells
+      .iter()
+      .map(|cell| {
+        let cell: String = cell
+          .iter()
+          .map(|token| String::from(token.clone()))
+          .collect();
+        let left = cell.starts_with(':');
+        let right = cell.len() > 1 && cell.ends_with(':');
+        let dashes = &cell[usize::from(left)..cell.len() - usize::from(right)];
+        if dashes.is_empty() || !dashes.chars().all(|char| char == '-') {
+          return None;
+        }
+        Some(match (left, right) {
+          (true, true) => Alignment::Center,
+          (true, false) => Alignment::Left,
+          (false, true) => Alignment::Right,
+          (false, false) => Alignment::None,
+        })
+      })
+      .collect()
+  }
+}
+
+/// Parses the cells of a table row, filling it with empty cells or cutting it down so that it has `columns` cells
+pub(crate) fn parse_table_row(
+  line: &Line,
+  columns: usize,
+  context: &mut DocContext,
+) -> Spanned<TableRow> {
+  let span = line
+    .span()
+    .unwrap_or_else(|| Span::empty(end_position(&line.0)));
+  let mut cells: TableRow = line
+    .table_cells()
+    .into_iter()
+    .take(columns)
+    .map(|cell| {
+      let cell_span = Span::join_all(cell.iter().map(|token| &token.span))
+        .unwrap_or_else(|| Span::empty(span.end));
+      Spanned::new(parse_inlines(&cell, context), cell_span)
+    })
+    .collect();
+  cells.resize_with(columns, || Spanned::new(vec![], Span::empty(span.end)));
+  Spanned::new(cells, span)
+}
+
+fn trim_blanks(mut cell: Vec<Spanned<Token>>) -> Vec<Spanned<Token>> {
+  let start = cell
+    .iter()
+    .position(|token| !is_blank(token))
+    .unwrap_or(cell.len());
+  let end = cell
+    .iter()
+    .rposition(|token| !is_blank(token))
+    .map_or(start, |end| end + 1);
+  cell.truncate(end);
+  cell.drain(..start);
+  cell
+}
+
+/// The span of the bytes from `start` to `end` within a token covering `span`, which can't cross a line
+fn sub_span(span: &Span, start: usize, end: usize) -> Span {
+  let position = |offset: usize| Position {
+    offset: span.start.offset + offset,
+    line: span.start.line,
+    column: span.start.column + offset,
+  };
+  Span::new(position(start), position(end))
+}
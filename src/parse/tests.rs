@@ -1,10 +1,29 @@
 use crate::{
-  parse::{self, parse, tokens_to_lines},
+  parse::{
+    parse, parse_with_options, tokens_to_lines, Block, Inline, ParseOptions, RenderOptions,
+    SoftBreak,
+  },
+  span::{Position, Span, Spanned},
   tokeniser::Token,
 };
 
 use super::line::Line;
 
+/// Builds a line from tokens that weren't parsed from a source
+fn unspanned_line(tokens: Vec<Token>) -> Line {
+  Line(tokens.into_iter().map(Spanned::from).collect())
+}
+
+/// Builds a span from `(offset, line, column)` pairs
+fn span(start: (usize, usize, usize), end: (usize, usize, usize)) -> Span {
+  let position = |(offset, line, column)| Position {
+    offset,
+    line,
+    column,
+  };
+  Span::new(position(start), position(end))
+}
+
 #[test]
 fn line_test() {
   let input = vec![
@@ -14,38 +33,41 @@ fn line_test() {
     Token::NewLine,
     Token::NewLine,
     Token::Text("some paragraph content 2".to_string()),
-  ];
+  ]
+  .into_iter()
+  .map(Spanned::from)
+  .collect::<Vec<_>>();
   let expected_lines = vec![
-    Line(vec![Token::Text("Title".to_string())]),
-    Line(vec![Token::Text("some paragraph content".to_string())]),
-    Line(vec![]),
-    Line(vec![Token::Text("some paragraph content 2".to_string())]),
+    unspanned_line(vec![Token::Text("Title".to_string())]),
+    unspanned_line(vec![Token::Text("some paragraph content".to_string())]),
+    unspanned_line(vec![]),
+    unspanned_line(vec![Token::Text("some paragraph content 2".to_string())]),
   ];
   assert_eq!(tokens_to_lines(&input), expected_lines);
 }
 
 #[test]
 fn indentation_test() {
-  let mut lines = vec![
-    Line(vec![]),
-    Line(vec![Token::Space, Token::Text("Title".to_string())]),
-    Line(vec![
+  let mut lines = [
+    unspanned_line(vec![]),
+    unspanned_line(vec![Token::Space, Token::Text("Title".to_string())]),
+    unspanned_line(vec![
       Token::Space,
       Token::Space,
       Token::Space,
       Token::Space,
       Token::Text("some paragraph content".to_string()),
     ]),
-    Line(vec![
+    unspanned_line(vec![
       Token::Tab,
       Token::Text("some paragraph content".to_string()),
     ]),
-    Line(vec![
+    unspanned_line(vec![
       Token::Tab,
       Token::Space,
       Token::Text("some paragraph content".to_string()),
     ]),
-    Line(vec![
+    unspanned_line(vec![
       Token::Tab,
       Token::Tab,
       Token::Text("some paragraph content".to_string()),
@@ -67,24 +89,24 @@ fn indentation_test() {
 
 #[test]
 fn remove_blank_end() {
-  let mut line = Line(vec![
+  let mut line = unspanned_line(vec![
     Token::Text("Hey".to_string()),
     Token::Space,
     Token::Space,
   ]);
   line.remove_ending_blanks();
-  assert_eq!(line, Line(vec![Token::Text("Hey".to_string())]));
+  assert_eq!(line, unspanned_line(vec![Token::Text("Hey".to_string())]));
 }
 
 #[test]
 fn remove_blank_start() {
-  let mut line = Line(vec![
+  let mut line = unspanned_line(vec![
     Token::Space,
     Token::Space,
     Token::Text("Hey".to_string()),
   ]);
   line.trim_line_start(2);
-  assert_eq!(line, Line(vec![Token::Text("Hey".to_string())]));
+  assert_eq!(line, unspanned_line(vec![Token::Text("Hey".to_string())]));
 }
 
 #[test]
@@ -122,3 +144,276 @@ fn header_basic() {
   let expected_html = "<h2>Header 2</h2>\n<p>this is an example</p>";
   assert_eq!(parse(example_string).as_html(), expected_html);
 }
+
+#[test]
+fn emphasis_basic() {
+  let example_string = "some *emphasis*, __strong__ and ***both***";
+  let expected_html =
+    "<p>some <em>emphasis</em>, <strong>strong</strong> and <em><strong>both</strong></em></p>";
+  assert_eq!(parse(example_string).as_html(), expected_html);
+}
+
+#[test]
+fn link_basic() {
+  let example_string = "a [link *with* emphasis](/url \"title\") and an ![image](/image.png)";
+  let expected_html = "<p>a <a href=\"/url\" title=\"title\">link <em>with</em> emphasis</a> and an <img src=\"/image.png\" alt=\"image\" /></p>";
+  assert_eq!(parse(example_string).as_html(), expected_html);
+}
+
+#[test]
+fn soft_break_options() {
+  let document = parse("a soft\nbreak and a hard  \nbreak");
+  let mut options = RenderOptions::default();
+  assert_eq!(
+    document.as_html_with_options(&options),
+    "<p>a soft\nbreak and a hard<br />\nbreak</p>"
+  );
+  options.soft_break = SoftBreak::Space;
+  assert_eq!(
+    document.as_html_with_options(&options),
+    "<p>a soft break and a hard<br />\nbreak</p>"
+  );
+  options.soft_break = SoftBreak::LineBreak;
+  assert_eq!(
+    document.as_html_with_options(&options),
+    "<p>a soft<br />\nbreak and a hard<br />\nbreak</p>"
+  );
+}
+
+#[test]
+fn public_ast() {
+  let document = parse("# Title\n\nSome [link](/url)");
+  assert_eq!(
+    document.blocks(),
+    [
+      Spanned::new(
+        Block::Header(
+          1,
+          vec![Spanned::new(
+            Inline::Text("Title".to_string()),
+            span((2, 1, 3), (7, 1, 8))
+          )]
+        ),
+        span((0, 1, 1), (7, 1, 8))
+      ),
+      Spanned::new(
+        Block::Paragraph(vec![
+          Spanned::new(
+            Inline::Text("Some ".to_string()),
+            span((9, 3, 1), (14, 3, 6))
+          ),
+          Spanned::new(
+            Inline::Link {
+              dest: "/url".to_string(),
+              title: None,
+              children: vec![Spanned::new(
+                Inline::Text("link".to_string()),
+                span((15, 3, 7), (19, 3, 11))
+              )],
+            },
+            span((14, 3, 6), (26, 3, 18))
+          ),
+        ]),
+        span((9, 3, 1), (26, 3, 18))
+      ),
+    ]
+  );
+
+  // Blocks can be changed before rendering
+  let mut document = document.clone();
+  document.blocks_mut().remove(0);
+  assert_eq!(document.as_html(), "<p>Some <a href=\"/url\">link</a></p>");
+}
+
+#[test]
+fn emphasis_spans() {
+  let document = parse("**a** *b*");
+  let Block::Paragraph(inlines) = &document.blocks()[0].value else {
+    panic!("expected a paragraph");
+  };
+  let spans: Vec<Span> = inlines.iter().map(|inline| inline.span).collect();
+  assert_eq!(
+    spans,
+    [
+      span((0, 1, 1), (5, 1, 6)),
+      span((5, 1, 6), (6, 1, 7)),
+      span((6, 1, 7), (9, 1, 10)),
+    ]
+  );
+}
+
+#[test]
+fn emphasis_nested_too_deeply() {
+  let document = parse(&("*a ".repeat(101) + "b" + &" a*".repeat(101)));
+  let html = document.as_html();
+  assert_eq!(html.matches("<em>").count(), 100);
+  assert!(html.contains("<em>a *a b a* a</em>"));
+}
+
+#[test]
+fn sourcepos_option() {
+  let document = parse("# Title\n\n> quote\n> more\n\n- one\n- two\n\n```\ncode\n```");
+  let options = RenderOptions {
+    sourcepos: true,
+    ..Default::default()
+  };
+  assert_eq!(
+    document.as_html_with_options(&options),
+    "<h1 data-sourcepos=\"1:1-1:7\">Title</h1>\n\
+     <blockquote data-sourcepos=\"3:1-4:6\">\n<p data-sourcepos=\"3:3-4:6\">quote\nmore</p>\n</blockquote>\n\
+     <ul data-sourcepos=\"6:1-7:5\">\n<li data-sourcepos=\"6:1-6:5\">one</li>\n<li data-sourcepos=\"7:1-7:5\">two</li>\n</ul>\n\
+     <pre data-sourcepos=\"9:1-11:3\"><code>code\n</code></pre>"
+  );
+}
+
+#[test]
+fn smart_punctuation() {
+  let smart = ParseOptions::default().smart(true);
+  let cases = [
+    (
+      "\"Hello,\" she said -- 'it's a trap'...",
+      "<p>“Hello,” she said – ‘it’s a trap’…</p>",
+    ),
+    ("a---b a----b a-----b - c", "<p>a—b a––b a—–b - c</p>"),
+    (
+      "\\\"not\\\" \\-- `\"code\" --` . ..",
+      "<p>&quot;not&quot; -- <code>&quot;code&quot; --</code> . ..</p>",
+    ),
+    (
+      "*\"quoted\"* &quot;entity&quot;",
+      "<p><em>“quoted”</em> &quot;entity&quot;</p>",
+    ),
+    (
+      "[Bob](/bob)'s and (Alice)'s 'pet [cat]'",
+      "<p><a href=\"/bob\">Bob</a>’s and (Alice)’s ‘pet [cat]’</p>",
+    ),
+  ];
+  for (markdown, html) in cases {
+    assert_eq!(parse_with_options(markdown, &smart).as_html(), html);
+  }
+
+  // Without the option punctuation is left alone
+  let markdown = "\"a\" -- b...";
+  assert_eq!(parse(markdown).as_html(), "<p>&quot;a&quot; -- b...</p>");
+  assert_eq!(parse(markdown).context.options(), &ParseOptions::default());
+}
+
+#[test]
+fn tables() {
+  let tables = ParseOptions::default().tables(true);
+  let cases = [
+    (
+      "| foo | bar |\n| --- | --- |\n| baz | bim |",
+      "<table>\n<thead>\n<tr>\n<th>foo</th>\n<th>bar</th>\n</tr>\n</thead>\n<tbody>\n<tr>\n<td>baz</td>\n<td>bim</td>\n</tr>\n</tbody>\n</table>",
+    ),
+    (
+      "| abc | defghi |\n:-: | -----------:\nbar | baz",
+      "<table>\n<thead>\n<tr>\n<th align=\"center\">abc</th>\n<th align=\"right\">defghi</th>\n</tr>\n</thead>\n<tbody>\n<tr>\n<td align=\"center\">bar</td>\n<td align=\"right\">baz</td>\n</tr>\n</tbody>\n</table>",
+    ),
+    (
+      "| f\\|oo  |\n| ------ |\n| b `\\|` az |\n| b **\\|** im |",
+      "<table>\n<thead>\n<tr>\n<th>f|oo</th>\n</tr>\n</thead>\n<tbody>\n<tr>\n<td>b <code>|</code> az</td>\n</tr>\n<tr>\n<td>b <strong>|</strong> im</td>\n</tr>\n</tbody>\n</table>",
+    ),
+    (
+      "| abc | def |\n| --- | --- |\n| bar | baz |\n> bar",
+      "<table>\n<thead>\n<tr>\n<th>abc</th>\n<th>def</th>\n</tr>\n</thead>\n<tbody>\n<tr>\n<td>bar</td>\n<td>baz</td>\n</tr>\n</tbody>\n</table>\n<blockquote>\n<p>bar</p>\n</blockquote>",
+    ),
+    (
+      "| abc | def |\n| --- | --- |\n| bar |\n| bar | baz | boo |\n\nbar",
+      "<table>\n<thead>\n<tr>\n<th>abc</th>\n<th>def</th>\n</tr>\n</thead>\n<tbody>\n<tr>\n<td>bar</td>\n<td></td>\n</tr>\n<tr>\n<td>bar</td>\n<td>baz</td>\n</tr>\n</tbody>\n</table>\n<p>bar</p>",
+    ),
+    (
+      "| abc | def |\n| --- |\n| bar |",
+      "<p>| abc | def |\n| --- |\n| bar |</p>",
+    ),
+    (
+      "| abc | def |\n| --- | --- |",
+      "<table>\n<thead>\n<tr>\n<th>abc</th>\n<th>def</th>\n</tr>\n</thead>\n</table>",
+    ),
+    // The header only takes the last line of a paragraph, and a delimiter row without pipes is still a setext underline
+    (
+      "intro\na | b\n-|-\nc | d",
+      "<p>intro</p>\n<table>\n<thead>\n<tr>\n<th>a</th>\n<th>b</th>\n</tr>\n</thead>\n<tbody>\n<tr>\n<td>c</td>\n<td>d</td>\n</tr>\n</tbody>\n</table>",
+    ),
+    ("a\n---", "<h2>a</h2>"),
+  ];
+  for (markdown, html) in cases {
+    assert_eq!(parse_with_options(markdown, &tables).as_html(), html);
+  }
+
+  // Without the option tables are left as paragraphs
+  assert_eq!(
+    parse("a | b\n- | -").as_html(),
+    "<p>a | b</p>\n<ul>\n<li>| -</li>\n</ul>"
+  );
+}
+
+#[test]
+fn task_list_items() {
+  let tasklists = ParseOptions::default().tasklists(true);
+  let cases = [
+    (
+      "- [ ] foo\n- [x] bar",
+      "<ul>\n<li><input type=\"checkbox\" disabled=\"\" /> foo</li>\n<li><input type=\"checkbox\" checked=\"\" disabled=\"\" /> bar</li>\n</ul>",
+    ),
+    (
+      "- [x] foo\n  - [ ] bar\n  - [X] baz\n- [ ] bim",
+      "<ul>\n<li><input type=\"checkbox\" checked=\"\" disabled=\"\" /> foo\n<ul>\n<li><input type=\"checkbox\" disabled=\"\" /> bar</li>\n<li><input type=\"checkbox\" checked=\"\" disabled=\"\" /> baz</li>\n</ul>\n</li>\n<li><input type=\"checkbox\" disabled=\"\" /> bim</li>\n</ul>",
+    ),
+    (
+      "- [ ] a\n\n- [x] b",
+      "<ul>\n<li><input type=\"checkbox\" disabled=\"\" />\n<p>a</p>\n</li>\n<li><input type=\"checkbox\" checked=\"\" disabled=\"\" />\n<p>b</p>\n</li>\n</ul>",
+    ),
+    // The marker needs a blank and then content after it, and only a space or `x` between the brackets
+    (
+      "- [ ]\n- [x]a\n- [y] b\n- [  ] c",
+      "<ul>\n<li>[ ]</li>\n<li>[x]a</li>\n<li>[y] b</li>\n<li>[  ] c</li>\n</ul>",
+    ),
+    // What follows the marker is part of a paragraph, even when it would start another block
+    (
+      "- [ ] # head\n- [x] > quote\n- [ ] ```rust\n  lazy",
+      "<ul>\n<li><input type=\"checkbox\" disabled=\"\" /> # head</li>\n<li><input type=\"checkbox\" checked=\"\" disabled=\"\" /> &gt; quote</li>\n<li><input type=\"checkbox\" disabled=\"\" /> ```rust\nlazy</li>\n</ul>",
+    ),
+    (
+      "- [ ] a\n\n  # b",
+      "<ul>\n<li><input type=\"checkbox\" disabled=\"\" />\n<p>a</p>\n<h1>b</h1>\n</li>\n</ul>",
+    ),
+  ];
+  for (markdown, html) in cases {
+    assert_eq!(parse_with_options(markdown, &tasklists).as_html(), html);
+  }
+
+  let document = parse_with_options("- [x] done\n- to do", &tasklists);
+  let Block::List { inner, .. } = &document.blocks()[0].value else {
+    panic!("expected a list");
+  };
+  let checked: Vec<Option<bool>> = inner
+    .iter()
+    .map(|item| match item.value {
+      Block::LineItem { checked, .. } => checked,
+      _ => panic!("expected an item"),
+    })
+    .collect();
+  assert_eq!(checked, [Some(true), None]);
+
+  // Without the option the marker is left as text
+  assert_eq!(
+    parse("- [ ] foo").as_html(),
+    "<ul>\n<li>[ ] foo</li>\n</ul>"
+  );
+}
+
+#[test]
+fn safe_option() {
+  let document = parse(
+    "<div onclick=\"x()\">\n\nhi <b>there</b> [a](javascript:x()) [b](/ok) ![c](data:image/png;base64,AA) <vbscript:x>",
+  );
+  let options = RenderOptions::default().safe(true);
+  assert_eq!(
+    document.as_html_with_options(&options),
+    "<!-- raw HTML omitted -->\n<p>hi <!-- raw HTML omitted -->there<!-- raw HTML omitted --> \
+     <a href=\"\">a</a> <a href=\"/ok\">b</a> <img src=\"data:image/png;base64,AA\" alt=\"c\" /> \
+     <a href=\"\">vbscript:x</a></p>"
+  );
+  assert!(document.as_html().starts_with("<div onclick=\"x()\">"));
+}
@@ -0,0 +1,576 @@
+mod commonmark;
+mod sanitise;
+mod text;
+mod xml;
+
+use std::{
+  fmt::{self, Write},
+  io,
+};
+
+use crate::{
+  parse::{
+    helpers::{is_dangerous_url, write_escaped_html, write_url},
+    Alignment, Block, Document, Inline, ListType, RenderOptions, SoftBreak, TableRow,
+  },
+  span::{Span, Spanned},
+};
+
+pub use commonmark::CommonMarkRenderer;
+pub use sanitise::Sanitiser;
+pub use text::TextRenderer;
+pub use xml::XmlRenderer;
+
+/// The options used by renderers which don't provide their own
+static DEFAULT_OPTIONS: RenderOptions = RenderOptions {
+  soft_break: SoftBreak::Newline,
+  sourcepos: false,
+  safe: false,
+  sanitiser: None,
+};
+
+/// What raw HTML is replaced with in [`RenderOptions::safe`] mode
+const RAW_HTML_OMITTED: &str = "<!-- raw HTML omitted -->";
+
+/// Writes a [`Document`] out, one node at a time.
+///
+/// Each kind of node has its own hook and the defaults write the same HTML as [`Document::as_html`],
+/// so a renderer only needs to override the hooks for the nodes it wants to write differently.
+/// [`Renderer::block`] and [`Renderer::inline`] pass each node to its hook, and the hooks for nodes with children
+/// hand those back to [`Renderer::blocks`] or [`Renderer::inlines`]
+pub trait Renderer {
+  /// The options used by the default hooks
+  fn options(&self) -> &RenderOptions {
+    &DEFAULT_OPTIONS
+  }
+
+  fn document(&mut self, w: &mut dyn Write, document: &Document) -> fmt::Result {
+    self.blocks(w, document.blocks(), true)
+  }
+
+  /// Writes each of `blocks` on its own line. `loose` decides if paragraphs are wrapped in `<p>` tags
+  fn blocks(&mut self, w: &mut dyn Write, blocks: &[Spanned<Block>], loose: bool) -> fmt::Result {
+    for (i, block) in blocks.iter().enumerate() {
+      if i > 0 {
+        w.write_char('\n')?;
+      }
+      self.block(w, block, loose)?;
+    }
+    Ok(())
+  }
+
+  fn block(&mut self, w: &mut dyn Write, block: &Spanned<Block>, loose: bool) -> fmt::Result {
+    let span = &block.span;
+    match &block.value {
+      Block::Paragraph(inlines) => self.paragraph(w, inlines, loose, span),
+      Block::BlockQuote(blocks) => self.block_quote(w, blocks, span),
+      Block::List {
+        list_type,
+        inner,
+        loose,
+      } => self.list(w, *list_type, inner, *loose, span),
+      Block::LineItem { inner, checked } => self.list_item(w, inner, *checked, loose, span),
+      Block::ThematicBreak => self.thematic_break(w, span),
+      Block::Header(level, inlines) => self.heading(w, *level, inlines, span),
+      Block::IndentedCodeBlock(code) => self.indented_code_block(w, code, span),
+      Block::FencedCodeBlock { info, content } => self.fenced_code_block(w, info, content, span),
+      Block::HtmlBlock(html) => self.html_block(w, html, span),
+      Block::Table {
+        alignments,
+        header,
+        rows,
+      } => self.table(w, alignments, header, rows, span),
+    }
+  }
+
+  /// Writes a `data-sourcepos` attribute for `span` if [`RenderOptions::sourcepos`] is set
+  fn sourcepos(&mut self, w: &mut dyn Write, span: &Span) -> fmt::Result {
+    match self.options().sourcepos {
+      true => write!(w, " data-sourcepos=\"{span}\""),
+      false => Ok(()),
+    }
+  }
+
+  /// Paragraphs in tight lists aren't `loose`, so are written without their `<p>` tags
+  fn paragraph(
+    &mut self,
+    w: &mut dyn Write,
+    inlines: &[Spanned<Inline>],
+    loose: bool,
+    span: &Span,
+  ) -> fmt::Result {
+    if !loose {
+      return self.inlines(w, inlines);
+    }
+    w.write_str("<p")?;
+    self.sourcepos(w, span)?;
+    w.write_char('>')?;
+    self.inlines(w, inlines)?;
+    w.write_str("</p>")
+  }
+
+  fn block_quote(
+    &mut self,
+    w: &mut dyn Write,
+    blocks: &[Spanned<Block>],
+    span: &Span,
+  ) -> fmt::Result {
+    w.write_str("<blockquote")?;
+    self.sourcepos(w, span)?;
+    w.write_str(">\n")?;
+    if !blocks.is_empty() {
+      self.blocks(w, blocks, true)?;
+      w.write_char('\n')?;
+    }
+    w.write_str("</blockquote>")
+  }
+
+  fn list(
+    &mut self,
+    w: &mut dyn Write,
+    list_type: ListType,
+    items: &[Spanned<Block>],
+    loose: bool,
+    span: &Span,
+  ) -> fmt::Result {
+    let list_name = match list_type {
+      ListType::Number(..) | ListType::BracketedNumber(..) => "ol",
+      _ => "ul",
+    };
+    write!(w, "<{list_name}")?;
+    self.sourcepos(w, span)?;
+    if let ListType::Number(start) | ListType::BracketedNumber(start) = list_type {
+      if start != 1 {
+        write!(w, " start=\"{start}\"")?;
+      }
+    }
+    w.write_str(">\n")?;
+    self.blocks(w, items, loose)?;
+    write!(w, "\n</{list_name}>")
+  }
+
+  /// Items of a tight list that begin with a paragraph keep it on the same line as the `<li>` tag.
+  /// Task list items start with a disabled checkbox, as GitHub shows them
+  fn list_item(
+    &mut self,
+    w: &mut dyn Write,
+    blocks: &[Spanned<Block>],
+    checked: Option<bool>,
+    loose: bool,
+    span: &Span,
+  ) -> fmt::Result {
+    w.write_str("<li")?;
+    self.sourcepos(w, span)?;
+    w.write_char('>')?;
+    match checked {
+      Some(true) => w.write_str("<input type=\"checkbox\" checked=\"\" disabled=\"\" />")?,
+      Some(false) => w.write_str("<input type=\"checkbox\" disabled=\"\" />")?,
+      None => {}
+    }
+    if blocks.is_empty() {
+      return w.write_str("</li>");
+    }
+    if !loose && matches!(blocks[0].value, Block::Paragraph(..)) {
+      if checked.is_some() {
+        w.write_char(' ')?;
+      }
+      self.blocks(w, blocks, loose)?;
+      if blocks
+        .last()
+        .is_some_and(|block| matches!(block.value, Block::List { .. }))
+      {
+        w.write_char('\n')?;
+      }
+      return w.write_str("</li>");
+    }
+    w.write_char('\n')?;
+    self.blocks(w, blocks, loose)?;
+    w.write_str("\n</li>")
+  }
+
+  fn thematic_break(&mut self, w: &mut dyn Write, span: &Span) -> fmt::Result {
+    w.write_str("<hr")?;
+    self.sourcepos(w, span)?;
+    w.write_str(" />")
+  }
+
+  fn heading(
+    &mut self,
+    w: &mut dyn Write,
+    level: u8,
+    inlines: &[Spanned<Inline>],
+    span: &Span,
+  ) -> fmt::Result {
+    write!(w, "<h{level}")?;
+    self.sourcepos(w, span)?;
+    w.write_char('>')?;
+    self.inlines(w, inlines)?;
+    write!(w, "</h{level}>")
+  }
+
+  /// `code` doesn't include the final line ending
+  fn indented_code_block(&mut self, w: &mut dyn Write, code: &str, span: &Span) -> fmt::Result {
+    w.write_str("<pre")?;
+    self.sourcepos(w, span)?;
+    w.write_str("><code>")?;
+    write_escaped_html(w, code)?;
+    w.write_str("\n</code></pre>")
+  }
+
+  /// Only the first word of `info` is used, as the language of the code
+  fn fenced_code_block(
+    &mut self,
+    w: &mut dyn Write,
+    info: &str,
+    content: &str,
+    span: &Span,
+  ) -> fmt::Result {
+    w.write_str("<pre")?;
+    self.sourcepos(w, span)?;
+    w.write_str("><code")?;
+    if let Some(language) = info.split_whitespace().next() {
+      w.write_str(" class=\"language-")?;
+      write_escaped_html(w, language)?;
+      w.write_char('"')?;
+    }
+    w.write_char('>')?;
+    write_escaped_html(w, content)?;
+    w.write_str("</code></pre>")
+  }
+
+  /// Replaced with a comment in [`RenderOptions::safe`] mode, or cleaned up by [`RenderOptions::sanitiser`]
+  fn html_block(&mut self, w: &mut dyn Write, html: &str, _span: &Span) -> fmt::Result {
+    write_raw_html(w, html, self.options())
+  }
+
+  /// The header row is written in `<thead>` and the other rows in `<tbody>`, which is left out if there are none
+  fn table(
+    &mut self,
+    w: &mut dyn Write,
+    alignments: &[Alignment],
+    header: &Spanned<TableRow>,
+    rows: &[Spanned<TableRow>],
+    span: &Span,
+  ) -> fmt::Result {
+    w.write_str("<table")?;
+    self.sourcepos(w, span)?;
+    w.write_str(">\n<thead>\n")?;
+    self.table_row(w, alignments, &header.value, true, &header.span)?;
+    w.write_str("</thead>\n")?;
+    if !rows.is_empty() {
+      w.write_str("<tbody>\n")?;
+      for row in rows {
+        self.table_row(w, alignments, &row.value, false, &row.span)?;
+      }
+      w.write_str("</tbody>\n")?;
+    }
+    w.write_str("</table>")
+  }
+
+  /// Cells of the `header` row are written as `<th>` rather than `<td>`, with each given the alignment of its column
+  fn table_row(
+    &mut self,
+    w: &mut dyn Write,
+    alignments: &[Alignment],
+    cells: &TableRow,
+    header: bool,
+    span: &Span,
+  ) -> fmt::Result {
+    let cell_name = match header {
+      true => "th",
+      false => "td",
+    };
+    w.write_str("<tr")?;
+    self.sourcepos(w, span)?;
+    w.write_str(">\n")?;
+    for (cell, alignment) in cells.iter().zip(alignments) {
+      write!(w, "<{cell_name}")?;
+      match alignment {
+        Alignment::None => {}
+        Alignment::Left => w.write_str(" align=\"left\"")?,
+        Alignment::Center => w.write_str(" align=\"center\"")?,
+        Alignment::Right => w.write_str(" align=\"right\"")?,
+      }
+      self.sourcepos(w, &cell.span)?;
+      w.write_char('>')?;
+      self.inlines(w, &cell.value)?;
+      writeln!(w, "</{cell_name}>")?;
+    }
+    w.write_str("</tr>\n")
+  }
+
+  fn inlines(&mut self, w: &mut dyn Write, inlines: &[Spanned<Inline>]) -> fmt::Result {
+    for inline in inlines {
+      self.inline(w, inline)?;
+    }
+    Ok(())
+  }
+
+  fn inline(&mut self, w: &mut dyn Write, inline: &Spanned<Inline>) -> fmt::Result {
+    let span = &inline.span;
+    match &inline.value {
+      Inline::Text(text) => self.text(w, text, span),
+      Inline::Emphasis(children) => self.emphasis(w, children, span),
+      Inline::Strong(children) => self.strong(w, children, span),
+      Inline::Code(code) => self.code(w, code, span),
+      Inline::Link {
+        dest,
+        title,
+        children,
+      } => self.link(w, dest, title.as_deref(), children, span),
+      Inline::Image {
+        dest,
+        title,
+        children,
+      } => self.image(w, dest, title.as_deref(), children, span),
+      Inline::Autolink { url, email } => self.autolink(w, url, *email, span),
+      Inline::RawHtml(html) => self.raw_html(w, html, span),
+      Inline::SoftBreak => self.soft_break(w, span),
+      Inline::HardBreak => self.hard_break(w, span),
+    }
+  }
+
+  fn text(&mut self, w: &mut dyn Write, text: &str, _span: &Span) -> fmt::Result {
+    write_escaped_html(w, text)
+  }
+
+  fn emphasis(
+    &mut self,
+    w: &mut dyn Write,
+    children: &[Spanned<Inline>],
+    _span: &Span,
+  ) -> fmt::Result {
+    w.write_str("<em>")?;
+    self.inlines(w, children)?;
+    w.write_str("</em>")
+  }
+
+  fn strong(
+    &mut self,
+    w: &mut dyn Write,
+    children: &[Spanned<Inline>],
+    _span: &Span,
+  ) -> fmt::Result {
+    w.write_str("<strong>")?;
+    self.inlines(w, children)?;
+    w.write_str("</strong>")
+  }
+
+  fn code(&mut self, w: &mut dyn Write, code: &str, _span: &Span) -> fmt::Result {
+    w.write_str("<code>")?;
+    write_escaped_html(w, code)?;
+    w.write_str("</code>")
+  }
+
+  fn link(
+    &mut self,
+    w: &mut dyn Write,
+    dest: &str,
+    title: Option<&str>,
+    children: &[Spanned<Inline>],
+    _span: &Span,
+  ) -> fmt::Result {
+    w.write_str("<a href=\"")?;
+    write_link_url(w, dest, self.options())?;
+    w.write_char('"')?;
+    write_title(w, title)?;
+    w.write_char('>')?;
+    self.inlines(w, children)?;
+    w.write_str("</a>")
+  }
+
+  /// The children of an image are written as plain text for its alt text
+  fn image(
+    &mut self,
+    w: &mut dyn Write,
+    dest: &str,
+    title: Option<&str>,
+    children: &[Spanned<Inline>],
+    _span: &Span,
+  ) -> fmt::Result {
+    w.write_str("<img src=\"")?;
+    write_link_url(w, dest, self.options())?;
+    w.write_str("\" alt=\"")?;
+    write_alt_text(w, children)?;
+    w.write_char('"')?;
+    write_title(w, title)?;
+    w.write_str(" />")
+  }
+
+  /// `email` links are given a `mailto:` scheme
+  fn autolink(&mut self, w: &mut dyn Write, url: &str, email: bool, _span: &Span) -> fmt::Result {
+    w.write_str("<a href=\"")?;
+    if email {
+      w.write_str("mailto:")?;
+      write_url(w, url)?;
+    } else {
+      write_link_url(w, url, self.options())?;
+    }
+    w.write_str("\">")?;
+    write_escaped_html(w, url)?;
+    w.write_str("</a>")
+  }
+
+  /// Replaced with a comment in [`RenderOptions::safe`] mode, or cleaned up by [`RenderOptions::sanitiser`]
+  fn raw_html(&mut self, w: &mut dyn Write, html: &str, _span: &Span) -> fmt::Result {
+    write_raw_html(w, html, self.options())
+  }
+
+  /// Written depending on [`RenderOptions::soft_break`]
+  fn soft_break(&mut self, w: &mut dyn Write, _span: &Span) -> fmt::Result {
+    w.write_str(match self.options().soft_break {
+      SoftBreak::Newline => "\n",
+      SoftBreak::Space => " ",
+      SoftBreak::LineBreak => "<br />\n",
+    })
+  }
+
+  fn hard_break(&mut self, w: &mut dyn Write, _span: &Span) -> fmt::Result {
+    w.write_str("<br />\n")
+  }
+}
+
+fn write_raw_html(w: &mut dyn Write, html: &str, options: &RenderOptions) -> fmt::Result {
+  match &options.sanitiser {
+    _ if options.safe => w.write_str(RAW_HTML_OMITTED),
+    Some(sanitiser) => sanitiser.write(w, html),
+    None => w.write_str(html),
+  }
+}
+
+/// Writes the destination of a link or image, leaving it empty if it's dangerous and [`RenderOptions::safe`] is set
+fn write_link_url(w: &mut dyn Write, url: &str, options: &RenderOptions) -> fmt::Result {
+  match options.safe && is_dangerous_url(url) {
+    true => Ok(()),
+    false => write_url(w, url),
+  }
+}
+
+fn write_title(w: &mut dyn Write, title: Option<&str>) -> fmt::Result {
+  match title {
+    Some(title) => {
+      w.write_str(" title=\"")?;
+      write_escaped_html(w, title)?;
+      w.write_char('"')
+    }
+    None => Ok(()),
+  }
+}
+
+/// Writes the same text as [`Inline::vec_as_text`] without building it up first
+fn write_alt_text(w: &mut dyn Write, inlines: &[Spanned<Inline>]) -> fmt::Result {
+  for inline in inlines {
+    match &inline.value {
+      Inline::Text(text) | Inline::Code(text) | Inline::RawHtml(text) => {
+        write_escaped_html(w, text)?
+      }
+      Inline::Autolink { url, .. } => write_escaped_html(w, url)?,
+      Inline::SoftBreak | Inline::HardBreak => w.write_char('\n')?,
+      Inline::Emphasis(children)
+      | Inline::Strong(children)
+      | Inline::Link { children, .. }
+      | Inline::Image { children, .. } => write_alt_text(w, children)?,
+    }
+  }
+  Ok(())
+}
+
+/// The default [`Renderer`], which writes HTML using its `options`
+#[derive(Debug, PartialEq, Clone, Default)]
+pub struct HtmlRenderer {
+  pub options: RenderOptions,
+}
+
+impl HtmlRenderer {
+  pub fn new(options: RenderOptions) -> Self {
+    Self { options }
+  }
+}
+
+impl Renderer for HtmlRenderer {
+  fn options(&self) -> &RenderOptions {
+    &self.options
+  }
+}
+
+/// Lets a renderer write to an [`io::Write`], keeping hold of any error as [`fmt::Error`] can't carry one
+pub(crate) struct IoWriter<'a, W: io::Write + ?Sized> {
+  inner: &'a mut W,
+  pub error: Option<io::Error>,
+}
+
+impl<'a, W: io::Write + ?Sized> IoWriter<'a, W> {
+  pub fn new(inner: &'a mut W) -> Self {
+    Self { inner, error: None }
+  }
+
+  /// Converts the result of rendering into an [`io::Result`], recovering the original error if there was one
+  pub fn finish(self, result: fmt::Result) -> io::Result<()> {
+    match (result, self.error) {
+      (Ok(()), _) => Ok(()),
+      (Err(_), Some(error)) => Err(error),
+      (Err(_), None) => Err(io::Error::other("renderer failed to write")),
+    }
+  }
+}
+
+impl<W: io::Write + ?Sized> Write for IoWriter<'_, W> {
+  fn write_str(&mut self, s: &str) -> fmt::Result {
+    self.inner.write_all(s.as_bytes()).map_err(|error| {
+      self.error = Some(error);
+      fmt::Error
+    })
+  }
+}
+
+/// Adds a prefix to every line written through it, as is needed for the contents of block quotes and list items.
+///
+/// The first line is given `first` and the rest `rest`. Blank lines are given the prefix without its trailing spaces
+pub(crate) struct Prefixed<'a> {
+  inner: &'a mut dyn Write,
+  first: String,
+  rest: String,
+  line_start: bool,
+  first_line: bool,
+}
+
+impl<'a> Prefixed<'a> {
+  pub fn new(inner: &'a mut dyn Write, first: String, rest: String) -> Self {
+    Self {
+      inner,
+      first,
+      rest,
+      line_start: true,
+      first_line: true,
+    }
+  }
+
+  fn write_prefix(&mut self, blank: bool) -> fmt::Result {
+    let prefix = match self.first_line {
+      true => &self.first,
+      false => &self.rest,
+    };
+    self.first_line = false;
+    self.line_start = false;
+    match blank {
+      true => self.inner.write_str(prefix.trim_end()),
+      false => self.inner.write_str(prefix),
+    }
+  }
+}
+
+impl Write for Prefixed<'_> {
+  fn write_str(&mut self, s: &str) -> fmt::Result {
+    for line in s.split_inclusive('\n') {
+      if self.line_start {
+        self.write_prefix(line == "\n")?;
+      }
+      self.inner.write_str(line)?;
+      self.line_start = line.ends_with('\n');
+    }
+    Ok(())
+  }
+}
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,576 @@
+use std::fmt::{self, Write};
+
+use crate::{
+  parse::{
+    entities::scan_entity, helpers::is_punctuation, Alignment, Block, Document, Inline, ListType,
+    TableRow,
+  },
+  span::{Span, Spanned},
+};
+
+use super::{Prefixed, Renderer};
+
+/// Writes a document back out as markdown.
+///
+/// The output is normalised rather than a copy of the source. Headers are always ATX headers, code blocks are always fenced,
+/// bullet lists use `-` and ordered lists use `.`, with `*` and `)` being used instead when a list directly follows
+/// another of the same kind so that the two aren't joined. Any text that could be mistaken for markdown is escaped
+#[derive(Debug, Default)]
+pub struct CommonMarkRenderer {
+  /// Is the next piece of text at the start of a line, where more characters need escaping
+  line_start: bool,
+  /// Line endings within headers have to be written as spaces
+  in_header: bool,
+  /// Should the next list use its alternate marker
+  alternate_marker: bool,
+  /// Emphasis at the very start or end of other emphasis uses the other delimiter so that the two runs aren't read as one
+  alternate_delimiter: bool,
+  /// Whether the next inline is written directly against a word character on its left and right, looking through
+  /// any delimiter runs it joins. A `_` run can only open or close away from word characters
+  word_edges: (bool, bool),
+  /// Whether the document was parsed with smart punctuation, in which case quotes, dashes and ellipses are escaped
+  /// so that they aren't made smart when the output is parsed again
+  smart: bool,
+}
+
+impl Renderer for CommonMarkRenderer {
+  /// Ends the document with a line ending, as is expected of a text file
+  fn document(&mut self, w: &mut dyn Write, document: &Document) -> fmt::Result {
+    if document.blocks().is_empty() {
+      return Ok(());
+    }
+    self.smart = document.context.options().smart;
+    self.blocks(w, document.blocks(), true)?;
+    w.write_char('\n')
+  }
+
+  /// Blocks are separated by a blank line unless they are within the items of a tight list
+  fn blocks(&mut self, w: &mut dyn Write, blocks: &[Spanned<Block>], loose: bool) -> fmt::Result {
+    let mut previous: Option<&Block> = None;
+    let mut alternate = false;
+    for block in blocks {
+      if previous.is_some() {
+        w.write_str(if loose { "\n\n" } else { "\n" })?;
+      }
+      alternate = match (previous, &block.value) {
+        (
+          Some(Block::List {
+            list_type: previous,
+            ..
+          }),
+          Block::List { list_type, .. },
+        ) => is_ordered(*previous) == is_ordered(*list_type) && !alternate,
+        _ => false,
+      };
+      self.alternate_marker = alternate;
+      self.block(w, block, loose)?;
+      previous = Some(&block.value);
+    }
+    Ok(())
+  }
+
+  fn paragraph(
+    &mut self,
+    w: &mut dyn Write,
+    inlines: &[Spanned<Inline>],
+    _loose: bool,
+    _span: &Span,
+  ) -> fmt::Result {
+    self.line_start = true;
+    self.inlines(w, inlines)
+  }
+
+  fn block_quote(
+    &mut self,
+    w: &mut dyn Write,
+    blocks: &[Spanned<Block>],
+    _span: &Span,
+  ) -> fmt::Result {
+    if blocks.is_empty() {
+      return w.write_char('>');
+    }
+    let mut quoted = Prefixed::new(w, "> ".to_string(), "> ".to_string());
+    self.blocks(&mut quoted, blocks, true)
+  }
+
+  fn list(
+    &mut self,
+    w: &mut dyn Write,
+    list_type: ListType,
+    items: &[Spanned<Block>],
+    loose: bool,
+    _span: &Span,
+  ) -> fmt::Result {
+    let alternate = std::mem::take(&mut self.alternate_marker);
+    for (i, item) in items.iter().enumerate() {
+      if i > 0 {
+        w.write_str(if loose { "\n\n" } else { "\n" })?;
+      }
+      let marker = match (list_type, alternate) {
+        (ListType::Number(start) | ListType::BracketedNumber(start), false) => {
+          format!("{}.", start + i)
+        }
+        (ListType::Number(start) | ListType::BracketedNumber(start), true) => {
+          format!("{})", start + i)
+        }
+        (_, false) => "-".to_string(),
+        (_, true) => "*".to_string(),
+      };
+      let Block::LineItem { inner, checked } = &item.value else {
+        self.block(w, item, loose)?;
+        continue;
+      };
+      let indent = " ".repeat(marker.len() + 1);
+      let marker = match checked {
+        Some(true) => format!("{marker} [x]"),
+        Some(false) => format!("{marker} [ ]"),
+        None => marker,
+      };
+      if inner.is_empty() {
+        w.write_str(&marker)?;
+        continue;
+      }
+      let mut item_writer = Prefixed::new(w, format!("{marker} "), indent);
+      self.blocks(&mut item_writer, inner, loose)?;
+    }
+    Ok(())
+  }
+
+  fn thematic_break(&mut self, w: &mut dyn Write, _span: &Span) -> fmt::Result {
+    w.write_str("***")
+  }
+
+  fn heading(
+    &mut self,
+    w: &mut dyn Write,
+    level: u8,
+    inlines: &[Spanned<Inline>],
+    _span: &Span,
+  ) -> fmt::Result {
+    for _ in 0..level {
+      w.write_char('#')?;
+    }
+    if inlines.is_empty() {
+      return Ok(());
+    }
+    w.write_char(' ')?;
+    self.line_start = true;
+    self.in_header = true;
+    let result = self.inlines(w, inlines);
+    self.in_header = false;
+    result
+  }
+
+  fn indented_code_block(&mut self, w: &mut dyn Write, code: &str, span: &Span) -> fmt::Result {
+    self.fenced_code_block(w, "", &format!("{code}\n"), span)
+  }
+
+  /// The fence is made longer than any line of the content that could close it
+  fn fenced_code_block(
+    &mut self,
+    w: &mut dyn Write,
+    info: &str,
+    content: &str,
+    _span: &Span,
+  ) -> fmt::Result {
+    // Info strings for backtick fences can't contain backticks
+    let fence_char = if info.contains('`') { '~' } else { '`' };
+    let longest = content
+      .lines()
+      .map(|line| {
+        line
+          .trim_start_matches(' ')
+          .chars()
+          .take_while(|char| *char == fence_char)
+          .count()
+      })
+      .max()
+      .unwrap_or(0);
+    let fence = fence_char.to_string().repeat((longest + 1).max(3));
+    write!(w, "{fence}")?;
+    write_escaped(w, info, false, false, false)?;
+    w.write_char('\n')?;
+    w.write_str(content)?;
+    w.write_str(&fence)
+  }
+
+  fn html_block(&mut self, w: &mut dyn Write, html: &str, _span: &Span) -> fmt::Result {
+    w.write_str(html)
+  }
+
+  /// Tables are written with pipes at both ends of each row, and a delimiter row after the header
+  fn table(
+    &mut self,
+    w: &mut dyn Write,
+    alignments: &[Alignment],
+    header: &Spanned<TableRow>,
+    rows: &[Spanned<TableRow>],
+    _span: &Span,
+  ) -> fmt::Result {
+    self.table_row(w, alignments, &header.value, true, &header.span)?;
+    w.write_str("\n|")?;
+    for alignment in alignments {
+      let delimiter = match alignment {
+        Alignment::None => " --- |",
+        Alignment::Left => " :--- |",
+        Alignment::Center => " :---: |",
+        Alignment::Right => " ---: |",
+      };
+      w.write_str(delimiter)?;
+    }
+    for row in rows {
+      w.write_char('\n')?;
+      self.table_row(w, alignments, &row.value, false, &row.span)?;
+    }
+    Ok(())
+  }
+
+  /// Pipes within a cell are escaped so that they don't split it, including those within code spans
+  fn table_row(
+    &mut self,
+    w: &mut dyn Write,
+    _alignments: &[Alignment],
+    cells: &TableRow,
+    _header: bool,
+    _span: &Span,
+  ) -> fmt::Result {
+    w.write_char('|')?;
+    for cell in cells {
+      let mut content = String::new();
+      self.line_start = false;
+      self.inlines(&mut content, &cell.value)?;
+      write!(w, " {} |", content.replace('|', "\\|"))?;
+    }
+    Ok(())
+  }
+
+  fn inlines(&mut self, w: &mut dyn Write, inlines: &[Spanned<Inline>]) -> fmt::Result {
+    for (i, inline) in inlines.iter().enumerate() {
+      self.word_edges = (
+        i > 0 && ends_word(&inlines[i - 1].value),
+        inlines
+          .get(i + 1)
+          .is_some_and(|next| starts_word(&next.value)),
+      );
+      self.inline(w, inline)?;
+    }
+    Ok(())
+  }
+
+  fn text(&mut self, w: &mut dyn Write, text: &str, _span: &Span) -> fmt::Result {
+    write_escaped(w, text, self.line_start, self.in_header, self.smart)?;
+    if !text.is_empty() {
+      self.line_start = false;
+    }
+    Ok(())
+  }
+
+  fn emphasis(
+    &mut self,
+    w: &mut dyn Write,
+    children: &[Spanned<Inline>],
+    _span: &Span,
+  ) -> fmt::Result {
+    self.delimited(w, children, 1)
+  }
+
+  fn strong(
+    &mut self,
+    w: &mut dyn Write,
+    children: &[Spanned<Inline>],
+    _span: &Span,
+  ) -> fmt::Result {
+    self.delimited(w, children, 2)
+  }
+
+  /// The code is wrapped in a run of backticks longer than any found within it
+  fn code(&mut self, w: &mut dyn Write, code: &str, _span: &Span) -> fmt::Result {
+    self.line_start = false;
+    let mut longest = 0;
+    let mut run = 0;
+    for char in code.chars() {
+      run = if char == '`' { run + 1 } else { 0 };
+      longest = longest.max(run);
+    }
+    let fence = "`".repeat(longest + 1);
+    // A single space is stripped from each side of code that begins and ends with one, so it is padded to keep its own
+    let padded = code.starts_with('`')
+      || code.ends_with('`')
+      || (code.starts_with(' ') && code.ends_with(' ') && code.chars().any(|char| char != ' '));
+    let padding = if padded { " " } else { "" };
+    write!(w, "{fence}{padding}{code}{padding}{fence}")
+  }
+
+  fn link(
+    &mut self,
+    w: &mut dyn Write,
+    dest: &str,
+    title: Option<&str>,
+    children: &[Spanned<Inline>],
+    _span: &Span,
+  ) -> fmt::Result {
+    self.line_start = false;
+    w.write_char('[')?;
+    self.inlines(w, children)?;
+    w.write_char(']')?;
+    write_destination(w, dest, title)
+  }
+
+  fn image(
+    &mut self,
+    w: &mut dyn Write,
+    dest: &str,
+    title: Option<&str>,
+    children: &[Spanned<Inline>],
+    _span: &Span,
+  ) -> fmt::Result {
+    self.line_start = false;
+    w.write_str("![")?;
+    self.inlines(w, children)?;
+    w.write_char(']')?;
+    write_destination(w, dest, title)
+  }
+
+  fn autolink(&mut self, w: &mut dyn Write, url: &str, _email: bool, _span: &Span) -> fmt::Result {
+    self.line_start = false;
+    write!(w, "<{url}>")
+  }
+
+  fn raw_html(&mut self, w: &mut dyn Write, html: &str, _span: &Span) -> fmt::Result {
+    self.line_start = false;
+    w.write_str(html)
+  }
+
+  fn soft_break(&mut self, w: &mut dyn Write, _span: &Span) -> fmt::Result {
+    if self.in_header {
+      return w.write_char(' ');
+    }
+    self.line_start = true;
+    w.write_char('\n')
+  }
+
+  fn hard_break(&mut self, w: &mut dyn Write, span: &Span) -> fmt::Result {
+    if self.in_header {
+      return self.soft_break(w, span);
+    }
+    self.line_start = true;
+    w.write_str("\\\n")
+  }
+}
+
+impl CommonMarkRenderer {
+  /// Writes emphasis or strong emphasis with `*`, or `_` when it is placed at the edge of emphasis written with `*`
+  /// or where its `*` runs would be between punctuation.
+  ///
+  /// `_` can't open or close against a word character, so emphasis there is written with `*` instead, joining the
+  /// run of the emphasis around it
+  fn delimited(
+    &mut self,
+    w: &mut dyn Write,
+    children: &[Spanned<Inline>],
+    count: usize,
+  ) -> fmt::Result {
+    self.line_start = false;
+    let alternate = std::mem::take(&mut self.alternate_delimiter);
+    let (word_before, word_after) = self.word_edges;
+    let delimiter = if alternate { "_" } else { "*" }.repeat(count);
+    w.write_str(&delimiter)?;
+    for (i, child) in children.iter().enumerate() {
+      let first = i == 0;
+      let last = i + 1 == children.len();
+      // The word characters either side of the child, looking through this emphasis' delimiters at its edges
+      let before = match first {
+        true => word_before,
+        false => ends_word(&children[i - 1].value),
+      };
+      let after = match last {
+        true => word_after,
+        false => starts_word(&children[i + 1].value),
+      };
+      // A `*` run between two punctuation characters can both open and close, so could end this emphasis early
+      let flanks_both =
+        !first && !last && flanks_both_sides(&children[i - 1], child, &children[i + 1]);
+      let child_alternate = (first || last || flanks_both) && !alternate && !before && !after;
+      self.alternate_delimiter = child_alternate;
+      // A child written with the other delimiter is separated from anything outside by this emphasis' delimiters
+      let joined = child_alternate == alternate;
+      self.word_edges = (before && (joined || !first), after && (joined || !last));
+      self.inline(w, child)?;
+    }
+    self.alternate_delimiter = false;
+    w.write_str(&delimiter)
+  }
+}
+
+/// Does the markdown written for `inline` start with a word character
+fn starts_word(inline: &Inline) -> bool {
+  first_written(inline).is_some_and(char::is_alphanumeric)
+}
+
+/// Does the markdown written for `inline` end with a word character
+fn ends_word(inline: &Inline) -> bool {
+  last_written(inline).is_some_and(char::is_alphanumeric)
+}
+
+/// The first character of the markdown written for `inline`. Escapes are only ever added before punctuation,
+/// so the character from the text is used for text
+fn first_written(inline: &Inline) -> Option<char> {
+  match inline {
+    Inline::Text(text) => text.chars().next(),
+    Inline::Emphasis(..) | Inline::Strong(..) => Some('*'),
+    Inline::Code(..) => Some('`'),
+    Inline::Link { .. } => Some('['),
+    Inline::Image { .. } => Some('!'),
+    Inline::Autolink { .. } | Inline::RawHtml(..) => Some('<'),
+    Inline::HardBreak => Some('\\'),
+    Inline::SoftBreak => Some('\n'),
+  }
+}
+
+/// The last character of the markdown written for `inline`
+fn last_written(inline: &Inline) -> Option<char> {
+  match inline {
+    Inline::Text(text) => text.chars().next_back(),
+    Inline::Emphasis(..) | Inline::Strong(..) => Some('*'),
+    Inline::Code(..) => Some('`'),
+    Inline::Link { .. } | Inline::Image { .. } => Some(')'),
+    Inline::Autolink { .. } => Some('>'),
+    Inline::RawHtml(html) => html.chars().next_back(),
+    Inline::HardBreak | Inline::SoftBreak => Some('\n'),
+  }
+}
+
+/// Is `inline` emphasis with punctuation written on both sides of its opening or closing run, between `previous` and
+/// `next`. A `*` run there is both left and right flanking
+fn flanks_both_sides(
+  previous: &Spanned<Inline>,
+  inline: &Spanned<Inline>,
+  next: &Spanned<Inline>,
+) -> bool {
+  let (Inline::Emphasis(children) | Inline::Strong(children)) = &inline.value else {
+    return false;
+  };
+  let punctuation = |char: Option<char>| char.is_some_and(is_punctuation);
+  let opening = punctuation(last_written(&previous.value))
+    && punctuation(
+      children
+        .first()
+        .and_then(|child| first_written(&child.value)),
+    );
+  let closing = punctuation(children.last().and_then(|child| last_written(&child.value)))
+    && punctuation(first_written(&next.value));
+  opening || closing
+}
+
+fn is_ordered(list_type: ListType) -> bool {
+  matches!(
+    list_type,
+    ListType::Number(..) | ListType::BracketedNumber(..)
+  )
+}
+
+/// Writes text with backslash escapes before anything that could be read as markdown.
+///
+/// Some characters only have meaning at the start of a line, so are only escaped there if `line_start` is set.
+/// Within headers, `#` is always escaped as a run of them at the end would be taken as a closing sequence.
+/// With `smart` set, straight quotes and the start of each `--` and `...` are escaped so they stay as they are
+fn write_escaped(
+  w: &mut dyn Write,
+  text: &str,
+  line_start: bool,
+  in_header: bool,
+  smart: bool,
+) -> fmt::Result {
+  // An ordered list marker is a run of up to 9 digits followed by `.` or `)`
+  let digits = text.chars().take_while(char::is_ascii_digit).count();
+  let list_marker =
+    line_start && (1..=9).contains(&digits) && text[digits..].starts_with(['.', ')']);
+
+  for (i, char) in text.char_indices() {
+    let escape = match char {
+      '\\' | '*' | '_' | '`' | '[' | ']' | '<' | '>' => true,
+      '&' => scan_entity(&text[i..]).is_some(),
+      '#' => in_header || (line_start && i == 0),
+      '"' | '\'' => smart,
+      '-' => (line_start && i == 0) || (smart && text[i + 1..].starts_with('-')),
+      '+' | '=' | '~' => line_start && i == 0,
+      // A `!` at the end could be joined with a link that follows to make an image
+      '!' => i + 1 == text.len(),
+      '.' => (list_marker && i == digits) || (smart && text[i + 1..].starts_with("..")),
+      ')' => list_marker && i == digits,
+      // Leading blanks would be removed, so they are kept as character references instead
+      ' ' if line_start && i == 0 => {
+        return write!(w, "&#32;{}", Escaped(&text[1..], in_header, smart))
+      }
+      '\t' if line_start && i == 0 => {
+        return write!(w, "&#9;{}", Escaped(&text[1..], in_header, smart))
+      }
+      // Line endings in text would otherwise become breaks
+      '\n' => {
+        w.write_str("&#10;")?;
+        continue;
+      }
+      _ => false,
+    };
+    if escape {
+      w.write_char('\\')?;
+    }
+    w.write_char(char)?;
+  }
+  Ok(())
+}
+
+/// Text escaped as it would be partway through a line
+struct Escaped<'a>(&'a str, bool, bool);
+
+impl fmt::Display for Escaped<'_> {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    write_escaped(f, self.0, false, self.1, self.2)
+  }
+}
+
+/// Writes the `(destination "title")` part of a link or image
+fn write_destination(w: &mut dyn Write, dest: &str, title: Option<&str>) -> fmt::Result {
+  w.write_char('(')?;
+  // Destinations with spaces or brackets, or that are empty, have to be wrapped in pointy brackets
+  let pointy = dest.is_empty()
+    || dest
+      .chars()
+      .any(|char| char == ' ' || char == '(' || char == ')' || char.is_ascii_control());
+  if pointy {
+    w.write_char('<')?;
+  }
+  for (i, char) in dest.char_indices() {
+    let escape = match char {
+      '\\' | '<' | '>' => true,
+      '&' => scan_entity(&dest[i..]).is_some(),
+      _ => false,
+    };
+    if escape {
+      w.write_char('\\')?;
+    }
+    w.write_char(char)?;
+  }
+  if pointy {
+    w.write_char('>')?;
+  }
+  if let Some(title) = title {
+    w.write_str(" \"")?;
+    for (i, char) in title.char_indices() {
+      let escape = match char {
+        '\\' | '"' => true,
+        '&' => scan_entity(&title[i..]).is_some(),
+        _ => false,
+      };
+      if escape {
+        w.write_char('\\')?;
+      }
+      w.write_char(char)?;
+    }
+    w.write_char('"')?;
+  }
+  w.write_char(')')
+}
+
+#[cfg(test)]
+mod tests;
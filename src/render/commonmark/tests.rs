@@ -0,0 +1,180 @@
+use crate::{
+  parse::{parse, parse_with_options, Block, Document, Inline, ListType, ParseOptions},
+  span::{Span, Spanned},
+  visit::{fold_block, fold_inline, Fold},
+};
+
+/// Removes every span, along with the differences the renderer normalises away,
+/// so that documents parsed from different sources can be compared
+struct Normalise;
+
+impl Fold for Normalise {
+  fn fold_block(&mut self, block: Spanned<Block>) -> Option<Spanned<Block>> {
+    let mut block = fold_block(self, block)?;
+    block.span = Span::default();
+    match &mut block.value {
+      Block::IndentedCodeBlock(code) => {
+        block.value = Block::FencedCodeBlock {
+          info: String::new(),
+          content: format!("{code}\n"),
+        }
+      }
+      Block::List { list_type, .. } => {
+        *list_type = match *list_type {
+          ListType::Number(start) | ListType::BracketedNumber(start) => ListType::Number(start),
+          _ => ListType::Dash,
+        }
+      }
+      Block::Table { header, rows, .. } => {
+        for row in std::iter::once(header).chain(rows) {
+          row.span = Span::default();
+          for cell in &mut row.value {
+            cell.span = Span::default();
+          }
+        }
+      }
+      _ => {}
+    }
+    Some(block)
+  }
+
+  fn fold_inline(&mut self, inline: Spanned<Inline>) -> Option<Spanned<Inline>> {
+    let mut inline = fold_inline(self, inline)?;
+    inline.span = Span::default();
+    Some(inline)
+  }
+}
+
+fn without_spans(mut document: Document) -> Vec<Spanned<Block>> {
+  document.fold(&mut Normalise);
+  document.into_blocks()
+}
+
+/// Checks `markdown` is written out as `expected`, and that `expected` parses back into the same document
+fn assert_round_trip(markdown: &str, expected: &str) {
+  let document = parse(markdown);
+  let output = document.as_commonmark();
+  assert_eq!(output, expected);
+  assert_eq!(without_spans(parse(&output)), without_spans(document));
+}
+
+#[test]
+fn normalises_blocks() {
+  assert_round_trip(
+    "Title\n=====\n\n    code\n\n* one\n* two\n\n___\n\n> quote\nlazy",
+    "# Title\n\n```\ncode\n```\n\n- one\n- two\n\n***\n\n> quote\n> lazy\n",
+  );
+}
+
+#[test]
+fn nested_containers() {
+  assert_round_trip(
+    "> * a\n>   * b\n>   * c\n> * d\n>\n> 3. e\n>\n>    f",
+    "> - a\n>   - b\n>   - c\n> - d\n>\n> 3. e\n>\n>    f\n",
+  );
+}
+
+#[test]
+fn neighbouring_lists_keep_apart() {
+  assert_round_trip(
+    "- a\n+ b\n* c\n\n1. d\n2) e",
+    "- a\n\n* b\n\n- c\n\n1. d\n\n2) e\n",
+  );
+}
+
+#[test]
+fn inlines() {
+  assert_round_trip(
+    "*em* __strong__ `` a`b `` [link](</a b> 'title') ![img](i.png)  \nnext\\\nline <http://x.y> <span>",
+    "*em* **strong** ``a`b`` [link](</a b> \"title\") ![img](i.png)\\\nnext\\\nline <http://x.y> <span>\n",
+  );
+}
+
+#[test]
+fn nested_emphasis_keeps_apart() {
+  assert_round_trip("*_a_ b* **a *b***", "*_a_ b* **a _b_**\n");
+}
+
+#[test]
+fn nested_emphasis_within_words() {
+  assert_round_trip(
+    "*foo**bar*** a***b*c** foo******bar*********baz",
+    "*foo**bar*** a***b*c** foo******bar******\\*\\*\\*baz\n",
+  );
+}
+
+#[test]
+fn nested_emphasis_between_punctuation() {
+  assert_round_trip("*a:_\\:b!_<c*", "*a:_:b\\!_\\<c*\n");
+}
+
+#[test]
+fn empty_block_quotes() {
+  assert_round_trip("a\n\n>\n\n> >", "a\n\n>\n\n> >\n");
+}
+
+#[test]
+fn escapes_special_characters() {
+  assert_round_trip(
+    "\\# not a header\n\\- not a list 1\\. nor this\n\n2\\) no *\\*stars\\** &amp;copy; \\[x]",
+    "\\# not a header\n\\- not a list 1. nor this\n\n2\\) no *\\*stars\\** \\&copy; \\[x\\]\n",
+  );
+}
+
+#[test]
+fn code_fences_outlast_their_content() {
+  assert_round_trip(
+    "````md\n```\nnested\n```\n````",
+    "````md\n```\nnested\n```\n````\n",
+  );
+}
+
+#[test]
+fn escapes_header_closers_and_image_bangs() {
+  assert_round_trip("# a \\#\n\nb\\![c](d)", "# a \\#\n\nb\\![c](d)\n");
+}
+
+#[test]
+fn escapes_smart_punctuation() {
+  let options = ParseOptions::default().smart(true);
+  let document = parse_with_options("a \\-- \\\"b\\\" c\\.\\.\\. it\\'s -- \"d\"", &options);
+  let output = document.as_commonmark();
+  assert_eq!(output, "a \\-- \\\"b\\\" c\\... it\\'s – “d”\n");
+  assert_eq!(
+    without_spans(parse_with_options(&output, &options)),
+    without_spans(document)
+  );
+}
+
+#[test]
+fn tables() {
+  let options = ParseOptions::default().tables(true);
+  let document = parse_with_options(
+    "a | `b\\|c` | d\n:-|:-:|-:\ne \\| f | **g**\nh\n\nafter",
+    &options,
+  );
+  let output = document.as_commonmark();
+  assert_eq!(
+    output,
+    "| a | `b\\|c` | d |\n| :--- | :---: | ---: |\n| e \\| f | **g** |  |\n| h |  |  |\n\nafter\n"
+  );
+  assert_eq!(
+    without_spans(parse_with_options(&output, &options)),
+    without_spans(document)
+  );
+}
+
+#[test]
+fn task_list_items() {
+  let options = ParseOptions::default().tasklists(true);
+  let document = parse_with_options("* [ ] a\n* [X] b\n  * [x] c\n* \\[ ] d\n* [ ]", &options);
+  let output = document.as_commonmark();
+  assert_eq!(
+    output,
+    "- [ ] a\n- [x] b\n  - [x] c\n- \\[ \\] d\n- \\[ \\]\n"
+  );
+  assert_eq!(
+    without_spans(parse_with_options(&output, &options)),
+    without_spans(document)
+  );
+}
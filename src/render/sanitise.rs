@@ -0,0 +1,264 @@
+mod tags;
+
+use std::{
+  collections::{BTreeMap, BTreeSet},
+  fmt::{self, Write},
+};
+
+use crate::parse::{
+  entities::decode_entities,
+  helpers::{split_scheme, write_escaped_html},
+};
+
+use tags::{Attribute, Piece, Tags};
+
+/// Tags whose contents are dropped along with them when they aren't allowed, as they would never be shown as text
+const RAW_TEXT_TAGS: [&str; 10] = [
+  "script", "style", "template", "textarea", "title", "xmp", "iframe", "noscript", "noembed",
+  "noframes",
+];
+
+/// Attributes which hold a URL, and so have their scheme checked
+const URL_ATTRIBUTES: [&str; 10] = [
+  "href",
+  "src",
+  "cite",
+  "action",
+  "formaction",
+  "background",
+  "poster",
+  "longdesc",
+  "data",
+  "xlink:href",
+];
+
+/// Cleans up raw HTML using an allow-list, for when markdown can't be trusted but some HTML should still be shown.
+///
+/// Set it as [`RenderOptions::sanitiser`](crate::RenderOptions::sanitiser) to have it applied to HTML blocks and
+/// inline raw HTML when rendering.
+///
+/// - Tags that aren't allowed are removed, keeping their contents. The contents of tags such as `<script>` and `<style>` are
+///   removed as well, so long as the end tag is within the same piece of HTML
+/// - Attributes that aren't allowed for their tag are removed. Event handlers (`on*`) and `style` are always removed
+/// - URL attributes such as `href` and `src` are removed if they use a scheme that isn't allowed. Relative URLs are kept
+/// - Comments, declarations, processing instructions and CDATA sections are removed
+///
+/// Everything that is kept is written back out in a consistent form, with attribute values quoted and escaped.
+/// The default allows common formatting tags along with `http`, `https` and `mailto` links, similar to what GitHub allows:
+///
+/// ```
+/// use smarkdown::{parse, RenderOptions, Sanitiser};
+///
+/// let options = RenderOptions::default().sanitiser(Sanitiser::default().allow_tags(&["u"]));
+/// let html = parse("<u onclick=\"steal()\">hi</u> <a href=\"javascript:steal()\">there</a>")
+///   .as_html_with_options(&options);
+/// assert_eq!(html, "<p><u>hi</u> <a>there</a></p>");
+/// ```
+#[derive(Debug, PartialEq, Clone)]
+pub struct Sanitiser {
+  tags: BTreeSet<String>,
+  /// The attributes allowed on each tag, with those under `*` being allowed on every tag
+  attributes: BTreeMap<String, BTreeSet<String>>,
+  schemes: BTreeSet<String>,
+}
+
+impl Default for Sanitiser {
+  fn default() -> Self {
+    Sanitiser::empty()
+      .allow_tags(&[
+        "a",
+        "abbr",
+        "b",
+        "blockquote",
+        "br",
+        "code",
+        "dd",
+        "del",
+        "details",
+        "div",
+        "dl",
+        "dt",
+        "em",
+        "h1",
+        "h2",
+        "h3",
+        "h4",
+        "h5",
+        "h6",
+        "hr",
+        "i",
+        "img",
+        "ins",
+        "kbd",
+        "li",
+        "ol",
+        "p",
+        "pre",
+        "q",
+        "s",
+        "samp",
+        "span",
+        "strike",
+        "strong",
+        "sub",
+        "summary",
+        "sup",
+        "table",
+        "tbody",
+        "td",
+        "tfoot",
+        "th",
+        "thead",
+        "tr",
+        "ul",
+        "var",
+      ])
+      .allow_attributes("*", &["title", "lang", "dir"])
+      .allow_attributes("a", &["href"])
+      .allow_attributes("img", &["src", "alt", "width", "height"])
+      .allow_attributes("ol", &["start"])
+      .allow_attributes("blockquote", &["cite"])
+      .allow_attributes("q", &["cite"])
+      .allow_attributes("details", &["open"])
+      .allow_attributes("td", &["align", "colspan", "rowspan"])
+      .allow_attributes("th", &["align", "colspan", "rowspan"])
+      .allow_schemes(&["http", "https", "mailto"])
+  }
+}
+
+impl Sanitiser {
+  /// A sanitiser which allows nothing, so that every tag is removed and only text is left
+  pub fn empty() -> Self {
+    Self {
+      tags: BTreeSet::new(),
+      attributes: BTreeMap::new(),
+      schemes: BTreeSet::new(),
+    }
+  }
+
+  /// Allows each of `tags`, ignoring case
+  pub fn allow_tags(mut self, tags: &[&str]) -> Self {
+    self
+      .tags
+      .extend(tags.iter().map(|tag| tag.to_ascii_lowercase()));
+    self
+  }
+
+  /// Allows each of `attributes` on `tag`, or on every allowed tag if `tag` is `*`
+  pub fn allow_attributes(mut self, tag: &str, attributes: &[&str]) -> Self {
+    self
+      .attributes
+      .entry(tag.to_ascii_lowercase())
+      .or_default()
+      .extend(
+        attributes
+          .iter()
+          .map(|attribute| attribute.to_ascii_lowercase()),
+      );
+    self
+  }
+
+  /// Allows URLs using each of `schemes`, which are given without their `:`
+  pub fn allow_schemes(mut self, schemes: &[&str]) -> Self {
+    self
+      .schemes
+      .extend(schemes.iter().map(|scheme| scheme.to_ascii_lowercase()));
+    self
+  }
+
+  /// Sanitises `html`, returning what is left of it
+  pub fn sanitise(&self, html: &str) -> String {
+    let mut output = String::new();
+    // Writing to a String can't fail
+    let _ = self.write(&mut output, html);
+    output
+  }
+
+  /// Sanitises `html`, writing what is left of it to `w`
+  pub fn write(&self, w: &mut dyn Write, html: &str) -> fmt::Result {
+    let mut pieces = Tags::new(html);
+    while let Some(piece) = pieces.next() {
+      match piece {
+        Piece::Text(text) => match text {
+          "<" => w.write_str("&lt;")?,
+          text => w.write_str(text)?,
+        },
+        Piece::StartTag {
+          name,
+          attributes,
+          self_closing,
+        } if self.tags.contains(&name) => {
+          self.write_start_tag(w, &name, &attributes, self_closing)?
+        }
+        Piece::StartTag { name, .. } if RAW_TEXT_TAGS.contains(&name.as_str()) => {
+          // Skip everything up to and including the matching end tag
+          pieces.find(|piece| matches!(piece, Piece::EndTag { name: end } if *end == name));
+        }
+        Piece::EndTag { name } if self.tags.contains(&name) => write!(w, "</{name}>")?,
+        Piece::StartTag { .. } | Piece::EndTag { .. } | Piece::Other(..) => {}
+      }
+    }
+    Ok(())
+  }
+
+  fn write_start_tag(
+    &self,
+    w: &mut dyn Write,
+    name: &str,
+    attributes: &[Attribute],
+    self_closing: bool,
+  ) -> fmt::Result {
+    write!(w, "<{name}")?;
+    let mut seen: Vec<&str> = vec![];
+    for attribute in attributes {
+      // Browsers only use the first of any repeated attribute
+      if seen.contains(&attribute.name.as_str()) {
+        continue;
+      }
+      seen.push(&attribute.name);
+      let value = attribute.value.map(decode_entities);
+      if !self.allows_attribute(name, &attribute.name, value.as_deref()) {
+        continue;
+      }
+
+      w.write_char(' ')?;
+      w.write_str(&attribute.name)?;
+      if let Some(value) = value {
+        w.write_str("=\"")?;
+        write_escaped_html(w, &value)?;
+        w.write_char('"')?;
+      }
+    }
+    match self_closing {
+      true => w.write_str(" />"),
+      false => w.write_char('>'),
+    }
+  }
+
+  fn allows_attribute(&self, tag: &str, attribute: &str, value: Option<&str>) -> bool {
+    if attribute.starts_with("on") || attribute == "style" {
+      return false;
+    }
+    let allowed = [tag, "*"].iter().any(|tag| {
+      self
+        .attributes
+        .get(*tag)
+        .is_some_and(|attributes| attributes.contains(attribute))
+    });
+    match value {
+      Some(value) if allowed && URL_ATTRIBUTES.contains(&attribute) => self.allows_url(value),
+      _ => allowed,
+    }
+  }
+
+  /// Whether `url` is relative or uses an allowed scheme
+  fn allows_url(&self, url: &str) -> bool {
+    match split_scheme(url) {
+      Some((scheme, _)) => self.schemes.contains(&scheme),
+      None => true,
+    }
+  }
+}
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,161 @@
+/// A piece of HTML as read by [`Tags`]
+#[derive(Debug, PartialEq, Clone)]
+pub(super) enum Piece<'a> {
+  /// Text between tags, which may hold entity references. A lone `<` that doesn't start a tag is text on its own
+  Text(&'a str),
+  /// A start tag, with its name lowercased
+  StartTag {
+    name: String,
+    attributes: Vec<Attribute<'a>>,
+    self_closing: bool,
+  },
+  /// An end tag, with its name lowercased. Anything after the name is ignored, as browsers do
+  EndTag { name: String },
+  /// Comments, declarations, processing instructions and CDATA sections
+  Other(&'a str),
+}
+
+#[derive(Debug, PartialEq, Clone)]
+pub(super) struct Attribute<'a> {
+  /// The attribute's name, lowercased
+  pub name: String,
+  /// The value as written, without its quotes and with any entity references still in place
+  pub value: Option<&'a str>,
+}
+
+/// Splits HTML into tags and the text between them.
+///
+/// Tags are read leniently, the same way a browser would read them, rather than only accepting the tags that the
+/// spec counts as raw HTML. That way anything a browser would treat as a tag is seen by the sanitiser.
+/// A tag that is never closed by a `>` isn't a tag at all, so its `<` becomes text
+pub(super) struct Tags<'a> {
+  rest: &'a str,
+}
+
+impl<'a> Tags<'a> {
+  pub fn new(html: &'a str) -> Self {
+    Self { rest: html }
+  }
+
+  /// Reads the piece at the start of `rest`, returning it with its length
+  fn next_piece(&self) -> (Piece<'a>, usize) {
+    let html = self.rest;
+    if !html.starts_with('<') {
+      let end = html.find('<').unwrap_or(html.len());
+      return (Piece::Text(&html[..end]), end);
+    }
+
+    let markup = match html[1..].chars().next() {
+      Some('!') if html.starts_with("<!--") => {
+        // An unclosed comment runs to the end, as it would in a browser
+        let end = html[4..].find("-->").map_or(html.len(), |end| 4 + end + 3);
+        Some((Piece::Other(&html[..end]), end))
+      }
+      Some('!' | '?') => html
+        .find('>')
+        .map(|end| (Piece::Other(&html[..=end]), end + 1)),
+      Some('/') => end_tag(html),
+      Some(char) if char.is_ascii_alphabetic() => start_tag(html),
+      _ => None,
+    };
+    markup.unwrap_or((Piece::Text(&html[..1]), 1))
+  }
+}
+
+impl<'a> Iterator for Tags<'a> {
+  type Item = Piece<'a>;
+
+  fn next(&mut self) -> Option<Self::Item> {
+    if self.rest.is_empty() {
+      return None;
+    }
+    let (piece, length) = self.next_piece();
+    self.rest = &self.rest[length..];
+    Some(piece)
+  }
+}
+
+/// The length of the tag name at the start of `text`
+fn tag_name_length(text: &str) -> usize {
+  text
+    .find(|char: char| char.is_ascii_whitespace() || char == '/' || char == '>')
+    .unwrap_or(text.len())
+}
+
+fn end_tag(html: &str) -> Option<(Piece<'_>, usize)> {
+  let rest = &html[2..];
+  if !rest.starts_with(|char: char| char.is_ascii_alphabetic()) {
+    return None;
+  }
+  let name = rest[..tag_name_length(rest)].to_ascii_lowercase();
+  let end = rest.find('>')?;
+  Some((Piece::EndTag { name }, 2 + end + 1))
+}
+
+fn start_tag(html: &str) -> Option<(Piece<'_>, usize)> {
+  let name_length = tag_name_length(&html[1..]);
+  let name = html[1..=name_length].to_ascii_lowercase();
+  let mut position = 1 + name_length;
+  let mut attributes = vec![];
+  let mut self_closing = false;
+
+  loop {
+    let rest = &html[position..];
+    let char = rest.chars().next()?;
+    if char == '>' {
+      return Some((
+        Piece::StartTag {
+          name,
+          attributes,
+          self_closing,
+        },
+        position + 1,
+      ));
+    }
+    // A `/` anywhere other than right before the `>` is treated as whitespace
+    self_closing = char == '/';
+    if char.is_ascii_whitespace() || char == '/' {
+      position += char.len_utf8();
+      continue;
+    }
+
+    let (attribute, length) = attribute(rest)?;
+    attributes.push(attribute);
+    position += length;
+  }
+}
+
+/// Reads an attribute at the start of `text`, returning it with its length
+fn attribute(text: &str) -> Option<(Attribute<'_>, usize)> {
+  // A name can start with `=`, but can't contain one after that
+  let first = text.chars().next()?.len_utf8();
+  let name_length = text[first..]
+    .find(|char: char| char.is_ascii_whitespace() || "/>=".contains(char))
+    .map_or(text.len(), |length| length + first);
+  let name = text[..name_length].to_ascii_lowercase();
+
+  let after_name = &text[name_length..];
+  let Some(after_equals) = after_name.trim_start().strip_prefix('=') else {
+    return Some((Attribute { name, value: None }, name_length));
+  };
+  let value_start = after_equals.trim_start();
+  let position = text.len() - value_start.len();
+
+  let (value, length) = match value_start.chars().next()? {
+    quote @ ('"' | '\'') => {
+      let end = value_start[1..].find(quote)?;
+      (&value_start[1..=end], end + 2)
+    }
+    _ => {
+      let end = value_start
+        .find(|char: char| char.is_ascii_whitespace() || char == '>')
+        .unwrap_or(value_start.len());
+      (&value_start[..end], end)
+    }
+  };
+  let attribute = Attribute {
+    name,
+    value: Some(value),
+  };
+  Some((attribute, position + length))
+}
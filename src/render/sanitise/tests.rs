@@ -0,0 +1,111 @@
+use crate::parse::{parse, RenderOptions};
+
+use super::{
+  tags::{Attribute, Piece, Tags},
+  Sanitiser,
+};
+
+#[test]
+fn reads_tags_like_a_browser() {
+  let pieces: Vec<Piece> =
+    Tags::new("a <B class=x\nid = 'y' hidden/>< b</i foo><!-- c -->d <e f=\"g").collect();
+  assert_eq!(
+    pieces,
+    [
+      Piece::Text("a "),
+      Piece::StartTag {
+        name: "b".to_string(),
+        attributes: vec![
+          Attribute {
+            name: "class".to_string(),
+            value: Some("x"),
+          },
+          Attribute {
+            name: "id".to_string(),
+            value: Some("y"),
+          },
+          Attribute {
+            name: "hidden".to_string(),
+            value: None,
+          },
+        ],
+        self_closing: true,
+      },
+      Piece::Text("<"),
+      Piece::Text(" b"),
+      Piece::EndTag {
+        name: "i".to_string()
+      },
+      Piece::Other("<!-- c -->"),
+      Piece::Text("d "),
+      Piece::Text("<"),
+      Piece::Text("e f=\"g"),
+    ]
+  );
+}
+
+#[test]
+fn strips_what_isnt_allowed() {
+  let sanitiser = Sanitiser::default();
+  let cases = [
+    (
+      "<p onclick=\"x()\" style=\"color: red\" title='a \"b\"'>hi</p>",
+      "<p title=\"a &quot;b&quot;\">hi</p>",
+    ),
+    ("<script>alert(1)</script>ok<style>*{}</style>", "ok"),
+    ("<SCRIPT src=x></script >ok", "ok"),
+    ("<blink>text</blink><!-- c --><?x?>", "text"),
+    (
+      "<a href=\"https://a.com/?x=1&amp;y=2\">a</a><a href=/b>b</a>",
+      "<a href=\"https://a.com/?x=1&amp;y=2\">a</a><a href=\"/b\">b</a>",
+    ),
+    (
+      "<a href=\"javascript:x()\">a</a><a href=\"&#106;ava\tscript:x()\">b</a><a href=\"a/b:c\">c</a>",
+      "<a>a</a><a>b</a><a href=\"a/b:c\">c</a>",
+    ),
+    (
+      "<img src=\"data:image/png,x\" alt=\"\" width=1><br/>",
+      "<img alt=\"\" width=\"1\"><br />",
+    ),
+    ("<a href=x href=javascript:y>a</a>", "<a href=\"x\">a</a>"),
+    ("<a href=javascript:y href=x>a</a>", "<a>a</a>"),
+    ("1 < 2 <3", "1 &lt; 2 &lt;3"),
+  ];
+  for (html, sanitised) in cases {
+    assert_eq!(sanitiser.sanitise(html), sanitised, "sanitising {html:?}");
+  }
+}
+
+#[test]
+fn can_be_configured() {
+  let sanitiser = Sanitiser::empty()
+    .allow_tags(&["A", "video"])
+    .allow_attributes("*", &["class"])
+    .allow_attributes("video", &["src", "onplay", "style"])
+    .allow_schemes(&["ftp"]);
+  assert_eq!(
+    sanitiser.sanitise(
+      "<a class=c href=\"ftp://x\">a</a><video src=\"ftp://y\" onplay=\"x()\" style=\"\"><p>b</p></video>"
+    ),
+    "<a class=\"c\">a</a><video src=\"ftp://y\">b</video>"
+  );
+  assert_eq!(
+    Sanitiser::empty().sanitise("<b>bold</b> <i>text</i>"),
+    "bold text"
+  );
+}
+
+#[test]
+fn applies_to_raw_html() {
+  let document = parse("<div class=\"x\" onmouseover=\"x()\">\n<script>x()</script>\n</div>\n\na <b onclick=\"y()\">b</b> <u>c</u>");
+  let options = RenderOptions::default().sanitiser(Sanitiser::default());
+  assert_eq!(
+    document.as_html_with_options(&options),
+    "<div>\n\n</div>\n<p>a <b>b</b> c</p>"
+  );
+  // Safe mode takes priority
+  assert_eq!(
+    document.as_html_with_options(&options.safe(true)),
+    "<!-- raw HTML omitted -->\n<p>a <!-- raw HTML omitted -->b<!-- raw HTML omitted --> <!-- raw HTML omitted -->c<!-- raw HTML omitted --></p>"
+  );
+}
@@ -0,0 +1,104 @@
+use std::fmt::{self, Write};
+
+use crate::{
+  parse::{parse, Inline},
+  span::{Span, Spanned},
+};
+
+use super::{HtmlRenderer, Renderer};
+
+/// Gives headings an anchor and writes code blocks without their language
+struct Custom;
+
+impl Renderer for Custom {
+  fn heading(
+    &mut self,
+    w: &mut dyn Write,
+    level: u8,
+    inlines: &[Spanned<Inline>],
+    _span: &Span,
+  ) -> fmt::Result {
+    let id = Inline::vec_as_text(inlines)
+      .to_lowercase()
+      .replace(' ', "-");
+    write!(w, "<h{level} id=\"{id}\">")?;
+    self.inlines(w, inlines)?;
+    write!(w, "</h{level}>")
+  }
+
+  fn fenced_code_block(
+    &mut self,
+    w: &mut dyn Write,
+    _info: &str,
+    content: &str,
+    span: &Span,
+  ) -> fmt::Result {
+    self.indented_code_block(w, content.trim_end_matches('\n'), span)
+  }
+}
+
+#[test]
+fn override_hooks() {
+  let document = parse("## Getting *started*\n\n```rust\nlet x = 1;\n```\n\n> # Quoted");
+  assert_eq!(
+    document.render_to_string(&mut Custom),
+    "<h2 id=\"getting-started\">Getting <em>started</em></h2>\n<pre><code>let x = 1;\n</code></pre>\n<blockquote>\n<h1 id=\"quoted\">Quoted</h1>\n</blockquote>"
+  );
+}
+
+#[test]
+fn default_renderer_matches_as_html() {
+  let document = parse("- a\n- b\n\n  c\n\n[link](/url \"title\") ![img](/i.png)");
+  assert_eq!(
+    document.render_to_string(&mut HtmlRenderer::default()),
+    document.as_html()
+  );
+
+  let mut bytes: Vec<u8> = vec![];
+  document
+    .render_io(&mut HtmlRenderer::default(), &mut bytes)
+    .unwrap();
+  assert_eq!(String::from_utf8(bytes).unwrap(), document.as_html());
+}
+
+#[test]
+fn write_html_streams_the_same_output() {
+  let document = parse(
+    "# A & B\n\n[link](/a b?x=1&y=\"2\" 'ti\"tle') ![*alt* `code`](/ö.png)\n\n<b>raw</b> <me@example.com>",
+  );
+  let html = document.as_html();
+
+  let mut bytes: Vec<u8> = vec![];
+  document.write_html(&mut bytes).unwrap();
+  assert_eq!(String::from_utf8(bytes).unwrap(), html);
+
+  let mut text = String::new();
+  document.write_html_fmt(&mut text).unwrap();
+  assert_eq!(text, html);
+}
+
+/// A sink which fails once it has been given `limit` bytes
+struct Full {
+  limit: usize,
+}
+
+impl std::io::Write for Full {
+  fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+    if buf.len() > self.limit {
+      return Err(std::io::Error::new(std::io::ErrorKind::StorageFull, "full"));
+    }
+    self.limit -= buf.len();
+    Ok(buf.len())
+  }
+
+  fn flush(&mut self) -> std::io::Result<()> {
+    Ok(())
+  }
+}
+
+#[test]
+fn write_html_returns_io_errors() {
+  let document = parse("a fairly long paragraph of text");
+  let error = document.write_html(&mut Full { limit: 8 }).unwrap_err();
+  assert_eq!(error.kind(), std::io::ErrorKind::StorageFull);
+}
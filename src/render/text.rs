@@ -0,0 +1,244 @@
+use std::fmt::{self, Write};
+
+use crate::{
+  parse::{Alignment, Block, Inline, ListType, TableRow},
+  span::{Span, Spanned},
+};
+
+use super::{Prefixed, Renderer};
+
+/// Writes a document as plain text, without any markup.
+///
+/// Blocks are separated by a blank line, list items are given the bullet or number of their list with the rest of
+/// their lines indented to match, and thematic breaks and raw HTML are dropped. Links are written as their text,
+/// followed by their destination in brackets if `show_urls` is set
+#[derive(Debug, PartialEq, Clone, Default)]
+pub struct TextRenderer {
+  pub show_urls: bool,
+}
+
+impl TextRenderer {
+  pub fn new(show_urls: bool) -> Self {
+    Self { show_urls }
+  }
+
+  /// Writes ` (dest)` after a link's text, unless the text already is its destination
+  fn write_url(&self, w: &mut dyn Write, dest: &str, children: &[Spanned<Inline>]) -> fmt::Result {
+    if !self.show_urls || dest.is_empty() || Inline::vec_as_text(children) == dest {
+      return Ok(());
+    }
+    write!(w, " ({dest})")
+  }
+}
+
+impl Renderer for TextRenderer {
+  /// Blocks are separated by a blank line unless they are within the items of a tight list.
+  /// Blocks with nothing to show are skipped over entirely so they don't leave extra blank lines behind
+  fn blocks(&mut self, w: &mut dyn Write, blocks: &[Spanned<Block>], loose: bool) -> fmt::Result {
+    let shown = blocks
+      .iter()
+      .filter(|block| !matches!(block.value, Block::ThematicBreak | Block::HtmlBlock(..)));
+    for (i, block) in shown.enumerate() {
+      if i > 0 {
+        w.write_str(if loose { "\n\n" } else { "\n" })?;
+      }
+      self.block(w, block, loose)?;
+    }
+    Ok(())
+  }
+
+  fn paragraph(
+    &mut self,
+    w: &mut dyn Write,
+    inlines: &[Spanned<Inline>],
+    _loose: bool,
+    _span: &Span,
+  ) -> fmt::Result {
+    self.inlines(w, inlines)
+  }
+
+  fn block_quote(
+    &mut self,
+    w: &mut dyn Write,
+    blocks: &[Spanned<Block>],
+    _span: &Span,
+  ) -> fmt::Result {
+    self.blocks(w, blocks, true)
+  }
+
+  fn list(
+    &mut self,
+    w: &mut dyn Write,
+    list_type: ListType,
+    items: &[Spanned<Block>],
+    loose: bool,
+    _span: &Span,
+  ) -> fmt::Result {
+    for (i, item) in items.iter().enumerate() {
+      if i > 0 {
+        w.write_str(if loose { "\n\n" } else { "\n" })?;
+      }
+      let marker = match list_type {
+        ListType::Number(start) => format!("{}.", start + i),
+        ListType::BracketedNumber(start) => format!("{})", start + i),
+        ListType::Dash => "-".to_string(),
+        ListType::Star => "*".to_string(),
+        ListType::Plus => "+".to_string(),
+      };
+      let Block::LineItem { inner, checked } = &item.value else {
+        self.block(w, item, loose)?;
+        continue;
+      };
+      let indent = " ".repeat(marker.len() + 1);
+      let marker = match checked {
+        Some(true) => format!("{marker} [x]"),
+        Some(false) => format!("{marker} [ ]"),
+        None => marker,
+      };
+      if inner.is_empty() {
+        w.write_str(&marker)?;
+        continue;
+      }
+      let mut item_writer = Prefixed::new(w, format!("{marker} "), indent);
+      self.blocks(&mut item_writer, inner, loose)?;
+    }
+    Ok(())
+  }
+
+  fn thematic_break(&mut self, _w: &mut dyn Write, _span: &Span) -> fmt::Result {
+    Ok(())
+  }
+
+  fn heading(
+    &mut self,
+    w: &mut dyn Write,
+    _level: u8,
+    inlines: &[Spanned<Inline>],
+    _span: &Span,
+  ) -> fmt::Result {
+    self.inlines(w, inlines)
+  }
+
+  fn indented_code_block(&mut self, w: &mut dyn Write, code: &str, _span: &Span) -> fmt::Result {
+    w.write_str(code)
+  }
+
+  fn fenced_code_block(
+    &mut self,
+    w: &mut dyn Write,
+    _info: &str,
+    content: &str,
+    _span: &Span,
+  ) -> fmt::Result {
+    w.write_str(content.strip_suffix('\n').unwrap_or(content))
+  }
+
+  fn html_block(&mut self, _w: &mut dyn Write, _html: &str, _span: &Span) -> fmt::Result {
+    Ok(())
+  }
+
+  /// Each row is written on its own line
+  fn table(
+    &mut self,
+    w: &mut dyn Write,
+    alignments: &[Alignment],
+    header: &Spanned<TableRow>,
+    rows: &[Spanned<TableRow>],
+    _span: &Span,
+  ) -> fmt::Result {
+    self.table_row(w, alignments, &header.value, true, &header.span)?;
+    for row in rows {
+      w.write_char('\n')?;
+      self.table_row(w, alignments, &row.value, false, &row.span)?;
+    }
+    Ok(())
+  }
+
+  /// Cells are separated by tabs so that columns can still be told apart
+  fn table_row(
+    &mut self,
+    w: &mut dyn Write,
+    _alignments: &[Alignment],
+    cells: &TableRow,
+    _header: bool,
+    _span: &Span,
+  ) -> fmt::Result {
+    for (i, cell) in cells.iter().enumerate() {
+      if i > 0 {
+        w.write_char('\t')?;
+      }
+      self.inlines(w, &cell.value)?;
+    }
+    Ok(())
+  }
+
+  fn text(&mut self, w: &mut dyn Write, text: &str, _span: &Span) -> fmt::Result {
+    w.write_str(text)
+  }
+
+  fn emphasis(
+    &mut self,
+    w: &mut dyn Write,
+    children: &[Spanned<Inline>],
+    _span: &Span,
+  ) -> fmt::Result {
+    self.inlines(w, children)
+  }
+
+  fn strong(
+    &mut self,
+    w: &mut dyn Write,
+    children: &[Spanned<Inline>],
+    _span: &Span,
+  ) -> fmt::Result {
+    self.inlines(w, children)
+  }
+
+  fn code(&mut self, w: &mut dyn Write, code: &str, _span: &Span) -> fmt::Result {
+    w.write_str(code)
+  }
+
+  fn link(
+    &mut self,
+    w: &mut dyn Write,
+    dest: &str,
+    _title: Option<&str>,
+    children: &[Spanned<Inline>],
+    _span: &Span,
+  ) -> fmt::Result {
+    self.inlines(w, children)?;
+    self.write_url(w, dest, children)
+  }
+
+  /// Images are written as their alt text
+  fn image(
+    &mut self,
+    w: &mut dyn Write,
+    dest: &str,
+    _title: Option<&str>,
+    children: &[Spanned<Inline>],
+    _span: &Span,
+  ) -> fmt::Result {
+    self.inlines(w, children)?;
+    self.write_url(w, dest, children)
+  }
+
+  fn autolink(&mut self, w: &mut dyn Write, url: &str, _email: bool, _span: &Span) -> fmt::Result {
+    w.write_str(url)
+  }
+
+  fn raw_html(&mut self, _w: &mut dyn Write, _html: &str, _span: &Span) -> fmt::Result {
+    Ok(())
+  }
+
+  fn soft_break(&mut self, w: &mut dyn Write, _span: &Span) -> fmt::Result {
+    w.write_char('\n')
+  }
+
+  fn hard_break(&mut self, w: &mut dyn Write, _span: &Span) -> fmt::Result {
+    w.write_char('\n')
+  }
+}
+
+#[cfg(test)]
+mod tests;
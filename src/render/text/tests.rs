@@ -0,0 +1,51 @@
+use crate::parse::{parse, parse_with_options, ParseOptions};
+
+use super::TextRenderer;
+
+#[test]
+fn removes_markup() {
+  let document = parse(
+    "# The *title*\n\nSome **bold** `code` and a [link](/url).\n\n***\n\n> quoted\n\n<div>\nhidden\n</div>\n\n```rust\nlet x = 1;\n```",
+  );
+  assert_eq!(
+    document.as_text(),
+    "The title\n\nSome bold code and a link.\n\nquoted\n\nlet x = 1;"
+  );
+}
+
+#[test]
+fn lists_keep_their_markers() {
+  let document = parse("- first\n- second\n  - nested\n  - again\n\n3. a\n\n   b");
+  assert_eq!(
+    document.as_text(),
+    "- first\n- second\n  - nested\n  - again\n\n3. a\n\n   b"
+  );
+}
+
+#[test]
+fn shows_urls_when_asked() {
+  let document = parse("[docs](https://example.com) <https://example.com> ![logo](/logo.png)");
+  assert_eq!(document.as_text(), "docs https://example.com logo");
+  assert_eq!(
+    document.render_to_string(&mut TextRenderer::new(true)),
+    "docs (https://example.com) https://example.com logo (/logo.png)"
+  );
+}
+
+#[test]
+fn tables_separate_cells_with_tabs() {
+  let document = parse_with_options(
+    "| a | *b* |\n| --- | --- |\n| c |\n\nafter",
+    &ParseOptions::default().tables(true),
+  );
+  assert_eq!(document.as_text(), "a\tb\nc\t\n\nafter");
+}
+
+#[test]
+fn task_list_items_keep_their_checkbox() {
+  let document = parse_with_options(
+    "- [x] done\n- [ ] to do",
+    &ParseOptions::default().tasklists(true),
+  );
+  assert_eq!(document.as_text(), "- [x] done\n- [ ] to do");
+}
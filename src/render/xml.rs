@@ -0,0 +1,360 @@
+use std::fmt::{self, Write};
+
+use crate::{
+  parse::{
+    helpers::write_escaped_html, Alignment, Block, Document, Inline, ListType, RenderOptions,
+    TableRow,
+  },
+  span::{Span, Spanned},
+};
+
+use super::Renderer;
+
+/// Writes the document tree as XML following the CommonMark DTD, in the same layout as `cmark --to xml`.
+///
+/// Each node is written on its own line, indented by two spaces for each of its ancestors.
+/// Only [`RenderOptions::sourcepos`] is used from its options, which adds a `sourcepos` attribute to every node
+#[derive(Debug, PartialEq, Clone, Default)]
+pub struct XmlRenderer {
+  pub options: RenderOptions,
+  /// How many elements the next one is nested within
+  depth: usize,
+}
+
+impl XmlRenderer {
+  pub fn new(options: RenderOptions) -> Self {
+    Self { options, depth: 0 }
+  }
+
+  /// Writes the opening tag of an element, up to but not including its closing `>`
+  fn open_tag(
+    &mut self,
+    w: &mut dyn Write,
+    name: &str,
+    span: &Span,
+    attributes: &[(&str, &str)],
+  ) -> fmt::Result {
+    write!(w, "{:1$}<{name}", "", self.depth * 2)?;
+    if self.options.sourcepos {
+      write!(w, " sourcepos=\"{span}\"")?;
+    }
+    for (attribute, value) in attributes {
+      write!(w, " {attribute}=\"")?;
+      write_escaped_html(w, value)?;
+      w.write_char('"')?;
+    }
+    Ok(())
+  }
+
+  /// Writes an element which holds other nodes, with `children` writing them
+  fn element(
+    &mut self,
+    w: &mut dyn Write,
+    name: &str,
+    span: &Span,
+    attributes: &[(&str, &str)],
+    children: impl FnOnce(&mut Self, &mut dyn Write) -> fmt::Result,
+  ) -> fmt::Result {
+    self.open_tag(w, name, span, attributes)?;
+    w.write_str(">\n")?;
+    self.depth += 1;
+    let result = children(self, w);
+    self.depth -= 1;
+    result?;
+    writeln!(w, "{:1$}</{name}>", "", self.depth * 2)
+  }
+
+  /// Writes an element which holds `content` as text, or is empty if there is none
+  fn leaf(
+    &mut self,
+    w: &mut dyn Write,
+    name: &str,
+    span: &Span,
+    attributes: &[(&str, &str)],
+    content: Option<&str>,
+  ) -> fmt::Result {
+    self.open_tag(w, name, span, attributes)?;
+    let Some(content) = content else {
+      return w.write_str(" />\n");
+    };
+    w.write_str(" xml:space=\"preserve\">")?;
+    write_escaped_html(w, content)?;
+    writeln!(w, "</{name}>")
+  }
+
+  fn link_like(
+    &mut self,
+    w: &mut dyn Write,
+    name: &str,
+    dest: &str,
+    title: Option<&str>,
+    children: &[Spanned<Inline>],
+    span: &Span,
+  ) -> fmt::Result {
+    let attributes = [("destination", dest), ("title", title.unwrap_or_default())];
+    self.element(w, name, span, &attributes, |renderer, w| {
+      renderer.inlines(w, children)
+    })
+  }
+}
+
+impl Renderer for XmlRenderer {
+  fn options(&self) -> &RenderOptions {
+    &self.options
+  }
+
+  fn document(&mut self, w: &mut dyn Write, document: &Document) -> fmt::Result {
+    w.write_str("<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n")?;
+    w.write_str("<!DOCTYPE document SYSTEM \"CommonMark.dtd\">\n")?;
+    w.write_str("<document xmlns=\"http://commonmark.org/xml/1.0\">\n")?;
+    self.depth = 1;
+    self.blocks(w, document.blocks(), true)?;
+    self.depth = 0;
+    w.write_str("</document>\n")
+  }
+
+  /// Every element ends its own line, so blocks need nothing between them
+  fn blocks(&mut self, w: &mut dyn Write, blocks: &[Spanned<Block>], loose: bool) -> fmt::Result {
+    for block in blocks {
+      self.block(w, block, loose)?;
+    }
+    Ok(())
+  }
+
+  fn paragraph(
+    &mut self,
+    w: &mut dyn Write,
+    inlines: &[Spanned<Inline>],
+    _loose: bool,
+    span: &Span,
+  ) -> fmt::Result {
+    self.element(w, "paragraph", span, &[], |renderer, w| {
+      renderer.inlines(w, inlines)
+    })
+  }
+
+  fn block_quote(
+    &mut self,
+    w: &mut dyn Write,
+    blocks: &[Spanned<Block>],
+    span: &Span,
+  ) -> fmt::Result {
+    self.element(w, "block_quote", span, &[], |renderer, w| {
+      renderer.blocks(w, blocks, true)
+    })
+  }
+
+  fn list(
+    &mut self,
+    w: &mut dyn Write,
+    list_type: ListType,
+    items: &[Spanned<Block>],
+    loose: bool,
+    span: &Span,
+  ) -> fmt::Result {
+    let tight = if loose { "false" } else { "true" };
+    let start;
+    let attributes: &[(&str, &str)] = match list_type {
+      ListType::Number(number) | ListType::BracketedNumber(number) => {
+        start = number.to_string();
+        let delim = match list_type {
+          ListType::Number(..) => "period",
+          _ => "paren",
+        };
+        &[
+          ("type", "ordered"),
+          ("start", &start),
+          ("delim", delim),
+          ("tight", tight),
+        ]
+      }
+      ListType::Dash | ListType::Star | ListType::Plus => &[("type", "bullet"), ("tight", tight)],
+    };
+    self.element(w, "list", span, attributes, |renderer, w| {
+      renderer.blocks(w, items, loose)
+    })
+  }
+
+  /// Task list items have a `completed` attribute, as in `cmark-gfm`
+  fn list_item(
+    &mut self,
+    w: &mut dyn Write,
+    blocks: &[Spanned<Block>],
+    checked: Option<bool>,
+    loose: bool,
+    span: &Span,
+  ) -> fmt::Result {
+    let attributes: &[(&str, &str)] = match checked {
+      Some(true) => &[("completed", "true")],
+      Some(false) => &[("completed", "false")],
+      None => &[],
+    };
+    self.element(w, "item", span, attributes, |renderer, w| {
+      renderer.blocks(w, blocks, loose)
+    })
+  }
+
+  fn thematic_break(&mut self, w: &mut dyn Write, span: &Span) -> fmt::Result {
+    self.leaf(w, "thematic_break", span, &[], None)
+  }
+
+  fn heading(
+    &mut self,
+    w: &mut dyn Write,
+    level: u8,
+    inlines: &[Spanned<Inline>],
+    span: &Span,
+  ) -> fmt::Result {
+    let level = level.to_string();
+    self.element(w, "heading", span, &[("level", &level)], |renderer, w| {
+      renderer.inlines(w, inlines)
+    })
+  }
+
+  fn indented_code_block(&mut self, w: &mut dyn Write, code: &str, span: &Span) -> fmt::Result {
+    self.leaf(w, "code_block", span, &[], Some(&format!("{code}\n")))
+  }
+
+  fn fenced_code_block(
+    &mut self,
+    w: &mut dyn Write,
+    info: &str,
+    content: &str,
+    span: &Span,
+  ) -> fmt::Result {
+    let attributes: &[(&str, &str)] = match info.is_empty() {
+      true => &[],
+      false => &[("info", info)],
+    };
+    self.leaf(w, "code_block", span, attributes, Some(content))
+  }
+
+  fn html_block(&mut self, w: &mut dyn Write, html: &str, span: &Span) -> fmt::Result {
+    self.leaf(w, "html_block", span, &[], Some(html))
+  }
+
+  fn table(
+    &mut self,
+    w: &mut dyn Write,
+    alignments: &[Alignment],
+    header: &Spanned<TableRow>,
+    rows: &[Spanned<TableRow>],
+    span: &Span,
+  ) -> fmt::Result {
+    self.element(w, "table", span, &[], |renderer, w| {
+      renderer.table_row(w, alignments, &header.value, true, &header.span)?;
+      for row in rows {
+        renderer.table_row(w, alignments, &row.value, false, &row.span)?;
+      }
+      Ok(())
+    })
+  }
+
+  /// Follows `cmark-gfm`, where the header row is a `table_header` and each cell has the alignment of its column
+  fn table_row(
+    &mut self,
+    w: &mut dyn Write,
+    alignments: &[Alignment],
+    cells: &TableRow,
+    header: bool,
+    span: &Span,
+  ) -> fmt::Result {
+    let name = match header {
+      true => "table_header",
+      false => "table_row",
+    };
+    self.element(w, name, span, &[], |renderer, w| {
+      for (cell, alignment) in cells.iter().zip(alignments) {
+        let attributes: &[(&str, &str)] = match alignment {
+          Alignment::None => &[],
+          Alignment::Left => &[("align", "left")],
+          Alignment::Center => &[("align", "center")],
+          Alignment::Right => &[("align", "right")],
+        };
+        renderer.element(w, "table_cell", &cell.span, attributes, |renderer, w| {
+          renderer.inlines(w, &cell.value)
+        })?;
+      }
+      Ok(())
+    })
+  }
+
+  fn text(&mut self, w: &mut dyn Write, text: &str, span: &Span) -> fmt::Result {
+    self.leaf(w, "text", span, &[], Some(text))
+  }
+
+  fn emphasis(
+    &mut self,
+    w: &mut dyn Write,
+    children: &[Spanned<Inline>],
+    span: &Span,
+  ) -> fmt::Result {
+    self.element(w, "emph", span, &[], |renderer, w| {
+      renderer.inlines(w, children)
+    })
+  }
+
+  fn strong(
+    &mut self,
+    w: &mut dyn Write,
+    children: &[Spanned<Inline>],
+    span: &Span,
+  ) -> fmt::Result {
+    self.element(w, "strong", span, &[], |renderer, w| {
+      renderer.inlines(w, children)
+    })
+  }
+
+  fn code(&mut self, w: &mut dyn Write, code: &str, span: &Span) -> fmt::Result {
+    self.leaf(w, "code", span, &[], Some(code))
+  }
+
+  fn link(
+    &mut self,
+    w: &mut dyn Write,
+    dest: &str,
+    title: Option<&str>,
+    children: &[Spanned<Inline>],
+    span: &Span,
+  ) -> fmt::Result {
+    self.link_like(w, "link", dest, title, children, span)
+  }
+
+  fn image(
+    &mut self,
+    w: &mut dyn Write,
+    dest: &str,
+    title: Option<&str>,
+    children: &[Spanned<Inline>],
+    span: &Span,
+  ) -> fmt::Result {
+    self.link_like(w, "image", dest, title, children, span)
+  }
+
+  /// Autolinks are written as a link holding its URL as text, as cmark has no node of their own
+  fn autolink(&mut self, w: &mut dyn Write, url: &str, email: bool, span: &Span) -> fmt::Result {
+    let dest = match email {
+      true => format!("mailto:{url}"),
+      false => url.to_string(),
+    };
+    let attributes = [("destination", dest.as_str()), ("title", "")];
+    self.element(w, "link", span, &attributes, |renderer, w| {
+      renderer.text(w, url, span)
+    })
+  }
+
+  fn raw_html(&mut self, w: &mut dyn Write, html: &str, span: &Span) -> fmt::Result {
+    self.leaf(w, "html_inline", span, &[], Some(html))
+  }
+
+  fn soft_break(&mut self, w: &mut dyn Write, span: &Span) -> fmt::Result {
+    self.leaf(w, "softbreak", span, &[], None)
+  }
+
+  fn hard_break(&mut self, w: &mut dyn Write, span: &Span) -> fmt::Result {
+    self.leaf(w, "linebreak", span, &[], None)
+  }
+}
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,156 @@
+use crate::parse::{parse, parse_with_options, ParseOptions, RenderOptions};
+
+#[test]
+fn writes_the_tree() {
+  let document =
+    parse("# Hi *there*\n\n- a & b\n- [link](/url \"title\")\n\n```rust\nfn main() {}\n```");
+  assert_eq!(
+    document.as_xml(),
+    r#"<?xml version="1.0" encoding="UTF-8"?>
+<!DOCTYPE document SYSTEM "CommonMark.dtd">
+<document xmlns="http://commonmark.org/xml/1.0">
+  <heading level="1">
+    <text xml:space="preserve">Hi </text>
+    <emph>
+      <text xml:space="preserve">there</text>
+    </emph>
+  </heading>
+  <list type="bullet" tight="true">
+    <item>
+      <paragraph>
+        <text xml:space="preserve">a &amp; b</text>
+      </paragraph>
+    </item>
+    <item>
+      <paragraph>
+        <link destination="/url" title="title">
+          <text xml:space="preserve">link</text>
+        </link>
+      </paragraph>
+    </item>
+  </list>
+  <code_block info="rust" xml:space="preserve">fn main() {}
+</code_block>
+</document>
+"#
+  );
+}
+
+#[test]
+fn inline_leaves() {
+  let document = parse("2) `code`  \n<b> <me@example.com>\n\n***");
+  assert_eq!(
+    document.as_xml(),
+    r#"<?xml version="1.0" encoding="UTF-8"?>
+<!DOCTYPE document SYSTEM "CommonMark.dtd">
+<document xmlns="http://commonmark.org/xml/1.0">
+  <list type="ordered" start="2" delim="paren" tight="true">
+    <item>
+      <paragraph>
+        <code xml:space="preserve">code</code>
+        <linebreak />
+        <html_inline xml:space="preserve">&lt;b&gt;</html_inline>
+        <text xml:space="preserve"> </text>
+        <link destination="mailto:me@example.com" title="">
+          <text xml:space="preserve">me@example.com</text>
+        </link>
+      </paragraph>
+    </item>
+  </list>
+  <thematic_break />
+</document>
+"#
+  );
+}
+
+#[test]
+fn sourcepos() {
+  let options = RenderOptions {
+    sourcepos: true,
+    ..Default::default()
+  };
+  let document = parse("> quote\n> *here*");
+  assert_eq!(
+    document.as_xml_with_options(&options),
+    r#"<?xml version="1.0" encoding="UTF-8"?>
+<!DOCTYPE document SYSTEM "CommonMark.dtd">
+<document xmlns="http://commonmark.org/xml/1.0">
+  <block_quote sourcepos="1:1-2:8">
+    <paragraph sourcepos="1:3-2:8">
+      <text sourcepos="1:3-1:7" xml:space="preserve">quote</text>
+      <softbreak sourcepos="1:8-1:8" />
+      <emph sourcepos="2:3-2:8">
+        <text sourcepos="2:4-2:7" xml:space="preserve">here</text>
+      </emph>
+    </paragraph>
+  </block_quote>
+</document>
+"#
+  );
+}
+
+#[test]
+fn multi_line_paragraph_sourcepos() {
+  let options = RenderOptions {
+    sourcepos: true,
+    ..Default::default()
+  };
+  let document = parse("one\ntwo *three*\nfour");
+  assert_eq!(
+    document.as_xml_with_options(&options),
+    r#"<?xml version="1.0" encoding="UTF-8"?>
+<!DOCTYPE document SYSTEM "CommonMark.dtd">
+<document xmlns="http://commonmark.org/xml/1.0">
+  <paragraph sourcepos="1:1-3:4">
+    <text sourcepos="1:1-1:3" xml:space="preserve">one</text>
+    <softbreak sourcepos="1:4-1:4" />
+    <text sourcepos="2:1-2:4" xml:space="preserve">two </text>
+    <emph sourcepos="2:5-2:11">
+      <text sourcepos="2:6-2:10" xml:space="preserve">three</text>
+    </emph>
+    <softbreak sourcepos="2:12-2:12" />
+    <text sourcepos="3:1-3:4" xml:space="preserve">four</text>
+  </paragraph>
+</document>
+"#
+  );
+}
+
+#[test]
+fn tables() {
+  let document = parse_with_options(
+    "| a | b |\n| :-: | --- |\n| c |",
+    &ParseOptions::default().tables(true),
+  );
+  assert_eq!(
+    document.as_xml(),
+    r#"<?xml version="1.0" encoding="UTF-8"?>
+<!DOCTYPE document SYSTEM "CommonMark.dtd">
+<document xmlns="http://commonmark.org/xml/1.0">
+  <table>
+    <table_header>
+      <table_cell align="center">
+        <text xml:space="preserve">a</text>
+      </table_cell>
+      <table_cell>
+        <text xml:space="preserve">b</text>
+      </table_cell>
+    </table_header>
+    <table_row>
+      <table_cell align="center">
+        <text xml:space="preserve">c</text>
+      </table_cell>
+      <table_cell>
+      </table_cell>
+    </table_row>
+  </table>
+</document>
+"#
+  );
+}
+
+#[test]
+fn task_list_items() {
+  let document = parse_with_options("- [x] a", &ParseOptions::default().tasklists(true));
+  assert!(document.as_xml().contains("<item completed=\"true\">\n"));
+}
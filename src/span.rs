@@ -0,0 +1,106 @@
+use std::{fmt, ops::Deref};
+
+/// A point in the source markdown
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Default)]
+pub struct Position {
+  /// The byte offset from the start of the source
+  pub offset: usize,
+  /// The line number, starting from 1
+  pub line: usize,
+  /// The byte column within the line, starting from 1
+  pub column: usize,
+}
+
+/// A range of the source markdown. `start` is inclusive and `end` is exclusive
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Default)]
+pub struct Span {
+  pub start: Position,
+  pub end: Position,
+}
+
+impl Span {
+  pub fn new(start: Position, end: Position) -> Self {
+    Self { start, end }
+  }
+
+  /// An empty span sitting at `position`, used for anything that doesn't come directly from the source
+  pub fn empty(position: Position) -> Self {
+    Self::new(position, position)
+  }
+
+  /// Does the span cover none of the source
+  pub fn is_empty(&self) -> bool {
+    self.start.offset == self.end.offset
+  }
+
+  /// Creates a span covering both `self` and `other`
+  pub fn join(&self, other: &Span) -> Span {
+    let start = match other.start.offset < self.start.offset {
+      true => other.start,
+      false => self.start,
+    };
+    let end = match other.end.offset > self.end.offset {
+      true => other.end,
+      false => self.end,
+    };
+    Span::new(start, end)
+  }
+
+  /// Creates a span covering every span in `spans`, if there are any
+  pub fn join_all<'a>(spans: impl IntoIterator<Item = &'a Span>) -> Option<Span> {
+    spans
+      .into_iter()
+      .fold(None, |joined: Option<Span>, span| match joined {
+        Some(joined) => Some(joined.join(span)),
+        None => Some(*span),
+      })
+  }
+}
+
+/// Formats the span in the same `line:column-line:column` form as cmark's `data-sourcepos`, where the end is inclusive.
+///
+/// An empty span has no last character, so is written as covering the single position it sits at
+impl fmt::Display for Span {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    if self.is_empty() {
+      let Position { line, column, .. } = self.start;
+      return write!(f, "{line}:{column}-{line}:{column}");
+    }
+    write!(
+      f,
+      "{}:{}-{}:{}",
+      self.start.line,
+      self.start.column,
+      self.end.line,
+      self.end.column.saturating_sub(1).max(1)
+    )
+  }
+}
+
+/// A value along with the span of source it came from
+#[derive(Debug, PartialEq, Clone)]
+pub struct Spanned<T> {
+  pub value: T,
+  pub span: Span,
+}
+
+impl<T> Spanned<T> {
+  pub fn new(value: T, span: Span) -> Self {
+    Self { value, span }
+  }
+}
+
+/// Values that weren't parsed from a source can be given an empty span
+impl<T> From<T> for Spanned<T> {
+  fn from(value: T) -> Self {
+    Self::new(value, Span::default())
+  }
+}
+
+impl<T> Deref for Spanned<T> {
+  type Target = T;
+
+  fn deref(&self) -> &T {
+    &self.value
+  }
+}
@@ -1,25 +1,27 @@
 mod token;
-pub use token::{Bracket, Token, TokenType, VecNum};
+pub use token::{Bracket, Token, TokenSlice, TokenType, VecNum};
 
-/// Parses a single token onto a given token stack
-fn parse_token(token_list: &mut Vec<Token>, latest_char: char) {
-  let mut last_token = token_list.last_mut();
+use crate::span::{Position, Span, Spanned};
 
+/// Parses a single token onto a given token stack. `span` is where `latest_char` was found in the source
+fn parse_token(token_list: &mut Vec<Spanned<Token>>, latest_char: char, span: Span) {
   // As per [spec](https://spec.commonmark.org/0.31.2/#backslash-escapes) Any ASCII punctuation character may be backslash-escaped
-  // but Backslashes before other characters are treated as literal backslashes
-  let mut escaped = false;
-  if last_token == Some(&mut Token::Escape) {
-    for escapable_char in "!\"#$%&\'()*+,-./:;<=>?@[\\]^_`{|}~".chars() {
-      if latest_char == escapable_char {
-        token_list.pop(); // Remove the escape from the stack as it's been used
-        last_token = token_list.last_mut(); // Grab the new last token
-        escaped = true;
-      }
-    }
+  // but Backslashes before other characters are treated as literal backslashes.
+  // Escaped characters keep their own token so that contexts where escapes don't apply (such as code spans) can undo them
+  if token_list.last().is_some_and(|last| last == &Token::Escape)
+    && latest_char.is_ascii_punctuation()
+  {
+    let escape = token_list.pop().unwrap(); // Remove the escape from the stack as it's been used
+    token_list.push(Spanned::new(
+      Token::Escaped(latest_char),
+      escape.span.join(&span),
+    ));
+    return;
   }
+  let last_token = token_list.last_mut();
 
   // What token is this?
-  let mut token = match latest_char {
+  let token = match latest_char {
     '#' => Token::Hash,
     '!' => Token::Bang,
     '(' => Token::OpenBracket(Bracket::Parenthesis),
@@ -45,7 +47,7 @@ fn parse_token(token_list: &mut Vec<Token>, latest_char: char) {
     // Below this point are tokens with a bit of weirdness to them as they aren't direct one to one mappings of characters
 
     // Numbers
-    '0'..='9' => match last_token {
+    '0'..='9' => match last_token.as_ref().map(|last| &last.value) {
       Some(Token::Text(..)) => Token::Text(latest_char.to_string()), // If the previous token is a string then the number is part of it
       _ => Token::Number(VecNum(vec![latest_char.to_string().parse::<u8>().unwrap()])), // otherwise attempt to parse the number
     },
@@ -60,45 +62,55 @@ fn parse_token(token_list: &mut Vec<Token>, latest_char: char) {
       }
 
       // Convert the character to a text token
-      text.push_str(&char.to_string());
+      text.push(char);
       Token::Text(text)
     }
   };
 
-  // If this char should be escaped and is not already being treated as text
-  if escaped && (matches!(token, Token::Text(..)) == false) {
-    token = Token::Text(latest_char.to_string()); // Escape the char that needs to be escaped
-  }
-
   // If the last token was text or number and this token is the same type then we should merge this token into the previous token
   // rather than creating a whole new token. We should then return early to avoiding adding our new token to the stack
-  match last_token {
-    Some(Token::Text(last_string)) => match token {
-      Token::Text(char) => {
-        last_string.push_str(&char.to_string());
+  if let Some(last_token) = last_token {
+    match (&mut last_token.value, &token) {
+      (Token::Text(last_string), Token::Text(char)) => {
+        last_string.push_str(char);
+        last_token.span = last_token.span.join(&span);
         return;
       }
-      _ => {} // Do Nothing
-    },
-    Some(Token::Number(num_list)) => match token {
-      Token::Number(num) => {
+      (Token::Number(num_list), Token::Number(num)) => {
         num_list.0.push(num.0[0]);
+        last_token.span = last_token.span.join(&span);
         return;
       }
       _ => {} // Do Nothing
-    },
-    _ => {} // Do Nothing
-  };
+    };
+  }
 
   // Add the new token to the token stack
-  token_list.push(token);
+  token_list.push(Spanned::new(token, span));
 }
 
-/// Parses a markdown string into Tokens
-pub fn tokenise(markdown: &str) -> Vec<Token> {
+/// Parses a markdown string into Tokens, each of which knows where it came from in the source
+pub fn tokenise(markdown: &str) -> Vec<Spanned<Token>> {
   let mut tokens = vec![];
+  let mut position = Position {
+    offset: 0,
+    line: 1,
+    column: 1,
+  };
   for char in markdown.chars() {
-    parse_token(&mut tokens, char);
+    let mut end = Position {
+      offset: position.offset + char.len_utf8(),
+      line: position.line,
+      column: position.column + char.len_utf8(),
+    };
+    parse_token(&mut tokens, char, Span::new(position, end));
+
+    // The line ending is the last char of its line
+    if char == '\n' {
+      end.line += 1;
+      end.column = 1;
+    }
+    position = end;
   }
   tokens
 }
@@ -60,3 +60,40 @@ fn lex_test() {
   ];
   assert_eq!(tokenise(example_string), expected);
 }
+
+#[test]
+fn escape_test() {
+  let example_string = "\\*not\\emphasis\\*";
+  let expected = vec![
+    Token::Escaped('*'),
+    Token::Text("not".to_string()),
+    Token::Escape,
+    Token::Text("emphasis".to_string()),
+    Token::Escaped('*'),
+  ];
+  assert_eq!(tokenise(example_string), expected);
+}
+
+#[test]
+fn span_test() {
+  let example_string = "a \\*\n12";
+  let spans: Vec<String> = tokenise(example_string)
+    .iter()
+    .map(|token| {
+      format!(
+        "{}-{} {}",
+        token.span.start.offset, token.span.end.offset, token.span
+      )
+    })
+    .collect();
+  assert_eq!(
+    spans,
+    vec![
+      "0-1 1:1-1:1",
+      "1-2 1:2-1:2",
+      "2-4 1:3-1:4",
+      "4-5 1:5-1:5",
+      "5-7 2:1-2:2"
+    ]
+  );
+}
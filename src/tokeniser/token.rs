@@ -1,3 +1,7 @@
+use std::fmt;
+
+use crate::span::Spanned;
+
 /// The recognised types of bracket
 #[derive(Debug, Eq, PartialEq, Copy, Clone)]
 pub enum Bracket {
@@ -28,6 +32,8 @@ pub enum Token {
   Text(String),
   Number(VecNum), // This is for handling series of numbers without loosing leading 0s. a u8 is likely still to big
   Escape,         // Used to handle escape chars
+  /// A punctuation char that has been backslash escaped. Kept separate from text so the escape can be undone
+  Escaped(char),
 }
 
 /// A lexical Token used by the Parser to understand the markdown
@@ -51,18 +57,21 @@ pub enum TokenType {
   Text,
   Number,
   Escape, // Used to handle escape chars
+  Escaped,
 }
 
 #[derive(Debug, Eq, PartialEq, Clone)]
 pub struct VecNum(pub Vec<u8>);
-impl VecNum {
-  pub fn to_string(&self) -> String {
-    self
-      .0
-      .iter()
-      .fold(String::new(), |str, val| format!("{str}{val}"))
+impl fmt::Display for VecNum {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    for val in self.0.iter() {
+      write!(f, "{val}")?;
+    }
+    Ok(())
   }
+}
 
+impl VecNum {
   pub fn to_usize(&self) -> usize {
     let mut total: usize = 0;
     for num in self.0.iter() {
@@ -72,9 +81,9 @@ impl VecNum {
   }
 }
 
-impl Into<TokenType> for Token {
-  fn into(self) -> TokenType {
-    match self {
+impl From<Token> for TokenType {
+  fn from(val: Token) -> Self {
+    match val {
       Token::Hash => TokenType::Hash,
       Token::Bang => TokenType::Bang,
       Token::OpenBracket(bracket) => TokenType::OpenBracket(bracket),
@@ -93,13 +102,14 @@ impl Into<TokenType> for Token {
       Token::Text(..) => TokenType::Text,
       Token::Escape => TokenType::Escape,
       Token::Number(..) => TokenType::Number,
+      Token::Escaped(..) => TokenType::Escaped,
     }
   }
 }
 
-impl Into<String> for Token {
-  fn into(self) -> String {
-    match self {
+impl From<Token> for String {
+  fn from(val: Token) -> Self {
+    match val {
       Token::Hash => "#".to_string(),
       Token::Bang => "!".to_string(),
       Token::OpenBracket(bracket) => match bracket {
@@ -128,6 +138,37 @@ impl Into<String> for Token {
       Token::Text(text) => text,
       Token::Escape => "\\".to_string(),
       Token::Number(val) => val.to_string(),
+      Token::Escaped(char) => format!("\\{char}"),
     }
   }
 }
+
+impl From<Spanned<Token>> for TokenType {
+  fn from(val: Spanned<Token>) -> Self {
+    val.value.into()
+  }
+}
+
+impl From<Spanned<Token>> for String {
+  fn from(val: Spanned<Token>) -> Self {
+    val.value.into()
+  }
+}
+
+impl PartialEq<Token> for Spanned<Token> {
+  fn eq(&self, other: &Token) -> bool {
+    self.value == *other
+  }
+}
+
+/// Allows tokens to be looked up without their spans
+pub trait TokenSlice {
+  /// Gets the token at `index`, if there is one
+  fn token(&self, index: usize) -> Option<&Token>;
+}
+
+impl TokenSlice for [Spanned<Token>] {
+  fn token(&self, index: usize) -> Option<&Token> {
+    self.get(index).map(|token| &token.value)
+  }
+}
@@ -0,0 +1,304 @@
+use crate::{
+  parse::{Block, Inline, ListType, TableRow},
+  span::Spanned,
+};
+
+/// Walks a document tree without changing it.
+///
+/// Every method has a default which carries on into the node's children, so only the nodes of interest need overriding.
+/// Overriding methods can call the matching `walk_` function to keep walking below the node
+pub trait Visitor {
+  fn visit_block(&mut self, block: &Spanned<Block>) {
+    walk_block(self, block);
+  }
+
+  /// Called for every header, setext headers included
+  fn visit_heading(&mut self, _level: u8, children: &[Spanned<Inline>]) {
+    walk_inlines(self, children);
+  }
+
+  /// Called for every list, `items` are each a [`Block::LineItem`]
+  fn visit_list(&mut self, _list_type: ListType, _loose: bool, items: &[Spanned<Block>]) {
+    walk_blocks(self, items);
+  }
+
+  /// Called for every list item. Task list items are passed on to [`Visitor::visit_task`]
+  fn visit_list_item(&mut self, checked: Option<bool>, children: &[Spanned<Block>]) {
+    match checked {
+      Some(checked) => self.visit_task(checked, children),
+      None => walk_blocks(self, children),
+    }
+  }
+
+  /// Called for every task list item, with whether it is checked
+  fn visit_task(&mut self, _checked: bool, children: &[Spanned<Block>]) {
+    walk_blocks(self, children);
+  }
+
+  /// Called for both indented and fenced code blocks, only fenced code blocks have an `info` string
+  fn visit_code_block(&mut self, _info: Option<&str>, _content: &str) {}
+
+  fn visit_inline(&mut self, inline: &Spanned<Inline>) {
+    walk_inline(self, inline);
+  }
+
+  fn visit_text(&mut self, _text: &str) {}
+
+  fn visit_link(&mut self, _dest: &str, _title: Option<&str>, children: &[Spanned<Inline>]) {
+    walk_inlines(self, children);
+  }
+
+  fn visit_image(&mut self, _dest: &str, _title: Option<&str>, children: &[Spanned<Inline>]) {
+    walk_inlines(self, children);
+  }
+
+  fn visit_autolink(&mut self, _url: &str, _email: bool) {}
+}
+
+/// Visits the children of `block`, along with the more specific method for it if there is one
+pub fn walk_block<V: Visitor + ?Sized>(visitor: &mut V, block: &Spanned<Block>) {
+  match &block.value {
+    Block::Paragraph(inlines) => walk_inlines(visitor, inlines),
+    Block::Header(level, inlines) => visitor.visit_heading(*level, inlines),
+    Block::BlockQuote(blocks) => walk_blocks(visitor, blocks),
+    Block::List {
+      list_type,
+      inner,
+      loose,
+    } => visitor.visit_list(*list_type, *loose, inner),
+    Block::LineItem { inner, checked } => visitor.visit_list_item(*checked, inner),
+    Block::Table { header, rows, .. } => {
+      for row in std::iter::once(header).chain(rows) {
+        for cell in &row.value {
+          walk_inlines(visitor, &cell.value);
+        }
+      }
+    }
+    Block::IndentedCodeBlock(content) => visitor.visit_code_block(None, content),
+    Block::FencedCodeBlock { info, content } => visitor.visit_code_block(Some(info), content),
+    Block::ThematicBreak | Block::HtmlBlock(..) => {}
+  }
+}
+
+pub fn walk_blocks<V: Visitor + ?Sized>(visitor: &mut V, blocks: &[Spanned<Block>]) {
+  for block in blocks {
+    visitor.visit_block(block);
+  }
+}
+
+/// Visits the children of `inline`, along with the more specific method for it if there is one
+pub fn walk_inline<V: Visitor + ?Sized>(visitor: &mut V, inline: &Spanned<Inline>) {
+  match &inline.value {
+    Inline::Text(text) => visitor.visit_text(text),
+    Inline::Emphasis(inlines) | Inline::Strong(inlines) => walk_inlines(visitor, inlines),
+    Inline::Link {
+      dest,
+      title,
+      children,
+    } => visitor.visit_link(dest, title.as_deref(), children),
+    Inline::Image {
+      dest,
+      title,
+      children,
+    } => visitor.visit_image(dest, title.as_deref(), children),
+    Inline::Autolink { url, email } => visitor.visit_autolink(url, *email),
+    Inline::Code(..) | Inline::RawHtml(..) | Inline::SoftBreak | Inline::HardBreak => {}
+  }
+}
+
+pub fn walk_inlines<V: Visitor + ?Sized>(visitor: &mut V, inlines: &[Spanned<Inline>]) {
+  for inline in inlines {
+    visitor.visit_inline(inline);
+  }
+}
+
+/// Rebuilds a document tree, allowing nodes to be replaced or removed.
+///
+/// Each method takes ownership of a node and returns what should take its place, with `None` removing it.
+/// As with [`Visitor`], the defaults carry on into the node's children and the matching `fold_` function
+/// can be called from an overriding method to do the same.
+///
+/// Lists, paragraphs and emphasis can't be written without any content, so the defaults remove them once every
+/// one of their children has been removed. Block quotes, list items, headers and links are kept even when left empty
+pub trait Fold {
+  fn fold_block(&mut self, block: Spanned<Block>) -> Option<Spanned<Block>> {
+    fold_block(self, block)
+  }
+
+  /// Called for every header, setext headers included. The returned block replaces the header
+  fn fold_heading(&mut self, level: u8, children: Vec<Spanned<Inline>>) -> Option<Block> {
+    Some(Block::Header(level, fold_inlines(self, children)))
+  }
+
+  /// Called for every list, `items` are each a [`Block::LineItem`]. The list is removed if no items are left
+  fn fold_list(
+    &mut self,
+    list_type: ListType,
+    loose: bool,
+    items: Vec<Spanned<Block>>,
+  ) -> Option<Block> {
+    let inner = non_empty(fold_blocks(self, items))?;
+    Some(Block::List {
+      list_type,
+      inner,
+      loose,
+    })
+  }
+
+  /// Called for every list item, `checked` is set for task list items
+  fn fold_list_item(
+    &mut self,
+    checked: Option<bool>,
+    children: Vec<Spanned<Block>>,
+  ) -> Option<Block> {
+    Some(Block::LineItem {
+      inner: fold_blocks(self, children),
+      checked,
+    })
+  }
+
+  /// Called for both indented and fenced code blocks, only fenced code blocks have an `info` string
+  fn fold_code_block(&mut self, info: Option<String>, content: String) -> Option<Block> {
+    Some(match info {
+      Some(info) => Block::FencedCodeBlock { info, content },
+      None => Block::IndentedCodeBlock(content),
+    })
+  }
+
+  fn fold_inline(&mut self, inline: Spanned<Inline>) -> Option<Spanned<Inline>> {
+    fold_inline(self, inline)
+  }
+
+  fn fold_text(&mut self, text: String) -> Option<Inline> {
+    Some(Inline::Text(text))
+  }
+
+  fn fold_link(
+    &mut self,
+    dest: String,
+    title: Option<String>,
+    children: Vec<Spanned<Inline>>,
+  ) -> Option<Inline> {
+    Some(Inline::Link {
+      dest,
+      title,
+      children: fold_inlines(self, children),
+    })
+  }
+
+  fn fold_image(
+    &mut self,
+    dest: String,
+    title: Option<String>,
+    children: Vec<Spanned<Inline>>,
+  ) -> Option<Inline> {
+    Some(Inline::Image {
+      dest,
+      title,
+      children: fold_inlines(self, children),
+    })
+  }
+
+  fn fold_autolink(&mut self, url: String, email: bool) -> Option<Inline> {
+    Some(Inline::Autolink { url, email })
+  }
+}
+
+/// Folds the children of `block`, passing it to the more specific method for it if there is one
+pub fn fold_block<F: Fold + ?Sized>(
+  folder: &mut F,
+  block: Spanned<Block>,
+) -> Option<Spanned<Block>> {
+  let Spanned { value, span } = block;
+  let value = match value {
+    Block::Paragraph(inlines) => Block::Paragraph(non_empty(fold_inlines(folder, inlines))?),
+    Block::Header(level, inlines) => folder.fold_heading(level, inlines)?,
+    Block::BlockQuote(blocks) => Block::BlockQuote(fold_blocks(folder, blocks)),
+    Block::List {
+      list_type,
+      inner,
+      loose,
+    } => folder.fold_list(list_type, loose, inner)?,
+    Block::LineItem { inner, checked } => folder.fold_list_item(checked, inner)?,
+    Block::IndentedCodeBlock(content) => folder.fold_code_block(None, content)?,
+    Block::FencedCodeBlock { info, content } => folder.fold_code_block(Some(info), content)?,
+    Block::Table {
+      alignments,
+      header,
+      rows,
+    } => Block::Table {
+      alignments,
+      header: fold_table_row(folder, header),
+      rows: rows
+        .into_iter()
+        .map(|row| fold_table_row(folder, row))
+        .collect(),
+    },
+    block => block,
+  };
+  Some(Spanned::new(value, span))
+}
+
+/// Folds the inlines of each cell in `row`. Cells are never removed, so that the row still fills every column
+fn fold_table_row<F: Fold + ?Sized>(folder: &mut F, row: Spanned<TableRow>) -> Spanned<TableRow> {
+  let Spanned { value, span } = row;
+  let cells = value
+    .into_iter()
+    .map(|cell| Spanned::new(fold_inlines(folder, cell.value), cell.span))
+    .collect();
+  Spanned::new(cells, span)
+}
+
+pub fn fold_blocks<F: Fold + ?Sized>(
+  folder: &mut F,
+  blocks: Vec<Spanned<Block>>,
+) -> Vec<Spanned<Block>> {
+  blocks
+    .into_iter()
+    .filter_map(|block| folder.fold_block(block))
+    .collect()
+}
+
+/// Folds the children of `inline`, passing it to the more specific method for it if there is one
+pub fn fold_inline<F: Fold + ?Sized>(
+  folder: &mut F,
+  inline: Spanned<Inline>,
+) -> Option<Spanned<Inline>> {
+  let Spanned { value, span } = inline;
+  let value = match value {
+    Inline::Text(text) => folder.fold_text(text)?,
+    Inline::Emphasis(inlines) => Inline::Emphasis(non_empty(fold_inlines(folder, inlines))?),
+    Inline::Strong(inlines) => Inline::Strong(non_empty(fold_inlines(folder, inlines))?),
+    Inline::Link {
+      dest,
+      title,
+      children,
+    } => folder.fold_link(dest, title, children)?,
+    Inline::Image {
+      dest,
+      title,
+      children,
+    } => folder.fold_image(dest, title, children)?,
+    Inline::Autolink { url, email } => folder.fold_autolink(url, email)?,
+    inline => inline,
+  };
+  Some(Spanned::new(value, span))
+}
+
+pub fn fold_inlines<F: Fold + ?Sized>(
+  folder: &mut F,
+  inlines: Vec<Spanned<Inline>>,
+) -> Vec<Spanned<Inline>> {
+  inlines
+    .into_iter()
+    .filter_map(|inline| folder.fold_inline(inline))
+    .collect()
+}
+
+/// Gives back `items` unless every one of them has been removed
+fn non_empty<T>(items: Vec<T>) -> Option<Vec<T>> {
+  (!items.is_empty()).then_some(items)
+}
+
+#[cfg(test)]
+mod tests;
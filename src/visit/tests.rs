@@ -0,0 +1,192 @@
+use crate::{
+  parse::{parse, parse_with_options, Block, Inline, ListType, ParseOptions},
+  span::Spanned,
+};
+
+use super::{fold_block, fold_blocks, fold_inlines, walk_blocks, walk_inlines, Fold, Visitor};
+
+/// Collects the destination of every link and image
+#[derive(Default)]
+struct Destinations(Vec<String>);
+
+impl Visitor for Destinations {
+  fn visit_link(&mut self, dest: &str, _title: Option<&str>, children: &[Spanned<Inline>]) {
+    self.0.push(dest.to_string());
+    walk_inlines(self, children);
+  }
+
+  fn visit_image(&mut self, dest: &str, _title: Option<&str>, _children: &[Spanned<Inline>]) {
+    self.0.push(dest.to_string());
+  }
+}
+
+#[test]
+fn visit_nested_links() {
+  let document = parse("> - [a](/a)\n>\n>   *[![b](/b.png)](/c)*\n\n# [d](/d)");
+  let mut destinations = Destinations::default();
+  document.visit(&mut destinations);
+  assert_eq!(destinations.0, ["/a", "/c", "/b.png", "/d"]);
+}
+
+/// Collects a description of each list, code block and autolink
+#[derive(Default)]
+struct Outline(Vec<String>);
+
+impl Visitor for Outline {
+  fn visit_list(&mut self, list_type: ListType, loose: bool, items: &[Spanned<Block>]) {
+    self.0.push(format!("{list_type:?} list, loose: {loose}"));
+    walk_blocks(self, items);
+  }
+
+  fn visit_list_item(&mut self, checked: Option<bool>, children: &[Spanned<Block>]) {
+    self.0.push(format!("item, checked: {checked:?}"));
+    walk_blocks(self, children);
+  }
+
+  fn visit_code_block(&mut self, info: Option<&str>, content: &str) {
+    self.0.push(format!("code {info:?}: {content:?}"));
+  }
+
+  fn visit_autolink(&mut self, url: &str, email: bool) {
+    self.0.push(format!("autolink {url}, email: {email}"));
+  }
+}
+
+#[test]
+fn visit_lists_code_and_autolinks() {
+  let options = ParseOptions::default().tasklists(true);
+  let document = parse_with_options(
+    "    code\n\n```rust\nfn main() {}\n```\n\n- <https://a.com>\n- [x] <me@b.com>",
+    &options,
+  );
+  let mut outline = Outline::default();
+  document.visit(&mut outline);
+  assert_eq!(
+    outline.0,
+    [
+      "code None: \"code\"",
+      "code Some(\"rust\"): \"fn main() {}\\n\"",
+      "Dash list, loose: false",
+      "item, checked: None",
+      "autolink https://a.com, email: false",
+      "item, checked: Some(true)",
+      "autolink me@b.com, email: true",
+    ]
+  );
+}
+
+/// Counts the task list items that are still to be done
+#[derive(Default)]
+struct OpenTasks(usize);
+
+impl Visitor for OpenTasks {
+  fn visit_task(&mut self, checked: bool, children: &[Spanned<Block>]) {
+    self.0 += usize::from(!checked);
+    walk_blocks(self, children);
+  }
+}
+
+#[test]
+fn visit_tasks() {
+  let options = ParseOptions::default().tasklists(true);
+  let mut open = OpenTasks::default();
+  for markdown in [
+    "- [ ] a\n- [x] b\n  - [ ] c",
+    "> 1. [ ] d\n\n- [X] e\n- f [ ]",
+  ] {
+    parse_with_options(markdown, &options).visit(&mut open);
+  }
+  assert_eq!(open.0, 3);
+}
+
+struct Rewrite;
+
+impl Fold for Rewrite {
+  fn fold_heading(&mut self, level: u8, children: Vec<Spanned<Inline>>) -> Option<Block> {
+    Some(Block::Header((level + 1).min(6), children))
+  }
+
+  fn fold_link(
+    &mut self,
+    dest: String,
+    title: Option<String>,
+    children: Vec<Spanned<Inline>>,
+  ) -> Option<Inline> {
+    Some(Inline::Link {
+      dest: dest.replace("http:", "https:"),
+      title,
+      children: fold_inlines(self, children),
+    })
+  }
+
+  fn fold_image(
+    &mut self,
+    dest: String,
+    title: Option<String>,
+    children: Vec<Spanned<Inline>>,
+  ) -> Option<Inline> {
+    Some(Inline::Image {
+      dest: format!("/static/{dest}"),
+      title,
+      children,
+    })
+  }
+
+  fn fold_block(&mut self, block: Spanned<Block>) -> Option<Spanned<Block>> {
+    match block.value {
+      Block::ThematicBreak => None,
+      _ => fold_block(self, block),
+    }
+  }
+}
+
+#[test]
+fn fold_rewrites_and_removes() {
+  let mut document = parse("# Title\n\n***\n\n- [link](http://example.com)\n\n  ![image](cat.png)");
+  document.fold(&mut Rewrite);
+  assert_eq!(
+    document.as_html(),
+    "<h2>Title</h2>\n<ul>\n<li>\n<p><a href=\"https://example.com\">link</a></p>\n<p><img src=\"/static/cat.png\" alt=\"image\" /></p>\n</li>\n</ul>"
+  );
+}
+
+/// Removes every autolink and code block, along with the task list items that are done
+struct Prune;
+
+impl Fold for Prune {
+  fn fold_list_item(
+    &mut self,
+    checked: Option<bool>,
+    children: Vec<Spanned<Block>>,
+  ) -> Option<Block> {
+    match checked {
+      Some(true) => None,
+      _ => Some(Block::LineItem {
+        inner: fold_blocks(self, children),
+        checked,
+      }),
+    }
+  }
+
+  fn fold_code_block(&mut self, _info: Option<String>, _content: String) -> Option<Block> {
+    None
+  }
+
+  fn fold_autolink(&mut self, _url: String, _email: bool) -> Option<Inline> {
+    None
+  }
+}
+
+#[test]
+fn fold_removes_emptied_containers() {
+  let options = ParseOptions::default().tasklists(true);
+  let mut document = parse_with_options(
+    "- [x] done\n- [X] also done\n\n> ```\n> code\n> ```\n\n*<https://a.com>* b\n\n<https://c.com>\n\n- [ ] <https://d.com>",
+    &options,
+  );
+  document.fold(&mut Prune);
+  assert_eq!(
+    document.as_html(),
+    "<blockquote>\n</blockquote>\n<p> b</p>\n<ul>\n<li><input type=\"checkbox\" disabled=\"\" /></li>\n</ul>"
+  );
+}
@@ -0,0 +1,154 @@
+use smarkdown::parse;
+
+/// Here are some valid autolinks:
+#[test]
+fn example_594() {
+  let example_string = "<http://foo.bar.baz>";
+  let expected_html = "<p><a href=\"http://foo.bar.baz\">http://foo.bar.baz</a></p>";
+  assert_eq!(parse(example_string).as_html(), expected_html);
+}
+
+/// Here are some valid autolinks:
+#[test]
+fn example_596() {
+  let example_string = "<irc://foo.bar:2233/baz>";
+  let expected_html = "<p><a href=\"irc://foo.bar:2233/baz\">irc://foo.bar:2233/baz</a></p>";
+  assert_eq!(parse(example_string).as_html(), expected_html);
+}
+
+/// Uppercase is also fine:
+#[test]
+fn example_597() {
+  let example_string = "<MAILTO:FOO@BAR.BAZ>";
+  let expected_html = "<p><a href=\"MAILTO:FOO@BAR.BAZ\">MAILTO:FOO@BAR.BAZ</a></p>";
+  assert_eq!(parse(example_string).as_html(), expected_html);
+}
+
+/// Note that many strings that count as absolute URIs for purposes of this spec are not valid URIs, because their schemes are not registered or because of other problems with their syntax:
+#[test]
+fn example_598() {
+  let example_string = "<a+b+c:d>";
+  let expected_html = "<p><a href=\"a+b+c:d\">a+b+c:d</a></p>";
+  assert_eq!(parse(example_string).as_html(), expected_html);
+}
+
+/// Note that many strings that count as absolute URIs for purposes of this spec are not valid URIs, because their schemes are not registered or because of other problems with their syntax:
+#[test]
+fn example_599() {
+  let example_string = "<made-up-scheme://foo,bar>";
+  let expected_html = "<p><a href=\"made-up-scheme://foo,bar\">made-up-scheme://foo,bar</a></p>";
+  assert_eq!(parse(example_string).as_html(), expected_html);
+}
+
+/// Note that many strings that count as absolute URIs for purposes of this spec are not valid URIs, because their schemes are not registered or because of other problems with their syntax:
+#[test]
+fn example_600() {
+  let example_string = "<https://../>";
+  let expected_html = "<p><a href=\"https://../\">https://../</a></p>";
+  assert_eq!(parse(example_string).as_html(), expected_html);
+}
+
+/// Note that many strings that count as absolute URIs for purposes of this spec are not valid URIs, because their schemes are not registered or because of other problems with their syntax:
+#[test]
+fn example_601() {
+  let example_string = "<localhost:5001/foo>";
+  let expected_html = "<p><a href=\"localhost:5001/foo\">localhost:5001/foo</a></p>";
+  assert_eq!(parse(example_string).as_html(), expected_html);
+}
+
+/// Examples of email autolinks:
+#[test]
+fn example_604() {
+  let example_string = "<foo@bar.example.com>";
+  let expected_html = "<p><a href=\"mailto:foo@bar.example.com\">foo@bar.example.com</a></p>";
+  assert_eq!(parse(example_string).as_html(), expected_html);
+}
+
+/// Examples of email autolinks:
+#[test]
+fn example_605() {
+  let example_string = "<foo+special@Bar.baz-bar0.com>";
+  let expected_html =
+    "<p><a href=\"mailto:foo+special@Bar.baz-bar0.com\">foo+special@Bar.baz-bar0.com</a></p>";
+  assert_eq!(parse(example_string).as_html(), expected_html);
+}
+
+/// These are not autolinks:
+#[test]
+fn example_611() {
+  let example_string = "https://example.com";
+  let expected_html = "<p>https://example.com</p>";
+  assert_eq!(parse(example_string).as_html(), expected_html);
+}
+
+/// These are not autolinks:
+#[test]
+fn example_612() {
+  let example_string = "foo@bar.example.com";
+  let expected_html = "<p>foo@bar.example.com</p>";
+  assert_eq!(parse(example_string).as_html(), expected_html);
+}
+/// Here are some valid autolinks:
+#[test]
+fn example_595() {
+  let example_string = "<https://foo.bar.baz/test?q=hello&id=22&boolean>";
+  let expected_html = "<p><a href=\"https://foo.bar.baz/test?q=hello&amp;id=22&amp;boolean\">https://foo.bar.baz/test?q=hello&amp;id=22&amp;boolean</a></p>";
+  assert_eq!(parse(example_string).as_html(), expected_html);
+}
+
+/// Spaces are not allowed in autolinks:
+#[test]
+fn example_602() {
+  let example_string = "<https://foo.bar/baz bim>";
+  let expected_html = "<p>&lt;https://foo.bar/baz bim&gt;</p>";
+  assert_eq!(parse(example_string).as_html(), expected_html);
+}
+
+/// Backslash-escapes do not work inside autolinks:
+#[test]
+fn example_603() {
+  let example_string = "<https://example.com/\\[\\>";
+  let expected_html =
+    "<p><a href=\"https://example.com/%5C%5B%5C\">https://example.com/\\[\\</a></p>";
+  assert_eq!(parse(example_string).as_html(), expected_html);
+}
+
+/// Backslash-escapes do not work inside email autolinks:
+#[test]
+fn example_606() {
+  let example_string = "<foo\\+@bar.example.com>";
+  let expected_html = "<p>&lt;foo+@bar.example.com&gt;</p>";
+  assert_eq!(parse(example_string).as_html(), expected_html);
+}
+
+/// These are not autolinks:
+#[test]
+fn example_607() {
+  let example_string = "<>";
+  let expected_html = "<p>&lt;&gt;</p>";
+  assert_eq!(parse(example_string).as_html(), expected_html);
+}
+
+/// These are not autolinks:
+#[test]
+fn example_608() {
+  let example_string = "< https://foo.bar >";
+  let expected_html = "<p>&lt; https://foo.bar &gt;</p>";
+  assert_eq!(parse(example_string).as_html(), expected_html);
+}
+
+/// These are not autolinks:
+#[test]
+fn example_609() {
+  let example_string = "<m:abc>";
+  let expected_html = "<p>&lt;m:abc&gt;</p>";
+  assert_eq!(parse(example_string).as_html(), expected_html);
+}
+
+/// These are not autolinks:
+#[test]
+fn example_610() {
+  let example_string = "<foo.bar.baz>";
+  let expected_html = "<p>&lt;foo.bar.baz&gt;</p>";
+  assert_eq!(parse(example_string).as_html(), expected_html);
+}
@@ -0,0 +1,160 @@
+use smarkdown::parse;
+
+/// This is a simple code span:
+#[test]
+fn example_328() {
+  let example_string = "`foo`";
+  let expected_html = "<p><code>foo</code></p>";
+  assert_eq!(parse(example_string).as_html(), expected_html);
+}
+
+/// Here two backticks are used, because the code contains a backtick. This example also illustrates stripping of a single leading and trailing space:
+#[test]
+fn example_329() {
+  let example_string = "`` foo ` bar ``";
+  let expected_html = "<p><code>foo ` bar</code></p>";
+  assert_eq!(parse(example_string).as_html(), expected_html);
+}
+
+/// This example shows the motivation for stripping leading and trailing spaces:
+#[test]
+fn example_330() {
+  let example_string = "` `` `";
+  let expected_html = "<p><code>``</code></p>";
+  assert_eq!(parse(example_string).as_html(), expected_html);
+}
+
+/// Note that only *one* space is stripped:
+#[test]
+fn example_331() {
+  let example_string = "`  ``  `";
+  let expected_html = "<p><code> `` </code></p>";
+  assert_eq!(parse(example_string).as_html(), expected_html);
+}
+
+/// The stripping only happens if the space is on both sides of the string:
+#[test]
+fn example_332() {
+  let example_string = "` a`";
+  let expected_html = "<p><code> a</code></p>";
+  assert_eq!(parse(example_string).as_html(), expected_html);
+}
+
+/// Only spaces, and not unicode whitespace in general, are stripped in this way:
+#[test]
+fn example_333() {
+  let example_string = "`\u{a0}b\u{a0}`";
+  let expected_html = "<p><code>\u{a0}b\u{a0}</code></p>";
+  assert_eq!(parse(example_string).as_html(), expected_html);
+}
+
+/// No stripping occurs if the code span contains only spaces:
+#[test]
+fn example_334() {
+  let example_string = "`\u{a0}`\n`  `";
+  let expected_html = "<p><code>\u{a0}</code>\n<code>  </code></p>";
+  assert_eq!(parse(example_string).as_html(), expected_html);
+}
+
+/// Line endings are treated like spaces:
+#[test]
+fn example_335() {
+  let example_string = "``\nfoo\nbar  \nbaz\n``";
+  let expected_html = "<p><code>foo bar   baz</code></p>";
+  assert_eq!(parse(example_string).as_html(), expected_html);
+}
+
+/// Line endings are treated like spaces:
+#[test]
+fn example_336() {
+  let example_string = "``\nfoo \n``";
+  let expected_html = "<p><code>foo </code></p>";
+  assert_eq!(parse(example_string).as_html(), expected_html);
+}
+
+/// Interior spaces are not collapsed:
+#[test]
+fn example_337() {
+  let example_string = "`foo   bar \nbaz`";
+  let expected_html = "<p><code>foo   bar  baz</code></p>";
+  assert_eq!(parse(example_string).as_html(), expected_html);
+}
+
+/// Note that backslash escapes do not work in code spans. All backslashes are treated literally:
+#[test]
+fn example_338() {
+  let example_string = "`foo\\`bar`";
+  let expected_html = "<p><code>foo\\</code>bar`</p>";
+  assert_eq!(parse(example_string).as_html(), expected_html);
+}
+
+/// Backslash escapes are never needed, because one can always choose a string of *n* backtick characters as delimiters, where the string does not contain any string of exactly *n* backtick characters.
+#[test]
+fn example_339() {
+  let example_string = "``foo`bar``";
+  let expected_html = "<p><code>foo`bar</code></p>";
+  assert_eq!(parse(example_string).as_html(), expected_html);
+}
+
+/// Backslash escapes are never needed, because one can always choose a string of *n* backtick characters as delimiters, where the string does not contain any string of exactly *n* backtick characters.
+#[test]
+fn example_340() {
+  let example_string = "` foo `` bar `";
+  let expected_html = "<p><code>foo `` bar</code></p>";
+  assert_eq!(parse(example_string).as_html(), expected_html);
+}
+
+/// Code span backticks have higher precedence than any other inline constructs except HTML tags and autolinks. Thus, for example, this is not parsed as emphasized text, since the second `*` is part of a code span:
+#[test]
+fn example_341() {
+  let example_string = "*foo`*`";
+  let expected_html = "<p>*foo<code>*</code></p>";
+  assert_eq!(parse(example_string).as_html(), expected_html);
+}
+
+/// When a backtick string is not closed by a matching backtick string, we just have literal backticks:
+#[test]
+fn example_347() {
+  let example_string = "```foo``";
+  let expected_html = "<p>```foo``</p>";
+  assert_eq!(parse(example_string).as_html(), expected_html);
+}
+
+/// When a backtick string is not closed by a matching backtick string, we just have literal backticks:
+#[test]
+fn example_348() {
+  let example_string = "`foo";
+  let expected_html = "<p>`foo</p>";
+  assert_eq!(parse(example_string).as_html(), expected_html);
+}
+
+/// The following case also illustrates the need for opening and closing backtick strings to be equal in length:
+#[test]
+fn example_349() {
+  let example_string = "`foo``bar``";
+  let expected_html = "<p>`foo<code>bar</code></p>";
+  assert_eq!(parse(example_string).as_html(), expected_html);
+}
+/// Code spans, HTML tags, and autolinks have the same precedence. Thus, this is code:
+#[test]
+fn example_343() {
+  let example_string = "`<a href=\"`\">`";
+  let expected_html = "<p><code>&lt;a href=&quot;</code>&quot;&gt;`</p>";
+  assert_eq!(parse(example_string).as_html(), expected_html);
+}
+
+/// And this is code:
+#[test]
+fn example_345() {
+  let example_string = "`<https://foo.bar.`baz>`";
+  let expected_html = "<p><code>&lt;https://foo.bar.</code>baz&gt;`</p>";
+  assert_eq!(parse(example_string).as_html(), expected_html);
+}
+
+/// But this is an autolink:
+#[test]
+fn example_346() {
+  let example_string = "<https://foo.bar.`baz>`";
+  let expected_html = "<p><a href=\"https://foo.bar.%60baz\">https://foo.bar.`baz</a>`</p>";
+  assert_eq!(parse(example_string).as_html(), expected_html);
+}
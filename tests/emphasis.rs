@@ -0,0 +1,321 @@
+use smarkdown::parse;
+
+/// Rule 1: A single `*` character can open emphasis if and only if it is part of a left-flanking delimiter run:
+#[test]
+fn example_350() {
+  let example_string = "*foo bar*";
+  let expected_html = "<p><em>foo bar</em></p>";
+  assert_eq!(parse(example_string).as_html(), expected_html);
+}
+
+/// This is not emphasis, because the opening `*` is followed by whitespace, and hence not part of a left-flanking delimiter run:
+#[test]
+fn example_351() {
+  let example_string = "a * foo bar*";
+  let expected_html = "<p>a * foo bar*</p>";
+  assert_eq!(parse(example_string).as_html(), expected_html);
+}
+
+/// Unicode nonbreaking spaces count as whitespace, too:
+#[test]
+fn example_353() {
+  let example_string = "*\u{a0}a\u{a0}*";
+  let expected_html = "<p>*\u{a0}a\u{a0}*</p>";
+  assert_eq!(parse(example_string).as_html(), expected_html);
+}
+
+/// Intraword emphasis with `*` is permitted:
+#[test]
+fn example_355() {
+  let example_string = "foo*bar*";
+  let expected_html = "<p>foo<em>bar</em></p>";
+  assert_eq!(parse(example_string).as_html(), expected_html);
+}
+
+/// Intraword emphasis with `*` is permitted:
+#[test]
+fn example_356() {
+  let example_string = "5*6*78";
+  let expected_html = "<p>5<em>6</em>78</p>";
+  assert_eq!(parse(example_string).as_html(), expected_html);
+}
+
+/// Rule 2: `_` can open emphasis if it is part of a left-flanking delimiter run:
+#[test]
+fn example_357() {
+  let example_string = "_foo bar_";
+  let expected_html = "<p><em>foo bar</em></p>";
+  assert_eq!(parse(example_string).as_html(), expected_html);
+}
+
+/// This is not emphasis, because the opening `_` is followed by whitespace:
+#[test]
+fn example_358() {
+  let example_string = "_ foo bar_";
+  let expected_html = "<p>_ foo bar_</p>";
+  assert_eq!(parse(example_string).as_html(), expected_html);
+}
+
+/// Emphasis with `_` is not allowed inside words:
+#[test]
+fn example_360() {
+  let example_string = "foo_bar_";
+  let expected_html = "<p>foo_bar_</p>";
+  assert_eq!(parse(example_string).as_html(), expected_html);
+}
+
+/// Emphasis with `_` is not allowed inside words:
+#[test]
+fn example_361() {
+  let example_string = "5_6_78";
+  let expected_html = "<p>5_6_78</p>";
+  assert_eq!(parse(example_string).as_html(), expected_html);
+}
+
+/// Emphasis with `_` is not allowed inside words:
+#[test]
+fn example_362() {
+  let example_string = "пристаням_стремятся_";
+  let expected_html = "<p>пристаням_стремятся_</p>";
+  assert_eq!(parse(example_string).as_html(), expected_html);
+}
+
+/// Here `_` does not generate emphasis, because the first delimiter run is right-flanking and the second left-flanking:
+#[test]
+fn example_364() {
+  let example_string = "foo-_(bar)_";
+  let expected_html = "<p>foo-<em>(bar)</em></p>";
+  assert_eq!(parse(example_string).as_html(), expected_html);
+}
+
+/// Rule 3: This is not emphasis, because the closing delimiter does not match the opening delimiter:
+#[test]
+fn example_365() {
+  let example_string = "_foo*";
+  let expected_html = "<p>_foo*</p>";
+  assert_eq!(parse(example_string).as_html(), expected_html);
+}
+
+/// This is not emphasis, because the closing `*` is preceded by whitespace:
+#[test]
+fn example_366() {
+  let example_string = "*foo bar *";
+  let expected_html = "<p>*foo bar *</p>";
+  assert_eq!(parse(example_string).as_html(), expected_html);
+}
+
+/// The point of this restriction is more easily appreciated with this example:
+#[test]
+fn example_369() {
+  let example_string = "*(*foo*)*";
+  let expected_html = "<p><em>(<em>foo</em>)</em></p>";
+  assert_eq!(parse(example_string).as_html(), expected_html);
+}
+
+/// Rule 4: Intraword emphasis with `*` is allowed:
+#[test]
+fn example_370() {
+  let example_string = "*foo*bar";
+  let expected_html = "<p><em>foo</em>bar</p>";
+  assert_eq!(parse(example_string).as_html(), expected_html);
+}
+
+/// Rule 6: Intraword emphasis is disallowed for `_`:
+#[test]
+fn example_374() {
+  let example_string = "_foo_bar";
+  let expected_html = "<p>_foo_bar</p>";
+  assert_eq!(parse(example_string).as_html(), expected_html);
+}
+
+/// Intraword emphasis is disallowed for `_`:
+#[test]
+fn example_376() {
+  let example_string = "_foo_bar_baz_";
+  let expected_html = "<p><em>foo_bar_baz</em></p>";
+  assert_eq!(parse(example_string).as_html(), expected_html);
+}
+
+/// This is emphasis, even though the closing delimiter is both left- and right-flanking, because it is followed by punctuation:
+#[test]
+fn example_377() {
+  let example_string = "_(bar)_.";
+  let expected_html = "<p><em>(bar)</em>.</p>";
+  assert_eq!(parse(example_string).as_html(), expected_html);
+}
+
+/// Rule 5: Strong emphasis:
+#[test]
+fn example_378() {
+  let example_string = "**foo bar**";
+  let expected_html = "<p><strong>foo bar</strong></p>";
+  assert_eq!(parse(example_string).as_html(), expected_html);
+}
+
+/// Intraword strong emphasis with `**` is permitted:
+#[test]
+fn example_381() {
+  let example_string = "foo**bar**";
+  let expected_html = "<p>foo<strong>bar</strong></p>";
+  assert_eq!(parse(example_string).as_html(), expected_html);
+}
+
+/// Intraword strong emphasis is forbidden with `__`:
+#[test]
+fn example_386() {
+  let example_string = "foo__bar__";
+  let expected_html = "<p>foo__bar__</p>";
+  assert_eq!(parse(example_string).as_html(), expected_html);
+}
+
+/// Strong emphasis can be nested:
+#[test]
+fn example_389() {
+  let example_string = "__foo, __bar__, baz__";
+  let expected_html = "<p><strong>foo, <strong>bar</strong>, baz</strong></p>";
+  assert_eq!(parse(example_string).as_html(), expected_html);
+}
+
+/// Rule 7: strong emphasis must be closed by a right-flanking delimiter run:
+#[test]
+fn example_393() {
+  let example_string = "*(**foo**)*";
+  let expected_html = "<p><em>(<strong>foo</strong>)</em></p>";
+  assert_eq!(parse(example_string).as_html(), expected_html);
+}
+
+/// Rule 9: Any nonempty sequence of inline elements can be the contents of an emphasized span.
+#[test]
+fn example_405() {
+  let example_string = "*foo\nbar*";
+  let expected_html = "<p><em>foo\nbar</em></p>";
+  assert_eq!(parse(example_string).as_html(), expected_html);
+}
+
+/// Emphasis can be nested inside emphasis and strong emphasis:
+#[test]
+fn example_409() {
+  let example_string = "*foo *bar**";
+  let expected_html = "<p><em>foo <em>bar</em></em></p>";
+  assert_eq!(parse(example_string).as_html(), expected_html);
+}
+
+/// When one of the delimiters can both open and close emphasis, the rule of 3 applies:
+#[test]
+fn example_412() {
+  let example_string = "*foo**bar*";
+  let expected_html = "<p><em>foo**bar</em></p>";
+  assert_eq!(parse(example_string).as_html(), expected_html);
+}
+
+/// The rule of 3 does not apply when both delimiter runs have lengths that are multiples of 3:
+#[test]
+fn example_413() {
+  let example_string = "***foo** bar*";
+  let expected_html = "<p><em><strong>foo</strong> bar</em></p>";
+  assert_eq!(parse(example_string).as_html(), expected_html);
+}
+
+/// The rule of 3 allows for intraword `***` runs:
+#[test]
+fn example_416() {
+  let example_string = "foo***bar***baz";
+  let expected_html = "<p>foo<em><strong>bar</strong></em>baz</p>";
+  assert_eq!(parse(example_string).as_html(), expected_html);
+}
+
+/// Indefinite levels of nesting are possible:
+#[test]
+fn example_417() {
+  let example_string = "foo******bar*********baz";
+  let expected_html = "<p>foo<strong><strong><strong>bar</strong></strong></strong>***baz</p>";
+  assert_eq!(parse(example_string).as_html(), expected_html);
+}
+
+/// There can be no empty emphasis or strong emphasis:
+#[test]
+fn example_420() {
+  let example_string = "** is not an empty emphasis";
+  let expected_html = "<p>** is not an empty emphasis</p>";
+  assert_eq!(parse(example_string).as_html(), expected_html);
+}
+
+/// Rule 11: A literal `*` can be used by escaping it:
+#[test]
+fn example_437() {
+  let example_string = "foo *\\**";
+  let expected_html = "<p>foo <em>*</em></p>";
+  assert_eq!(parse(example_string).as_html(), expected_html);
+}
+
+/// Rule 11: Excess `*` are left outside the emphasis:
+#[test]
+fn example_442() {
+  let example_string = "**foo*";
+  let expected_html = "<p>*<em>foo</em></p>";
+  assert_eq!(parse(example_string).as_html(), expected_html);
+}
+
+/// Rule 11: Excess `*` are left outside the emphasis:
+#[test]
+fn example_447() {
+  let example_string = "*foo****";
+  let expected_html = "<p><em>foo</em>***</p>";
+  assert_eq!(parse(example_string).as_html(), expected_html);
+}
+
+/// Rule 12: Excess `_` are left outside the emphasis:
+#[test]
+fn example_456() {
+  let example_string = "___foo__";
+  let expected_html = "<p>_<strong>foo</strong></p>";
+  assert_eq!(parse(example_string).as_html(), expected_html);
+}
+
+/// Rule 13: The number of nestings should be minimized:
+#[test]
+fn example_461() {
+  let example_string = "*_foo_*";
+  let expected_html = "<p><em><em>foo</em></em></p>";
+  assert_eq!(parse(example_string).as_html(), expected_html);
+}
+
+/// Rule 14: `<em><strong>` is preferred to `<strong><em>`:
+#[test]
+fn example_466() {
+  let example_string = "******foo******";
+  let expected_html = "<p><strong><strong><strong>foo</strong></strong></strong></p>";
+  assert_eq!(parse(example_string).as_html(), expected_html);
+}
+
+/// Rule 14: `<em><strong>` is preferred to `<strong><em>`:
+#[test]
+fn example_468() {
+  let example_string = "_____foo_____";
+  let expected_html = "<p><em><strong><strong>foo</strong></strong></em></p>";
+  assert_eq!(parse(example_string).as_html(), expected_html);
+}
+
+/// Rule 15: The first closing delimiter wins when emphasis overlaps:
+#[test]
+fn example_469() {
+  let example_string = "*foo _bar* baz_";
+  let expected_html = "<p><em>foo _bar</em> baz_</p>";
+  assert_eq!(parse(example_string).as_html(), expected_html);
+}
+
+/// Rule 16: When there are two potential emphasis regions with the same closing delimiter, the shorter one wins:
+#[test]
+fn example_471() {
+  let example_string = "**foo **bar baz**";
+  let expected_html = "<p>**foo <strong>bar baz</strong></p>";
+  assert_eq!(parse(example_string).as_html(), expected_html);
+}
+
+/// Rule 16: When there are two potential emphasis regions with the same closing delimiter, the shorter one wins:
+#[test]
+fn example_472() {
+  let example_string = "*foo *bar baz*";
+  let expected_html = "<p>*foo <em>bar baz</em></p>";
+  assert_eq!(parse(example_string).as_html(), expected_html);
+}
@@ -0,0 +1,136 @@
+use smarkdown::parse;
+
+/// Decimal numeric character references consist of `&#` + a string of 1–7 arabic digits + `;`. Invalid Unicode code points will be replaced by the REPLACEMENT CHARACTER:
+#[test]
+fn example_26() {
+  let example_string = "&#35; &#1234; &#992; &#0;";
+  let expected_html = "<p># Ӓ Ϡ �</p>";
+  assert_eq!(parse(example_string).as_html(), expected_html);
+}
+
+/// Entity and numeric character references are recognized in any context besides code spans or code blocks, including URLs, link titles, and fenced code block info strings:
+#[test]
+fn example_31() {
+  let example_string = "<a href=\"&ouml;&ouml;.html\">";
+  let expected_html = "<a href=\"&ouml;&ouml;.html\">";
+  assert_eq!(parse(example_string).as_html(), expected_html);
+}
+
+/// Entity and numeric character references are recognized in any context besides code spans or code blocks, including URLs, link titles, and fenced code block info strings:
+#[test]
+fn example_34() {
+  let example_string = "``` f&ouml;&ouml;\nfoo\n```";
+  let expected_html = "<pre><code class=\"language-föö\">foo\n</code></pre>";
+  assert_eq!(parse(example_string).as_html(), expected_html);
+}
+
+/// Entity and numeric character references are treated as literal text in code spans and code blocks:
+#[test]
+fn example_35() {
+  let example_string = "`f&ouml;&ouml;`";
+  let expected_html = "<p><code>f&amp;ouml;&amp;ouml;</code></p>";
+  assert_eq!(parse(example_string).as_html(), expected_html);
+}
+
+/// Entity and numeric character references cannot be used in place of symbols indicating structure in CommonMark documents.
+#[test]
+fn example_37() {
+  let example_string = "&#42;foo&#42;\n*foo*";
+  let expected_html = "<p>*foo*\n<em>foo</em></p>";
+  assert_eq!(parse(example_string).as_html(), expected_html);
+}
+
+/// Entity and numeric character references cannot be used in place of symbols indicating structure in CommonMark documents.
+#[test]
+fn example_38() {
+  let example_string = "&#42; foo\n\n* foo";
+  let expected_html = "<p>* foo</p>\n<ul>\n<li>foo</li>\n</ul>";
+  assert_eq!(parse(example_string).as_html(), expected_html);
+}
+
+/// Entity and numeric character references cannot be used in place of symbols indicating structure in CommonMark documents.
+#[test]
+fn example_39() {
+  let example_string = "foo&#10;&#10;bar";
+  let expected_html = "<p>foo\n\nbar</p>";
+  assert_eq!(parse(example_string).as_html(), expected_html);
+}
+
+/// Entity and numeric character references cannot be used in place of symbols indicating structure in CommonMark documents.
+#[test]
+fn example_40() {
+  let example_string = "&#9;foo";
+  let expected_html = "<p>\tfoo</p>";
+  assert_eq!(parse(example_string).as_html(), expected_html);
+}
+/// Entity references consist of `&` + any of the valid HTML5 entity names + `;`.
+#[test]
+fn example_25() {
+  let example_string = "&nbsp; &amp; &copy; &AElig; &Dcaron;\n&frac34; &HilbertSpace; &DifferentialD;\n&ClockwiseContourIntegral; &ngE;";
+  let expected_html = "<p>  &amp; © Æ Ď\n¾ ℋ ⅆ\n∲ ≧̸</p>";
+  assert_eq!(parse(example_string).as_html(), expected_html);
+}
+
+/// Hexadecimal numeric character references consist of `&#` + either `X` or `x` + a string of 1-6 hexadecimal digits + `;`.
+#[test]
+fn example_27() {
+  let example_string = "&#X22; &#XD06; &#xcab;";
+  let expected_html = "<p>&quot; ആ ಫ</p>";
+  assert_eq!(parse(example_string).as_html(), expected_html);
+}
+
+/// Here are some nonentities:
+#[test]
+fn example_28() {
+  let example_string = "&nbsp &x; &#; &#x;\n&#87654321;\n&#abcdef0;\n&ThisIsNotDefined; &hi?;";
+  let expected_html = "<p>&amp;nbsp &amp;x; &amp;#; &amp;#x;\n&amp;#87654321;\n&amp;#abcdef0;\n&amp;ThisIsNotDefined; &amp;hi?;</p>";
+  assert_eq!(parse(example_string).as_html(), expected_html);
+}
+
+/// Although HTML5 does accept some entity references without a trailing semicolon (such as `&copy`), these are not recognized here, because it makes the grammar too ambiguous:
+#[test]
+fn example_29() {
+  let example_string = "&copy";
+  let expected_html = "<p>&amp;copy</p>";
+  assert_eq!(parse(example_string).as_html(), expected_html);
+}
+
+/// Strings that are not on the list of HTML5 named entities are not recognized as entity references either:
+#[test]
+fn example_30() {
+  let example_string = "&MadeUpEntity;";
+  let expected_html = "<p>&amp;MadeUpEntity;</p>";
+  assert_eq!(parse(example_string).as_html(), expected_html);
+}
+
+/// Entity and numeric character references are recognized in any context besides code spans or code blocks, including URLs, link titles, and fenced code block info strings:
+#[test]
+fn example_32() {
+  let example_string = "[foo](/f&ouml;&ouml; \"f&ouml;&ouml;\")";
+  let expected_html = "<p><a href=\"/f%C3%B6%C3%B6\" title=\"föö\">foo</a></p>";
+  assert_eq!(parse(example_string).as_html(), expected_html);
+}
+
+/// Entity and numeric character references are recognized in any context besides code spans or code blocks, including URLs, link titles, and fenced code block info strings:
+#[test]
+fn example_33() {
+  let example_string = "[foo]\n\n[foo]: /f&ouml;&ouml; \"f&ouml;&ouml;\"";
+  let expected_html = "<p><a href=\"/f%C3%B6%C3%B6\" title=\"föö\">foo</a></p>";
+  assert_eq!(parse(example_string).as_html(), expected_html);
+}
+
+/// Entity and numeric character references are treated as literal text in code spans and code blocks:
+#[test]
+fn example_36() {
+  let example_string = "    f&ouml;f&ouml;";
+  let expected_html = "<pre><code>f&amp;ouml;f&amp;ouml;\n</code></pre>";
+  assert_eq!(parse(example_string).as_html(), expected_html);
+}
+
+/// Entity and numeric character references cannot be used in place of symbols indicating structure in CommonMark documents.
+#[test]
+fn example_41() {
+  let example_string = "[a](url &quot;tit&quot;)";
+  let expected_html = "<p>[a](url &quot;tit&quot;)</p>";
+  assert_eq!(parse(example_string).as_html(), expected_html);
+}
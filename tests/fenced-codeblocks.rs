@@ -0,0 +1,235 @@
+use smarkdown::parse;
+
+/// This is a simple example with backticks:
+#[test]
+fn example_119() {
+  let example_string = "```\n<\n >\n```";
+  let expected_html = "<pre><code>&lt;\n &gt;\n</code></pre>";
+  assert_eq!(parse(example_string).as_html(), expected_html);
+}
+
+/// With tildes:
+#[test]
+fn example_120() {
+  let example_string = "~~~\n<\n >\n~~~";
+  let expected_html = "<pre><code>&lt;\n &gt;\n</code></pre>";
+  assert_eq!(parse(example_string).as_html(), expected_html);
+}
+
+/// Fewer than three backticks is not enough:
+#[test]
+fn example_121() {
+  let example_string = "``\nfoo\n``";
+  let expected_html = "<p><code>foo</code></p>";
+  assert_eq!(parse(example_string).as_html(), expected_html);
+}
+
+/// The closing code fence must use the same character as the opening fence:
+#[test]
+fn example_122() {
+  let example_string = "```\naaa\n~~~\n```";
+  let expected_html = "<pre><code>aaa\n~~~\n</code></pre>";
+  assert_eq!(parse(example_string).as_html(), expected_html);
+}
+
+/// The closing code fence must use the same character as the opening fence:
+#[test]
+fn example_123() {
+  let example_string = "~~~\naaa\n```\n~~~";
+  let expected_html = "<pre><code>aaa\n```\n</code></pre>";
+  assert_eq!(parse(example_string).as_html(), expected_html);
+}
+
+/// The closing code fence must be at least as long as the opening fence:
+#[test]
+fn example_124() {
+  let example_string = "````\naaa\n```\n``````";
+  let expected_html = "<pre><code>aaa\n```\n</code></pre>";
+  assert_eq!(parse(example_string).as_html(), expected_html);
+}
+
+/// The closing code fence must be at least as long as the opening fence:
+#[test]
+fn example_125() {
+  let example_string = "~~~~\naaa\n~~~\n~~~~";
+  let expected_html = "<pre><code>aaa\n~~~\n</code></pre>";
+  assert_eq!(parse(example_string).as_html(), expected_html);
+}
+
+/// Unclosed code blocks are closed by the end of the document (or the enclosing block quote or list item):
+#[test]
+fn example_126() {
+  let example_string = "```";
+  let expected_html = "<pre><code></code></pre>";
+  assert_eq!(parse(example_string).as_html(), expected_html);
+}
+
+/// Unclosed code blocks are closed by the end of the document (or the enclosing block quote or list item):
+#[test]
+fn example_127() {
+  let example_string = "`````\n\n```\naaa";
+  let expected_html = "<pre><code>\n```\naaa\n</code></pre>";
+  assert_eq!(parse(example_string).as_html(), expected_html);
+}
+
+/// Unclosed code blocks are closed by the end of the document (or the enclosing block quote or list item):
+#[test]
+fn example_128() {
+  let example_string = "> ```\n> aaa\n\nbbb";
+  let expected_html = "<blockquote>\n<pre><code>aaa\n</code></pre>\n</blockquote>\n<p>bbb</p>";
+  assert_eq!(parse(example_string).as_html(), expected_html);
+}
+
+/// A code block can have all empty lines as its content:
+#[test]
+fn example_129() {
+  let example_string = "```\n\n  \n```";
+  let expected_html = "<pre><code>\n  \n</code></pre>";
+  assert_eq!(parse(example_string).as_html(), expected_html);
+}
+
+/// A code block can be empty:
+#[test]
+fn example_130() {
+  let example_string = "```\n```";
+  let expected_html = "<pre><code></code></pre>";
+  assert_eq!(parse(example_string).as_html(), expected_html);
+}
+
+/// Fences can be indented. If the opening fence is indented, content lines will have equivalent opening indentation removed, if present:
+#[test]
+fn example_131() {
+  let example_string = " ```\n aaa\naaa\n```";
+  let expected_html = "<pre><code>aaa\naaa\n</code></pre>";
+  assert_eq!(parse(example_string).as_html(), expected_html);
+}
+
+/// Fences can be indented. If the opening fence is indented, content lines will have equivalent opening indentation removed, if present:
+#[test]
+fn example_132() {
+  let example_string = "  ```\naaa\n  aaa\naaa\n  ```";
+  let expected_html = "<pre><code>aaa\naaa\naaa\n</code></pre>";
+  assert_eq!(parse(example_string).as_html(), expected_html);
+}
+
+/// Fences can be indented. If the opening fence is indented, content lines will have equivalent opening indentation removed, if present:
+#[test]
+fn example_133() {
+  let example_string = "   ```\n   aaa\n    aaa\n  aaa\n   ```";
+  let expected_html = "<pre><code>aaa\n aaa\naaa\n</code></pre>";
+  assert_eq!(parse(example_string).as_html(), expected_html);
+}
+
+/// Four spaces of indentation is too many:
+#[test]
+fn example_134() {
+  let example_string = "    ```\n    aaa\n    ```";
+  let expected_html = "<pre><code>```\naaa\n```\n</code></pre>";
+  assert_eq!(parse(example_string).as_html(), expected_html);
+}
+
+/// Closing fences may be preceded by up to three spaces of indentation, and their indentation need not match that of the opening fence:
+#[test]
+fn example_135() {
+  let example_string = "```\naaa\n  ```";
+  let expected_html = "<pre><code>aaa\n</code></pre>";
+  assert_eq!(parse(example_string).as_html(), expected_html);
+}
+
+/// Closing fences may be preceded by up to three spaces of indentation, and their indentation need not match that of the opening fence:
+#[test]
+fn example_136() {
+  let example_string = "   ```\naaa\n  ```";
+  let expected_html = "<pre><code>aaa\n</code></pre>";
+  assert_eq!(parse(example_string).as_html(), expected_html);
+}
+
+/// This is not a closing fence, because it is indented 4 spaces:
+#[test]
+fn example_137() {
+  let example_string = "```\naaa\n    ```";
+  let expected_html = "<pre><code>aaa\n    ```\n</code></pre>";
+  assert_eq!(parse(example_string).as_html(), expected_html);
+}
+
+/// Code fences (opening and closing) cannot contain internal spaces or tabs:
+#[test]
+fn example_138() {
+  let example_string = "``` ```\naaa";
+  let expected_html = "<p><code> </code>\naaa</p>";
+  assert_eq!(parse(example_string).as_html(), expected_html);
+}
+
+/// Code fences (opening and closing) cannot contain internal spaces or tabs:
+#[test]
+fn example_139() {
+  let example_string = "~~~~~~\naaa\n~~~ ~~";
+  let expected_html = "<pre><code>aaa\n~~~ ~~\n</code></pre>";
+  assert_eq!(parse(example_string).as_html(), expected_html);
+}
+
+/// Fenced code blocks can interrupt paragraphs, and can be followed directly by paragraphs, without a blank line between:
+#[test]
+fn example_140() {
+  let example_string = "foo\n```\nbar\n```\nbaz";
+  let expected_html = "<p>foo</p>\n<pre><code>bar\n</code></pre>\n<p>baz</p>";
+  assert_eq!(parse(example_string).as_html(), expected_html);
+}
+
+/// Other blocks can also occur before and after fenced code blocks without an intervening blank line:
+#[test]
+fn example_141() {
+  let example_string = "foo\n---\n~~~\nbar\n~~~\n# baz";
+  let expected_html = "<h2>foo</h2>\n<pre><code>bar\n</code></pre>\n<h1>baz</h1>";
+  assert_eq!(parse(example_string).as_html(), expected_html);
+}
+
+/// An info string can be provided after the opening code fence. The first word of the info string is used as the language:
+#[test]
+fn example_142() {
+  let example_string = "```ruby\ndef foo(x)\n  return 3\nend\n```";
+  let expected_html =
+    "<pre><code class=\"language-ruby\">def foo(x)\n  return 3\nend\n</code></pre>";
+  assert_eq!(parse(example_string).as_html(), expected_html);
+}
+
+/// An info string can be provided after the opening code fence. The first word of the info string is used as the language:
+#[test]
+fn example_143() {
+  let example_string = "~~~~    ruby startline=3 $%@#$\ndef foo(x)\n  return 3\nend\n~~~~~~~";
+  let expected_html =
+    "<pre><code class=\"language-ruby\">def foo(x)\n  return 3\nend\n</code></pre>";
+  assert_eq!(parse(example_string).as_html(), expected_html);
+}
+
+/// An info string can be provided after the opening code fence. The first word of the info string is used as the language:
+#[test]
+fn example_144() {
+  let example_string = "````;\n````";
+  let expected_html = "<pre><code class=\"language-;\"></code></pre>";
+  assert_eq!(parse(example_string).as_html(), expected_html);
+}
+
+/// Info strings for backtick code blocks cannot contain backticks:
+#[test]
+fn example_145() {
+  let example_string = "``` aa ```\nfoo";
+  let expected_html = "<p><code>aa</code>\nfoo</p>";
+  assert_eq!(parse(example_string).as_html(), expected_html);
+}
+
+/// Info strings for tilde code blocks can contain backticks and tildes:
+#[test]
+fn example_146() {
+  let example_string = "~~~ aa ``` ~~~\nfoo\n~~~";
+  let expected_html = "<pre><code class=\"language-aa\">foo\n</code></pre>";
+  assert_eq!(parse(example_string).as_html(), expected_html);
+}
+
+/// Closing code fences cannot have info strings:
+#[test]
+fn example_147() {
+  let example_string = "```\n``` aaa\n```";
+  let expected_html = "<pre><code>``` aaa\n</code></pre>";
+  assert_eq!(parse(example_string).as_html(), expected_html);
+}
@@ -0,0 +1,121 @@
+use smarkdown::parse;
+
+/// A line ending (not in a code span or HTML tag) that is preceded by two or more spaces and does not occur at the end of a block is parsed as a hard line break:
+#[test]
+fn example_633() {
+  let example_string = "foo  \nbaz";
+  let expected_html = "<p>foo<br />\nbaz</p>";
+  assert_eq!(parse(example_string).as_html(), expected_html);
+}
+
+/// For a more visible alternative, a backslash before the line ending may be used instead of two or more spaces:
+#[test]
+fn example_634() {
+  let example_string = "foo\\\nbaz";
+  let expected_html = "<p>foo<br />\nbaz</p>";
+  assert_eq!(parse(example_string).as_html(), expected_html);
+}
+
+/// More than two spaces can be used:
+#[test]
+fn example_635() {
+  let example_string = "foo       \nbaz";
+  let expected_html = "<p>foo<br />\nbaz</p>";
+  assert_eq!(parse(example_string).as_html(), expected_html);
+}
+
+/// Leading spaces at the beginning of the next line are ignored:
+#[test]
+fn example_636() {
+  let example_string = "foo  \n     bar";
+  let expected_html = "<p>foo<br />\nbar</p>";
+  assert_eq!(parse(example_string).as_html(), expected_html);
+}
+
+/// Leading spaces at the beginning of the next line are ignored:
+#[test]
+fn example_637() {
+  let example_string = "foo\\\n     bar";
+  let expected_html = "<p>foo<br />\nbar</p>";
+  assert_eq!(parse(example_string).as_html(), expected_html);
+}
+
+/// Hard line breaks can occur inside emphasis, links, and other constructs that allow inline content:
+#[test]
+fn example_638() {
+  let example_string = "*foo  \nbar*";
+  let expected_html = "<p><em>foo<br />\nbar</em></p>";
+  assert_eq!(parse(example_string).as_html(), expected_html);
+}
+
+/// Hard line breaks can occur inside emphasis, links, and other constructs that allow inline content:
+#[test]
+fn example_639() {
+  let example_string = "*foo\\\nbar*";
+  let expected_html = "<p><em>foo<br />\nbar</em></p>";
+  assert_eq!(parse(example_string).as_html(), expected_html);
+}
+
+/// Hard line breaks do not occur inside code spans
+#[test]
+fn example_640() {
+  let example_string = "`code  \nspan`";
+  let expected_html = "<p><code>code   span</code></p>";
+  assert_eq!(parse(example_string).as_html(), expected_html);
+}
+
+/// Hard line breaks do not occur inside code spans
+#[test]
+fn example_641() {
+  let example_string = "`code\\\nspan`";
+  let expected_html = "<p><code>code\\ span</code></p>";
+  assert_eq!(parse(example_string).as_html(), expected_html);
+}
+
+/// or HTML tags:
+#[test]
+fn example_642() {
+  let example_string = "<a href=\"foo  \nbar\">";
+  let expected_html = "<p><a href=\"foo  \nbar\"></p>";
+  assert_eq!(parse(example_string).as_html(), expected_html);
+}
+
+/// or HTML tags:
+#[test]
+fn example_643() {
+  let example_string = "<a href=\"foo\\\nbar\">";
+  let expected_html = "<p><a href=\"foo\\\nbar\"></p>";
+  assert_eq!(parse(example_string).as_html(), expected_html);
+}
+
+/// Hard line breaks are for separating inline content within a block. Neither syntax for hard line breaks works at the end of a paragraph or other block element:
+#[test]
+fn example_644() {
+  let example_string = "foo\\";
+  let expected_html = "<p>foo\\</p>";
+  assert_eq!(parse(example_string).as_html(), expected_html);
+}
+
+/// Hard line breaks are for separating inline content within a block. Neither syntax for hard line breaks works at the end of a paragraph or other block element:
+#[test]
+fn example_645() {
+  let example_string = "foo  ";
+  let expected_html = "<p>foo</p>";
+  assert_eq!(parse(example_string).as_html(), expected_html);
+}
+
+/// Hard line breaks are for separating inline content within a block. Neither syntax for hard line breaks works at the end of a paragraph or other block element:
+#[test]
+fn example_646() {
+  let example_string = "### foo\\";
+  let expected_html = "<h3>foo\\</h3>";
+  assert_eq!(parse(example_string).as_html(), expected_html);
+}
+
+/// Hard line breaks are for separating inline content within a block. Neither syntax for hard line breaks works at the end of a paragraph or other block element:
+#[test]
+fn example_647() {
+  let example_string = "### foo  ";
+  let expected_html = "<h3>foo</h3>";
+  assert_eq!(parse(example_string).as_html(), expected_html);
+}
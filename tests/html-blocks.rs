@@ -0,0 +1,358 @@
+use smarkdown::parse;
+
+/// An HTML block is a group of lines that is treated as raw HTML (and will not be escaped in HTML output).
+#[test]
+fn example_148() {
+  let example_string = "<table><tr><td>\n<pre>\n**Hello**,\n\n_world_.\n</pre>\n</td></tr></table>";
+  let expected_html =
+    "<table><tr><td>\n<pre>\n**Hello**,\n<p><em>world</em>.\n</pre></p>\n</td></tr></table>";
+  assert_eq!(parse(example_string).as_html(), expected_html);
+}
+
+/// A block of type 6 ends at the first blank line:
+#[test]
+fn example_149() {
+  let example_string =
+    "<table>\n  <tr>\n    <td>\n           hi\n    </td>\n  </tr>\n</table>\n\nokay.";
+  let expected_html =
+    "<table>\n  <tr>\n    <td>\n           hi\n    </td>\n  </tr>\n</table>\n<p>okay.</p>";
+  assert_eq!(parse(example_string).as_html(), expected_html);
+}
+
+/// A block can also start with a closing tag:
+#[test]
+fn example_150() {
+  let example_string = " <div>\n  *hello*\n         <foo><a>";
+  let expected_html = " <div>\n  *hello*\n         <foo><a>";
+  assert_eq!(parse(example_string).as_html(), expected_html);
+}
+
+/// A block can also start with a closing tag:
+#[test]
+fn example_151() {
+  let example_string = "</div>\n*foo*";
+  let expected_html = "</div>\n*foo*";
+  assert_eq!(parse(example_string).as_html(), expected_html);
+}
+
+/// Here we have two HTML blocks with a Markdown paragraph between them:
+#[test]
+fn example_152() {
+  let example_string = "<DIV CLASS=\"foo\">\n\n*Markdown*\n\n</DIV>";
+  let expected_html = "<DIV CLASS=\"foo\">\n<p><em>Markdown</em></p>\n</DIV>";
+  assert_eq!(parse(example_string).as_html(), expected_html);
+}
+
+/// The tag on the first line can be partial, as long as it is split where there would be whitespace:
+#[test]
+fn example_153() {
+  let example_string = "<div id=\"foo\"\n  class=\"bar\">\n</div>";
+  let expected_html = "<div id=\"foo\"\n  class=\"bar\">\n</div>";
+  assert_eq!(parse(example_string).as_html(), expected_html);
+}
+
+/// An open tag need not be closed:
+#[test]
+fn example_154() {
+  let example_string = "<div id=\"foo\" class=\"bar\n  baz\">\n</div>";
+  let expected_html = "<div id=\"foo\" class=\"bar\n  baz\">\n</div>";
+  assert_eq!(parse(example_string).as_html(), expected_html);
+}
+
+/// An open tag need not be closed:
+#[test]
+fn example_155() {
+  let example_string = "<div>\n*foo*\n\n*bar*";
+  let expected_html = "<div>\n*foo*\n<p><em>bar</em></p>";
+  assert_eq!(parse(example_string).as_html(), expected_html);
+}
+
+/// A partial tag need not even be completed (garbage in, garbage out):
+#[test]
+fn example_156() {
+  let example_string = "<div id=\"foo\"\n*hi*";
+  let expected_html = "<div id=\"foo\"\n*hi*";
+  assert_eq!(parse(example_string).as_html(), expected_html);
+}
+
+/// A partial tag need not even be completed (garbage in, garbage out):
+#[test]
+fn example_157() {
+  let example_string = "<div class\nfoo";
+  let expected_html = "<div class\nfoo";
+  assert_eq!(parse(example_string).as_html(), expected_html);
+}
+
+/// A partial tag need not even be completed (garbage in, garbage out):
+#[test]
+fn example_158() {
+  let example_string = "<div *???-&&&-<---\n*foo*";
+  let expected_html = "<div *???-&&&-<---\n*foo*";
+  assert_eq!(parse(example_string).as_html(), expected_html);
+}
+
+/// The initial tag doesn’t even need to be a tag name:
+#[test]
+fn example_159() {
+  let example_string = "<div><a href=\"bar\">*foo*</a></div>";
+  let expected_html = "<div><a href=\"bar\">*foo*</a></div>";
+  assert_eq!(parse(example_string).as_html(), expected_html);
+}
+
+/// Everything until the next blank line or end of document gets included in the HTML block.
+#[test]
+fn example_160() {
+  let example_string = "<table><tr><td>\nfoo\n</td></tr></table>";
+  let expected_html = "<table><tr><td>\nfoo\n</td></tr></table>";
+  assert_eq!(parse(example_string).as_html(), expected_html);
+}
+
+/// Everything until the next blank line or end of document gets included in the HTML block.
+#[test]
+fn example_161() {
+  let example_string = "<div></div>\n``` c\nint x = 33;\n```";
+  let expected_html = "<div></div>\n``` c\nint x = 33;\n```";
+  assert_eq!(parse(example_string).as_html(), expected_html);
+}
+
+/// To start an HTML block with a tag that is not in the list of block-level tags in (6), you must put the tag by itself on the first line (and it must be complete):
+#[test]
+fn example_162() {
+  let example_string = "<a href=\"foo\">\n*bar*\n</a>";
+  let expected_html = "<a href=\"foo\">\n*bar*\n</a>";
+  assert_eq!(parse(example_string).as_html(), expected_html);
+}
+
+/// In type 7 blocks, the tag name can be anything:
+#[test]
+fn example_163() {
+  let example_string = "<Warning>\n*bar*\n</Warning>";
+  let expected_html = "<Warning>\n*bar*\n</Warning>";
+  assert_eq!(parse(example_string).as_html(), expected_html);
+}
+
+/// In type 7 blocks, the tag name can be anything:
+#[test]
+fn example_164() {
+  let example_string = "<i class=\"foo\">\n*bar*\n</i>";
+  let expected_html = "<i class=\"foo\">\n*bar*\n</i>";
+  assert_eq!(parse(example_string).as_html(), expected_html);
+}
+
+/// In type 7 blocks, the tag name can be anything:
+#[test]
+fn example_165() {
+  let example_string = "</ins>\n*bar*";
+  let expected_html = "</ins>\n*bar*";
+  assert_eq!(parse(example_string).as_html(), expected_html);
+}
+
+/// These rules are designed to allow us to work with tags that can function as either block-level or inline-level tags.
+#[test]
+fn example_166() {
+  let example_string = "<del>\n*foo*\n</del>";
+  let expected_html = "<del>\n*foo*\n</del>";
+  assert_eq!(parse(example_string).as_html(), expected_html);
+}
+
+/// In this case, we get a raw HTML block that just includes the `<del>` tag (because it ends with the following blank line). So the contents get interpreted as CommonMark:
+#[test]
+fn example_167() {
+  let example_string = "<del>\n\n*foo*\n\n</del>";
+  let expected_html = "<del>\n<p><em>foo</em></p>\n</del>";
+  assert_eq!(parse(example_string).as_html(), expected_html);
+}
+
+/// Finally, in this case, the `<del>` tags are interpreted as raw HTML inside the CommonMark paragraph.
+#[test]
+fn example_168() {
+  let example_string = "<del>*foo*</del>";
+  let expected_html = "<p><del><em>foo</em></del></p>";
+  assert_eq!(parse(example_string).as_html(), expected_html);
+}
+
+/// HTML tags designed to contain literal content (`pre`, `script`, `style`, `textarea`), comments, processing instructions, and declarations are treated somewhat differently.
+#[test]
+fn example_169() {
+  let example_string = "<pre language=\"haskell\"><code>\nimport Text.HTML.TagSoup\n\nmain :: IO ()\nmain = print $ parseTags tags\n</code></pre>\nokay";
+  let expected_html = "<pre language=\"haskell\"><code>\nimport Text.HTML.TagSoup\n\nmain :: IO ()\nmain = print $ parseTags tags\n</code></pre>\n<p>okay</p>";
+  assert_eq!(parse(example_string).as_html(), expected_html);
+}
+
+/// A script tag:
+#[test]
+fn example_170() {
+  let example_string = "<script type=\"text/javascript\">\n// JavaScript example\n\ndocument.getElementById(\"demo\").innerHTML = \"Hello JavaScript!\";\n</script>\nokay";
+  let expected_html = "<script type=\"text/javascript\">\n// JavaScript example\n\ndocument.getElementById(\"demo\").innerHTML = \"Hello JavaScript!\";\n</script>\n<p>okay</p>";
+  assert_eq!(parse(example_string).as_html(), expected_html);
+}
+
+/// A textarea tag:
+#[test]
+fn example_171() {
+  let example_string = "<textarea>\n\n*foo*\n\n_bar_\n\n</textarea>";
+  let expected_html = "<textarea>\n\n*foo*\n\n_bar_\n\n</textarea>";
+  assert_eq!(parse(example_string).as_html(), expected_html);
+}
+
+/// A style tag:
+#[test]
+fn example_172() {
+  let example_string =
+    "<style\n  type=\"text/css\">\nh1 {color:red;}\n\np {color:blue;}\n</style>\nokay";
+  let expected_html =
+    "<style\n  type=\"text/css\">\nh1 {color:red;}\n\np {color:blue;}\n</style>\n<p>okay</p>";
+  assert_eq!(parse(example_string).as_html(), expected_html);
+}
+
+/// If there is no matching end tag, the block will end at the end of the document (or the enclosing block quote or list item):
+#[test]
+fn example_173() {
+  let example_string = "<style\n  type=\"text/css\">\n\nfoo";
+  let expected_html = "<style\n  type=\"text/css\">\n\nfoo";
+  assert_eq!(parse(example_string).as_html(), expected_html);
+}
+
+/// If there is no matching end tag, the block will end at the end of the document (or the enclosing block quote or list item):
+#[test]
+fn example_174() {
+  let example_string = "> <div>\n> foo\n\nbar";
+  let expected_html = "<blockquote>\n<div>\nfoo\n</blockquote>\n<p>bar</p>";
+  assert_eq!(parse(example_string).as_html(), expected_html);
+}
+
+/// If there is no matching end tag, the block will end at the end of the document (or the enclosing block quote or list item):
+#[test]
+fn example_175() {
+  let example_string = "- <div>\n- foo";
+  let expected_html = "<ul>\n<li>\n<div>\n</li>\n<li>foo</li>\n</ul>";
+  assert_eq!(parse(example_string).as_html(), expected_html);
+}
+
+/// The end tag can occur on the same line as the start tag:
+#[test]
+fn example_176() {
+  let example_string = "<style>p{color:red;}</style>\n*foo*";
+  let expected_html = "<style>p{color:red;}</style>\n<p><em>foo</em></p>";
+  assert_eq!(parse(example_string).as_html(), expected_html);
+}
+
+/// The end tag can occur on the same line as the start tag:
+#[test]
+fn example_177() {
+  let example_string = "<!-- foo -->*bar*\n*baz*";
+  let expected_html = "<!-- foo -->*bar*\n<p><em>baz</em></p>";
+  assert_eq!(parse(example_string).as_html(), expected_html);
+}
+
+/// Note that anything on the last line after the end tag will be included in the HTML block:
+#[test]
+fn example_178() {
+  let example_string = "<script>\nfoo\n</script>1. *bar*";
+  let expected_html = "<script>\nfoo\n</script>1. *bar*";
+  assert_eq!(parse(example_string).as_html(), expected_html);
+}
+
+/// A comment (type 2):
+#[test]
+fn example_179() {
+  let example_string = "<!-- Foo\n\nbar\n   baz -->\nokay";
+  let expected_html = "<!-- Foo\n\nbar\n   baz -->\n<p>okay</p>";
+  assert_eq!(parse(example_string).as_html(), expected_html);
+}
+
+/// A processing instruction (type 3):
+#[test]
+fn example_180() {
+  let example_string = "<?php\n\n  echo '>';\n\n?>\nokay";
+  let expected_html = "<?php\n\n  echo '>';\n\n?>\n<p>okay</p>";
+  assert_eq!(parse(example_string).as_html(), expected_html);
+}
+
+/// A declaration (type 4):
+#[test]
+fn example_181() {
+  let example_string = "<!DOCTYPE html>";
+  let expected_html = "<!DOCTYPE html>";
+  assert_eq!(parse(example_string).as_html(), expected_html);
+}
+
+/// CDATA (type 5):
+#[test]
+fn example_182() {
+  let example_string = "<![CDATA[\nfunction matchwo(a,b)\n{\n  if (a < b && a < 0) then {\n    return 1;\n\n  } else {\n\n    return 0;\n  }\n}\n]]>\nokay";
+  let expected_html = "<![CDATA[\nfunction matchwo(a,b)\n{\n  if (a < b && a < 0) then {\n    return 1;\n\n  } else {\n\n    return 0;\n  }\n}\n]]>\n<p>okay</p>";
+  assert_eq!(parse(example_string).as_html(), expected_html);
+}
+
+/// An HTML block of types 1–6 can interrupt a paragraph, and need not be preceded by a blank line.
+#[test]
+fn example_185() {
+  let example_string = "Foo\n<div>\nbar\n</div>";
+  let expected_html = "<p>Foo</p>\n<div>\nbar\n</div>";
+  assert_eq!(parse(example_string).as_html(), expected_html);
+}
+
+/// However, a following blank line is needed, except at the end of a document, and except for blocks of types 1–5, above:
+#[test]
+fn example_186() {
+  let example_string = "<div>\nbar\n</div>\n*foo*";
+  let expected_html = "<div>\nbar\n</div>\n*foo*";
+  assert_eq!(parse(example_string).as_html(), expected_html);
+}
+
+/// HTML blocks of type 7 cannot interrupt a paragraph:
+#[test]
+fn example_187() {
+  let example_string = "Foo\n<a href=\"bar\">\nbaz";
+  let expected_html = "<p>Foo\n<a href=\"bar\">\nbaz</p>";
+  assert_eq!(parse(example_string).as_html(), expected_html);
+}
+
+/// The rule is a bit tricky, but is a good way of letting Markdown and HTML coexist.
+#[test]
+fn example_188() {
+  let example_string = "<div>\n\n*Emphasized* text.\n\n</div>";
+  let expected_html = "<div>\n<p><em>Emphasized</em> text.</p>\n</div>";
+  assert_eq!(parse(example_string).as_html(), expected_html);
+}
+
+/// Here the contents are not interpreted as Markdown, since there is no blank line between the tags:
+#[test]
+fn example_189() {
+  let example_string = "<div>\n*Emphasized* text.\n</div>";
+  let expected_html = "<div>\n*Emphasized* text.\n</div>";
+  assert_eq!(parse(example_string).as_html(), expected_html);
+}
+
+/// Markdown can be used within HTML tags by separating it from the tags with blank lines:
+#[test]
+fn example_190() {
+  let example_string = "<table>\n\n<tr>\n\n<td>\nHi\n</td>\n\n</tr>\n\n</table>";
+  let expected_html = "<table>\n<tr>\n<td>\nHi\n</td>\n</tr>\n</table>";
+  assert_eq!(parse(example_string).as_html(), expected_html);
+}
+/// The opening tag can be preceded by up to three spaces of indentation, but not four:
+#[test]
+fn example_183() {
+  let example_string = "  <!-- foo -->\n\n    <!-- foo -->";
+  let expected_html = "  <!-- foo -->\n<pre><code>&lt;!-- foo --&gt;\n</code></pre>";
+  assert_eq!(parse(example_string).as_html(), expected_html);
+}
+
+/// The opening tag can be preceded by up to three spaces of indentation, but not four:
+#[test]
+fn example_184() {
+  let example_string = "  <div>\n\n    <div>";
+  let expected_html = "  <div>\n<pre><code>&lt;div&gt;\n</code></pre>";
+  assert_eq!(parse(example_string).as_html(), expected_html);
+}
+
+/// The inner tags are indented, and so are treated as indented code blocks, which means they are escaped:
+#[test]
+fn example_191() {
+  let example_string = "<table>\n\n  <tr>\n\n    <td>\n      Hi\n    </td>\n\n  </tr>\n\n</table>";
+  let expected_html =
+    "<table>\n  <tr>\n<pre><code>&lt;td&gt;\n  Hi\n&lt;/td&gt;\n</code></pre>\n  </tr>\n</table>";
+  assert_eq!(parse(example_string).as_html(), expected_html);
+}
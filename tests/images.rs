@@ -0,0 +1,58 @@
+use smarkdown::parse;
+
+/// Syntax for images is like the syntax for links, with one difference. Instead of link text, we have an image description. The rules for this are the same as for link text, except that (a) an image description starts with `![` rather than `[`, and (b) an image description may contain links. An image description has inline elements as its contents. When an image is rendered to HTML, this is used as the image's `alt` attribute.
+#[test]
+fn example_572() {
+  let example_string = "![foo](/url \"title\")";
+  let expected_html = "<p><img src=\"/url\" alt=\"foo\" title=\"title\" /></p>";
+  assert_eq!(parse(example_string).as_html(), expected_html);
+}
+
+/// Though this spec is concerned with parsing, not rendering, it is recommended that in rendering to HTML, only the plain string content of the image description be used.
+#[test]
+fn example_574() {
+  let example_string = "![foo ![bar](/url)](/url2)";
+  let expected_html = "<p><img src=\"/url2\" alt=\"foo bar\" /></p>";
+  assert_eq!(parse(example_string).as_html(), expected_html);
+}
+
+/// Though this spec is concerned with parsing, not rendering, it is recommended that in rendering to HTML, only the plain string content of the image description be used.
+#[test]
+fn example_575() {
+  let example_string = "![foo [bar](/url)](/url2)";
+  let expected_html = "<p><img src=\"/url2\" alt=\"foo bar\" /></p>";
+  assert_eq!(parse(example_string).as_html(), expected_html);
+}
+
+/// Images with a plain destination:
+#[test]
+fn example_578() {
+  let example_string = "![foo](train.jpg)";
+  let expected_html = "<p><img src=\"train.jpg\" alt=\"foo\" /></p>";
+  assert_eq!(parse(example_string).as_html(), expected_html);
+}
+
+/// Spaces around the destination and title are allowed:
+#[test]
+fn example_579() {
+  let example_string = "My ![foo bar](/path/to/train.jpg  \"title\"   )";
+  let expected_html =
+    "<p>My <img src=\"/path/to/train.jpg\" alt=\"foo bar\" title=\"title\" /></p>";
+  assert_eq!(parse(example_string).as_html(), expected_html);
+}
+
+/// The destination may be enclosed in pointy brackets:
+#[test]
+fn example_580() {
+  let example_string = "![foo](<url>)";
+  let expected_html = "<p><img src=\"url\" alt=\"foo\" /></p>";
+  assert_eq!(parse(example_string).as_html(), expected_html);
+}
+
+/// The image description may be empty:
+#[test]
+fn example_581() {
+  let example_string = "![](/url)";
+  let expected_html = "<p><img src=\"/url\" alt=\"\" /></p>";
+  assert_eq!(parse(example_string).as_html(), expected_html);
+}
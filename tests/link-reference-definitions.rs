@@ -0,0 +1,209 @@
+use smarkdown::parse;
+
+/// A link reference definition consists of a link label, optionally preceded by up to three spaces of indentation, followed by a colon (`:`), optional spaces or tabs (including up to one line ending), a link destination, optional spaces or tabs (including up to one line ending), and an optional link title:
+#[test]
+fn example_192() {
+  let example_string = "[foo]: /url \"title\"\n\n[foo]";
+  let expected_html = "<p><a href=\"/url\" title=\"title\">foo</a></p>";
+  assert_eq!(parse(example_string).as_html(), expected_html);
+}
+
+/// A link reference definition consists of a link label, optionally preceded by up to three spaces of indentation, followed by a colon (`:`), optional spaces or tabs (including up to one line ending), a link destination, optional spaces or tabs (including up to one line ending), and an optional link title:
+#[test]
+fn example_193() {
+  let example_string = "   [foo]: \n      /url  \n           'the title'  \n\n[foo]";
+  let expected_html = "<p><a href=\"/url\" title=\"the title\">foo</a></p>";
+  assert_eq!(parse(example_string).as_html(), expected_html);
+}
+
+/// A link reference definition consists of a link label, optionally preceded by up to three spaces of indentation, followed by a colon (`:`), optional spaces or tabs (including up to one line ending), a link destination, optional spaces or tabs (including up to one line ending), and an optional link title:
+#[test]
+fn example_194() {
+  let example_string = "[Foo*bar\\]]:my_(url) 'title (with parens)'\n\n[Foo*bar\\]]";
+  let expected_html = "<p><a href=\"my_(url)\" title=\"title (with parens)\">Foo*bar]</a></p>";
+  assert_eq!(parse(example_string).as_html(), expected_html);
+}
+
+/// The title may extend over multiple lines:
+#[test]
+fn example_196() {
+  let example_string = "[foo]: /url '\ntitle\nline1\nline2\n'\n\n[foo]";
+  let expected_html = "<p><a href=\"/url\" title=\"\ntitle\nline1\nline2\n\">foo</a></p>";
+  assert_eq!(parse(example_string).as_html(), expected_html);
+}
+
+/// However, it may not contain a blank line:
+#[test]
+fn example_197() {
+  let example_string = "[foo]: /url 'title\n\nwith blank line'\n\n[foo]";
+  let expected_html = "<p>[foo]: /url 'title</p>\n<p>with blank line'</p>\n<p>[foo]</p>";
+  assert_eq!(parse(example_string).as_html(), expected_html);
+}
+
+/// The title may be omitted:
+#[test]
+fn example_198() {
+  let example_string = "[foo]:\n/url\n\n[foo]";
+  let expected_html = "<p><a href=\"/url\">foo</a></p>";
+  assert_eq!(parse(example_string).as_html(), expected_html);
+}
+
+/// The link destination may not be omitted:
+#[test]
+fn example_199() {
+  let example_string = "[foo]:\n\n[foo]";
+  let expected_html = "<p>[foo]:</p>\n<p>[foo]</p>";
+  assert_eq!(parse(example_string).as_html(), expected_html);
+}
+
+/// However, an empty link destination may be specified using angle brackets:
+#[test]
+fn example_200() {
+  let example_string = "[foo]: <>\n\n[foo]";
+  let expected_html = "<p><a href=\"\">foo</a></p>";
+  assert_eq!(parse(example_string).as_html(), expected_html);
+}
+
+/// The title must be separated from the link destination by spaces or tabs:
+#[test]
+fn example_201() {
+  let example_string = "[foo]: <bar>(baz)\n\n[foo]";
+  let expected_html = "<p>[foo]: <bar>(baz)</p>\n<p>[foo]</p>";
+  assert_eq!(parse(example_string).as_html(), expected_html);
+}
+
+/// A link can come before its corresponding definition:
+#[test]
+fn example_203() {
+  let example_string = "[foo]\n\n[foo]: url";
+  let expected_html = "<p><a href=\"url\">foo</a></p>";
+  assert_eq!(parse(example_string).as_html(), expected_html);
+}
+
+/// If there are several matching definitions, the first one takes precedence:
+#[test]
+fn example_204() {
+  let example_string = "[foo]\n\n[foo]: first\n[foo]: second";
+  let expected_html = "<p><a href=\"first\">foo</a></p>";
+  assert_eq!(parse(example_string).as_html(), expected_html);
+}
+
+/// As noted in the section on Links, matching of labels is case-insensitive:
+#[test]
+fn example_205() {
+  let example_string = "[FOO]: /url\n\n[Foo]";
+  let expected_html = "<p><a href=\"/url\">Foo</a></p>";
+  assert_eq!(parse(example_string).as_html(), expected_html);
+}
+
+/// Whether something is a link reference definition is independent of whether the link reference it defines is used in the document. Thus, for example, the following document contains just a link reference definition, and no visible content:
+#[test]
+fn example_207() {
+  let example_string = "[foo]: /url";
+  let expected_html = "";
+  assert_eq!(parse(example_string).as_html(), expected_html);
+}
+
+/// Here is another one:
+#[test]
+fn example_208() {
+  let example_string = "[\nfoo\n]: /url\nbar";
+  let expected_html = "<p>bar</p>";
+  assert_eq!(parse(example_string).as_html(), expected_html);
+}
+
+/// This is not a link reference definition, because there are characters other than spaces or tabs after the title:
+#[test]
+fn example_209() {
+  let example_string = "[foo]: /url \"title\" ok";
+  let expected_html = "<p>[foo]: /url &quot;title&quot; ok</p>";
+  assert_eq!(parse(example_string).as_html(), expected_html);
+}
+
+/// This is a link reference definition, but it has no title:
+#[test]
+fn example_210() {
+  let example_string = "[foo]: /url\n\"title\" ok";
+  let expected_html = "<p>&quot;title&quot; ok</p>";
+  assert_eq!(parse(example_string).as_html(), expected_html);
+}
+
+/// This is not a link reference definition, because it is indented four spaces:
+#[test]
+fn example_211() {
+  let example_string = "    [foo]: /url \"title\"\n\n[foo]";
+  let expected_html = "<pre><code>[foo]: /url &quot;title&quot;\n</code></pre>\n<p>[foo]</p>";
+  assert_eq!(parse(example_string).as_html(), expected_html);
+}
+
+/// A link reference definition cannot interrupt a paragraph.
+#[test]
+fn example_213() {
+  let example_string = "Foo\n[bar]: /baz\n\n[bar]";
+  let expected_html = "<p>Foo\n[bar]: /baz</p>\n<p>[bar]</p>";
+  assert_eq!(parse(example_string).as_html(), expected_html);
+}
+
+/// However, it can directly follow other block elements, such as headings and thematic breaks, and it need not be followed by a blank line.
+#[test]
+fn example_214() {
+  let example_string = "# [Foo]\n[foo]: /url\n> bar";
+  let expected_html = "<h1><a href=\"/url\">Foo</a></h1>\n<blockquote>\n<p>bar</p>\n</blockquote>";
+  assert_eq!(parse(example_string).as_html(), expected_html);
+}
+
+/// However, it can directly follow other block elements, such as headings and thematic breaks, and it need not be followed by a blank line.
+#[test]
+fn example_215() {
+  let example_string = "[foo]: /url\nbar\n===\n[foo]";
+  let expected_html = "<h1>bar</h1>\n<p><a href=\"/url\">foo</a></p>";
+  assert_eq!(parse(example_string).as_html(), expected_html);
+}
+
+/// Several link reference definitions can occur one after another, without intervening blank lines.
+#[test]
+fn example_217() {
+  let example_string =
+    "[foo]: /foo-url \"foo\"\n[bar]: /bar-url\n  \"bar\"\n[baz]: /baz-url\n\n[foo],\n[bar],\n[baz]";
+  let expected_html = "<p><a href=\"/foo-url\" title=\"foo\">foo</a>,\n<a href=\"/bar-url\" title=\"bar\">bar</a>,\n<a href=\"/baz-url\">baz</a></p>";
+  assert_eq!(parse(example_string).as_html(), expected_html);
+}
+
+/// Link reference definitions can occur inside block containers, like lists and block quotations. They affect the entire document, not just the container in which they are defined:
+#[test]
+fn example_218() {
+  let example_string = "[foo]\n\n> [foo]: /url";
+  let expected_html = "<p><a href=\"/url\">foo</a></p>\n<blockquote>\n</blockquote>";
+  assert_eq!(parse(example_string).as_html(), expected_html);
+}
+
+/// Definitions inside list items can be used by links anywhere in the document
+#[test]
+fn definition_in_list_item() {
+  let example_string = "[foo] and [bar]\n\n- [foo]: /foo-url\n\n> [bar]: /bar-url";
+  let expected_html = "<p><a href=\"/foo-url\">foo</a> and <a href=\"/bar-url\">bar</a></p>\n<ul>\n<li></li>\n</ul>\n<blockquote>\n</blockquote>";
+  assert_eq!(parse(example_string).as_html(), expected_html);
+}
+/// The title may extend over multiple lines and the destination may be enclosed in pointy brackets:
+#[test]
+fn example_195() {
+  let example_string = "[Foo bar]:\n<my url>\n'title'\n\n[Foo bar]";
+  let expected_html = "<p><a href=\"my%20url\" title=\"title\">Foo bar</a></p>";
+  assert_eq!(parse(example_string).as_html(), expected_html);
+}
+
+/// The title must be separated from the link destination by spaces or tabs:
+#[test]
+fn example_202() {
+  let example_string = "[foo]: /url\\bar\\*baz \"foo\\\"bar\\baz\"\n\n[foo]";
+  let expected_html = "<p><a href=\"/url%5Cbar*baz\" title=\"foo&quot;bar\\baz\">foo</a></p>";
+  assert_eq!(parse(example_string).as_html(), expected_html);
+}
+
+/// Matching of labels is case-insensitive (see matches):
+#[test]
+fn example_206() {
+  let example_string = "[ΑΓΩ]: /φου\n\n[αγω]";
+  let expected_html = "<p><a href=\"/%CF%86%CE%BF%CF%85\">αγω</a></p>";
+  assert_eq!(parse(example_string).as_html(), expected_html);
+}
@@ -0,0 +1,561 @@
+use smarkdown::parse;
+
+/// Here is a simple inline link:
+#[test]
+fn example_482() {
+  let example_string = "[link](/uri \"title\")";
+  let expected_html = "<p><a href=\"/uri\" title=\"title\">link</a></p>";
+  assert_eq!(parse(example_string).as_html(), expected_html);
+}
+
+/// The title, the link text and even the destination may be omitted:
+#[test]
+fn example_483() {
+  let example_string = "[link](/uri)";
+  let expected_html = "<p><a href=\"/uri\">link</a></p>";
+  assert_eq!(parse(example_string).as_html(), expected_html);
+}
+
+/// The title, the link text and even the destination may be omitted:
+#[test]
+fn example_484() {
+  let example_string = "[](./target.md)";
+  let expected_html = "<p><a href=\"./target.md\"></a></p>";
+  assert_eq!(parse(example_string).as_html(), expected_html);
+}
+
+/// The title, the link text and even the destination may be omitted:
+#[test]
+fn example_485() {
+  let example_string = "[link]()";
+  let expected_html = "<p><a href=\"\">link</a></p>";
+  assert_eq!(parse(example_string).as_html(), expected_html);
+}
+
+/// The title, the link text and even the destination may be omitted:
+#[test]
+fn example_486() {
+  let example_string = "[link](<>)";
+  let expected_html = "<p><a href=\"\">link</a></p>";
+  assert_eq!(parse(example_string).as_html(), expected_html);
+}
+
+/// The title, the link text and even the destination may be omitted:
+#[test]
+fn example_487() {
+  let example_string = "[]()";
+  let expected_html = "<p><a href=\"\"></a></p>";
+  assert_eq!(parse(example_string).as_html(), expected_html);
+}
+
+/// The destination can only contain spaces if it is enclosed in pointy brackets:
+#[test]
+fn example_488() {
+  let example_string = "[link](/my uri)";
+  let expected_html = "<p>[link](/my uri)</p>";
+  assert_eq!(parse(example_string).as_html(), expected_html);
+}
+
+/// The destination cannot contain line endings, even if enclosed in pointy brackets:
+#[test]
+fn example_490() {
+  let example_string = "[link](foo\nbar)";
+  let expected_html = "<p>[link](foo\nbar)</p>";
+  assert_eq!(parse(example_string).as_html(), expected_html);
+}
+
+/// The destination cannot contain line endings, even if enclosed in pointy brackets:
+#[test]
+fn example_491() {
+  let example_string = "[link](<foo\nbar>)";
+  let expected_html = "<p>[link](<foo\nbar>)</p>";
+  assert_eq!(parse(example_string).as_html(), expected_html);
+}
+
+/// The destination can contain `)` if it is enclosed in pointy brackets:
+#[test]
+fn example_492() {
+  let example_string = "[a](<b)c>)";
+  let expected_html = "<p><a href=\"b)c\">a</a></p>";
+  assert_eq!(parse(example_string).as_html(), expected_html);
+}
+
+/// Parentheses inside the link destination may be escaped:
+#[test]
+fn example_495() {
+  let example_string = "[link](\\(foo\\))";
+  let expected_html = "<p><a href=\"(foo)\">link</a></p>";
+  assert_eq!(parse(example_string).as_html(), expected_html);
+}
+
+/// Any number of parentheses are allowed without escaping, as long as they are balanced:
+#[test]
+fn example_496() {
+  let example_string = "[link](foo(and(bar)))";
+  let expected_html = "<p><a href=\"foo(and(bar))\">link</a></p>";
+  assert_eq!(parse(example_string).as_html(), expected_html);
+}
+
+/// However, if you have unbalanced parentheses, you need to escape or use the `<...>` form:
+#[test]
+fn example_497() {
+  let example_string = "[link](foo(and(bar))";
+  let expected_html = "<p>[link](foo(and(bar))</p>";
+  assert_eq!(parse(example_string).as_html(), expected_html);
+}
+
+/// However, if you have unbalanced parentheses, you need to escape or use the `<...>` form:
+#[test]
+fn example_498() {
+  let example_string = "[link](foo\\(and\\(bar\\))";
+  let expected_html = "<p><a href=\"foo(and(bar)\">link</a></p>";
+  assert_eq!(parse(example_string).as_html(), expected_html);
+}
+
+/// However, if you have unbalanced parentheses, you need to escape or use the `<...>` form:
+#[test]
+fn example_499() {
+  let example_string = "[link](<foo(and(bar)>)";
+  let expected_html = "<p><a href=\"foo(and(bar)\">link</a></p>";
+  assert_eq!(parse(example_string).as_html(), expected_html);
+}
+
+/// Parentheses and other symbols can also be escaped, as usual in Markdown:
+#[test]
+fn example_500() {
+  let example_string = "[link](foo\\)\\:)";
+  let expected_html = "<p><a href=\"foo):\">link</a></p>";
+  assert_eq!(parse(example_string).as_html(), expected_html);
+}
+
+/// Titles must be delimited by the same characters, unless their nested quotes are escaped:
+#[test]
+fn example_508() {
+  let example_string = "[link](/url \"title \"and\" title\")";
+  let expected_html = "<p>[link](/url &quot;title &quot;and&quot; title&quot;)</p>";
+  assert_eq!(parse(example_string).as_html(), expected_html);
+}
+
+/// Spaces, tabs, and up to one line ending is allowed around the destination and title:
+#[test]
+fn example_510() {
+  let example_string = "[link](   /uri\n  \"title\"  )";
+  let expected_html = "<p><a href=\"/uri\" title=\"title\">link</a></p>";
+  assert_eq!(parse(example_string).as_html(), expected_html);
+}
+
+/// But it is not allowed between the link text and the following parenthesis:
+#[test]
+fn example_511() {
+  let example_string = "[link] (/uri)";
+  let expected_html = "<p>[link] (/uri)</p>";
+  assert_eq!(parse(example_string).as_html(), expected_html);
+}
+
+/// The link text may contain balanced brackets, but not unbalanced ones, unless they are escaped:
+#[test]
+fn example_512() {
+  let example_string = "[link [foo [bar]]](/uri)";
+  let expected_html = "<p><a href=\"/uri\">link [foo [bar]]</a></p>";
+  assert_eq!(parse(example_string).as_html(), expected_html);
+}
+
+/// The link text may contain balanced brackets, but not unbalanced ones, unless they are escaped:
+#[test]
+fn example_513() {
+  let example_string = "[link] bar](/uri)";
+  let expected_html = "<p>[link] bar](/uri)</p>";
+  assert_eq!(parse(example_string).as_html(), expected_html);
+}
+
+/// The link text may contain balanced brackets, but not unbalanced ones, unless they are escaped:
+#[test]
+fn example_514() {
+  let example_string = "[link [bar](/uri)";
+  let expected_html = "<p>[link <a href=\"/uri\">bar</a></p>";
+  assert_eq!(parse(example_string).as_html(), expected_html);
+}
+
+/// The link text may contain balanced brackets, but not unbalanced ones, unless they are escaped:
+#[test]
+fn example_515() {
+  let example_string = "[link \\[bar](/uri)";
+  let expected_html = "<p><a href=\"/uri\">link [bar</a></p>";
+  assert_eq!(parse(example_string).as_html(), expected_html);
+}
+
+/// The link text may contain inline content:
+#[test]
+fn example_516() {
+  let example_string = "[link *foo **bar** `#`*](/uri)";
+  let expected_html =
+    "<p><a href=\"/uri\">link <em>foo <strong>bar</strong> <code>#</code></em></a></p>";
+  assert_eq!(parse(example_string).as_html(), expected_html);
+}
+
+/// The link text may contain inline content:
+#[test]
+fn example_517() {
+  let example_string = "[![moon](moon.jpg)](/uri)";
+  let expected_html = "<p><a href=\"/uri\"><img src=\"moon.jpg\" alt=\"moon\" /></a></p>";
+  assert_eq!(parse(example_string).as_html(), expected_html);
+}
+
+/// However, links may not contain other links, at any level of nesting.
+#[test]
+fn example_518() {
+  let example_string = "[foo [bar](/uri)](/uri)";
+  let expected_html = "<p>[foo <a href=\"/uri\">bar</a>](/uri)</p>";
+  assert_eq!(parse(example_string).as_html(), expected_html);
+}
+
+/// However, links may not contain other links, at any level of nesting.
+#[test]
+fn example_519() {
+  let example_string = "[foo *[bar [baz](/uri)](/uri)*](/uri)";
+  let expected_html = "<p>[foo <em>[bar <a href=\"/uri\">baz</a>](/uri)</em>](/uri)</p>";
+  assert_eq!(parse(example_string).as_html(), expected_html);
+}
+
+/// However, links may not contain other links, at any level of nesting.
+#[test]
+fn example_520() {
+  let example_string = "![[[foo](uri1)](uri2)](uri3)";
+  let expected_html = "<p><img src=\"uri3\" alt=\"[foo](uri2)\" /></p>";
+  assert_eq!(parse(example_string).as_html(), expected_html);
+}
+
+/// These cases illustrate the precedence of link text grouping over emphasis grouping:
+#[test]
+fn example_521() {
+  let example_string = "*[foo*](/uri)";
+  let expected_html = "<p>*<a href=\"/uri\">foo*</a></p>";
+  assert_eq!(parse(example_string).as_html(), expected_html);
+}
+
+/// These cases illustrate the precedence of link text grouping over emphasis grouping:
+#[test]
+fn example_522() {
+  let example_string = "[foo *bar](baz*)";
+  let expected_html = "<p><a href=\"baz*\">foo *bar</a></p>";
+  assert_eq!(parse(example_string).as_html(), expected_html);
+}
+
+/// Note that brackets that *aren't* part of links do not take precedence:
+#[test]
+fn example_523() {
+  let example_string = "*foo [bar* baz]";
+  let expected_html = "<p><em>foo [bar</em> baz]</p>";
+  assert_eq!(parse(example_string).as_html(), expected_html);
+}
+
+/// These cases illustrate the precedence of HTML tags, code spans, and autolinks over link grouping:
+#[test]
+fn example_525() {
+  let example_string = "[foo`](/uri)`";
+  let expected_html = "<p>[foo<code>](/uri)</code></p>";
+  assert_eq!(parse(example_string).as_html(), expected_html);
+}
+/// Here is a simple example of a full reference link:
+#[test]
+fn example_527() {
+  let example_string = "[foo][bar]\n\n[bar]: /url \"title\"";
+  let expected_html = "<p><a href=\"/url\" title=\"title\">foo</a></p>";
+  assert_eq!(parse(example_string).as_html(), expected_html);
+}
+
+/// The link text may contain balanced brackets, but not unbalanced ones, unless they are escaped:
+#[test]
+fn example_528() {
+  let example_string = "[link [foo [bar]]][ref]\n\n[ref]: /uri";
+  let expected_html = "<p><a href=\"/uri\">link [foo [bar]]</a></p>";
+  assert_eq!(parse(example_string).as_html(), expected_html);
+}
+
+/// The link text may contain balanced brackets, but not unbalanced ones, unless they are escaped:
+#[test]
+fn example_529() {
+  let example_string = "[link \\[bar][ref]\n\n[ref]: /uri";
+  let expected_html = "<p><a href=\"/uri\">link [bar</a></p>";
+  assert_eq!(parse(example_string).as_html(), expected_html);
+}
+
+/// The link text may contain inline content:
+#[test]
+fn example_530() {
+  let example_string = "[link *foo **bar** `#`*][ref]\n\n[ref]: /uri";
+  let expected_html =
+    "<p><a href=\"/uri\">link <em>foo <strong>bar</strong> <code>#</code></em></a></p>";
+  assert_eq!(parse(example_string).as_html(), expected_html);
+}
+
+/// The link text may contain inline content:
+#[test]
+fn example_531() {
+  let example_string = "[![moon](moon.jpg)][ref]\n\n[ref]: /uri";
+  let expected_html = "<p><a href=\"/uri\"><img src=\"moon.jpg\" alt=\"moon\" /></a></p>";
+  assert_eq!(parse(example_string).as_html(), expected_html);
+}
+
+/// However, links may not contain other links, at any level of nesting.
+#[test]
+fn example_532() {
+  let example_string = "[foo [bar](/uri)][ref]\n\n[ref]: /uri";
+  let expected_html = "<p>[foo <a href=\"/uri\">bar</a>]<a href=\"/uri\">ref</a></p>";
+  assert_eq!(parse(example_string).as_html(), expected_html);
+}
+
+/// However, links may not contain other links, at any level of nesting.
+#[test]
+fn example_533() {
+  let example_string = "[foo *bar [baz][ref]*][ref]\n\n[ref]: /uri";
+  let expected_html = "<p>[foo <em>bar <a href=\"/uri\">baz</a></em>]<a href=\"/uri\">ref</a></p>";
+  assert_eq!(parse(example_string).as_html(), expected_html);
+}
+
+/// The following cases illustrate the precedence of link text grouping over emphasis grouping:
+#[test]
+fn example_534() {
+  let example_string = "*[foo*][ref]\n\n[ref]: /uri";
+  let expected_html = "<p>*<a href=\"/uri\">foo*</a></p>";
+  assert_eq!(parse(example_string).as_html(), expected_html);
+}
+
+/// The following cases illustrate the precedence of link text grouping over emphasis grouping:
+#[test]
+fn example_535() {
+  let example_string = "[foo *bar][ref]*\n\n[ref]: /uri";
+  let expected_html = "<p><a href=\"/uri\">foo *bar</a>*</p>";
+  assert_eq!(parse(example_string).as_html(), expected_html);
+}
+
+/// These cases illustrate the precedence of HTML tags, code spans, and autolinks over link grouping:
+#[test]
+fn example_537() {
+  let example_string = "[foo`][ref]`\n\n[ref]: /uri";
+  let expected_html = "<p>[foo<code>][ref]</code></p>";
+  assert_eq!(parse(example_string).as_html(), expected_html);
+}
+
+/// Matching is case-insensitive:
+#[test]
+fn example_539() {
+  let example_string = "[foo][BaR]\n\n[bar]: /url \"title\"";
+  let expected_html = "<p><a href=\"/url\" title=\"title\">foo</a></p>";
+  assert_eq!(parse(example_string).as_html(), expected_html);
+}
+
+/// Unicode case fold is used:
+#[test]
+fn example_540() {
+  let example_string = "[ẞ]\n\n[SS]: /url";
+  let expected_html = "<p><a href=\"/url\">ẞ</a></p>";
+  assert_eq!(parse(example_string).as_html(), expected_html);
+}
+
+/// Consecutive internal spaces, tabs, and line endings are treated as one space for purposes of determining matching:
+#[test]
+fn example_541() {
+  let example_string = "[Foo\n  bar]: /url\n\n[Baz][Foo bar]";
+  let expected_html = "<p><a href=\"/url\">Baz</a></p>";
+  assert_eq!(parse(example_string).as_html(), expected_html);
+}
+
+/// No spaces, tabs, or line endings are allowed between the link text and the link label:
+#[test]
+fn example_542() {
+  let example_string = "[foo] [bar]\n\n[bar]: /url \"title\"";
+  let expected_html = "<p>[foo] <a href=\"/url\" title=\"title\">bar</a></p>";
+  assert_eq!(parse(example_string).as_html(), expected_html);
+}
+
+/// No spaces, tabs, or line endings are allowed between the link text and the link label:
+#[test]
+fn example_543() {
+  let example_string = "[foo]\n[bar]\n\n[bar]: /url \"title\"";
+  let expected_html = "<p>[foo]\n<a href=\"/url\" title=\"title\">bar</a></p>";
+  assert_eq!(parse(example_string).as_html(), expected_html);
+}
+
+/// When there are multiple matching link reference definitions, the first is used:
+#[test]
+fn example_544() {
+  let example_string = "[foo]: /url1\n\n[foo]: /url2\n\n[bar][foo]";
+  let expected_html = "<p><a href=\"/url1\">bar</a></p>";
+  assert_eq!(parse(example_string).as_html(), expected_html);
+}
+
+/// Note that matching is performed on normalized strings, not parsed inline content. So the following does not match, even though the labels define equivalent inline content:
+#[test]
+fn example_545() {
+  let example_string = "[bar][foo\\!]\n\n[foo!]: /url";
+  let expected_html = "<p>[bar][foo!]</p>";
+  assert_eq!(parse(example_string).as_html(), expected_html);
+}
+
+/// Brackets are not allowed in link labels, unless they are backslash-escaped:
+#[test]
+fn example_549() {
+  let example_string = "[foo][ref\\[]\n\n[ref\\[]: /uri";
+  let expected_html = "<p><a href=\"/uri\">foo</a></p>";
+  assert_eq!(parse(example_string).as_html(), expected_html);
+}
+
+/// Brackets are not allowed in link labels, unless they are backslash-escaped:
+#[test]
+fn example_550() {
+  let example_string = "[bar\\\\]: /uri\n\n[bar\\\\]";
+  let expected_html = "<p><a href=\"/uri\">bar\\</a></p>";
+  assert_eq!(parse(example_string).as_html(), expected_html);
+}
+
+/// Here is a simple example of a collapsed reference link:
+#[test]
+fn example_553() {
+  let example_string = "[foo][]\n\n[foo]: /url \"title\"";
+  let expected_html = "<p><a href=\"/url\" title=\"title\">foo</a></p>";
+  assert_eq!(parse(example_string).as_html(), expected_html);
+}
+
+/// Here is a simple example of a collapsed reference link:
+#[test]
+fn example_554() {
+  let example_string = "[*foo* bar][]\n\n[*foo* bar]: /url \"title\"";
+  let expected_html = "<p><a href=\"/url\" title=\"title\"><em>foo</em> bar</a></p>";
+  assert_eq!(parse(example_string).as_html(), expected_html);
+}
+
+/// The link labels are case-insensitive:
+#[test]
+fn example_555() {
+  let example_string = "[Foo][]\n\n[foo]: /url \"title\"";
+  let expected_html = "<p><a href=\"/url\" title=\"title\">Foo</a></p>";
+  assert_eq!(parse(example_string).as_html(), expected_html);
+}
+
+/// Here is a simple example of a shortcut reference link:
+#[test]
+fn example_557() {
+  let example_string = "[foo]\n\n[foo]: /url \"title\"";
+  let expected_html = "<p><a href=\"/url\" title=\"title\">foo</a></p>";
+  assert_eq!(parse(example_string).as_html(), expected_html);
+}
+
+/// Here is a simple example of a shortcut reference link:
+#[test]
+fn example_558() {
+  let example_string = "[*foo* bar]\n\n[*foo* bar]: /url \"title\"";
+  let expected_html = "<p><a href=\"/url\" title=\"title\"><em>foo</em> bar</a></p>";
+  assert_eq!(parse(example_string).as_html(), expected_html);
+}
+
+/// Full and collapsed references take precedence over shortcut references:
+#[test]
+fn example_565() {
+  let example_string = "[foo][bar]\n\n[foo]: /url1\n[bar]: /url2";
+  let expected_html = "<p><a href=\"/url2\">foo</a></p>";
+  assert_eq!(parse(example_string).as_html(), expected_html);
+}
+
+/// Inline links also take precedence:
+#[test]
+fn example_568() {
+  let example_string = "[foo](not a link)\n\n[foo]: /url1";
+  let expected_html = "<p><a href=\"/url1\">foo</a>(not a link)</p>";
+  assert_eq!(parse(example_string).as_html(), expected_html);
+}
+
+/// In the following case `[bar][baz]` is parsed as a reference, `[foo]` as normal text:
+#[test]
+fn example_569() {
+  let example_string = "[foo][bar][baz]\n\n[baz]: /url";
+  let expected_html = "<p>[foo]<a href=\"/url\">bar</a></p>";
+  assert_eq!(parse(example_string).as_html(), expected_html);
+}
+
+/// Here, though, `[foo][bar]` is parsed as a reference, since `[bar]` is defined:
+#[test]
+fn example_570() {
+  let example_string = "[foo][bar][baz]\n\n[baz]: /url1\n[bar]: /url2";
+  let expected_html = "<p><a href=\"/url2\">foo</a><a href=\"/url1\">baz</a></p>";
+  assert_eq!(parse(example_string).as_html(), expected_html);
+}
+
+/// Here `[foo]` is not parsed as a reference, since it is followed by a link label (even though `[bar]` is not defined):
+#[test]
+fn example_571() {
+  let example_string = "[foo][bar][baz]\n\n[baz]: /url1\n[foo]: /url2";
+  let expected_html = "<p>[foo]<a href=\"/url1\">bar</a></p>";
+  assert_eq!(parse(example_string).as_html(), expected_html);
+}
+/// The destination can contain spaces if it is enclosed in pointy brackets:
+#[test]
+fn example_489() {
+  let example_string = "[link](</my uri>)";
+  let expected_html = "<p><a href=\"/my%20uri\">link</a></p>";
+  assert_eq!(parse(example_string).as_html(), expected_html);
+}
+
+/// The destination can contain `>` if it is escaped, but pointy brackets must be matched:
+#[test]
+fn example_493() {
+  let example_string = "[link](<foo\\>)";
+  let expected_html = "<p>[link](&lt;foo&gt;)</p>";
+  assert_eq!(parse(example_string).as_html(), expected_html);
+}
+
+/// These are not links, because the opening pointy bracket is not matched properly:
+#[test]
+fn example_494() {
+  let example_string = "[a](<b)c\n[a](<b)c>\n[a](<b>c)";
+  let expected_html = "<p>[a](&lt;b)c\n[a](&lt;b)c&gt;\n[a](<b>c)</p>";
+  assert_eq!(parse(example_string).as_html(), expected_html);
+}
+
+/// A link can contain fragment identifiers and queries, but a backslash before a non-punctuation character is left alone:
+#[test]
+fn example_502() {
+  let example_string = "[link](foo\\bar)";
+  let expected_html = "<p><a href=\"foo%5Cbar\">link</a></p>";
+  assert_eq!(parse(example_string).as_html(), expected_html);
+}
+
+/// URL-escaping should be left alone inside the destination, as all URL-escaped characters are also valid URL characters. Entity and numeric character references in the destination will be parsed into the corresponding Unicode code points, as usual.
+#[test]
+fn example_503() {
+  let example_string = "[link](foo%20b&auml;)";
+  let expected_html = "<p><a href=\"foo%20b%C3%A4\">link</a></p>";
+  assert_eq!(parse(example_string).as_html(), expected_html);
+}
+
+/// Note that, because titles can often be parsed as destinations, if you try to omit the destination and keep the title, you’ll get unexpected results:
+#[test]
+fn example_504() {
+  let example_string = "[link](\"title\")";
+  let expected_html = "<p><a href=\"%22title%22\">link</a></p>";
+  assert_eq!(parse(example_string).as_html(), expected_html);
+}
+
+/// Spaces, tabs, and up to one line ending other than a non-breaking space (U+00A0) can be used to separate the destination from the title:
+#[test]
+fn example_507() {
+  let example_string = "[link](/url \"title\")";
+  let expected_html = "<p><a href=\"/url%C2%A0%22title%22\">link</a></p>";
+  assert_eq!(parse(example_string).as_html(), expected_html);
+}
+
+/// These cases illustrate the precedence of HTML tags, code spans, and autolinks over link grouping:
+#[test]
+fn example_526() {
+  let example_string = "[foo<https://example.com/?search=](uri)>";
+  let expected_html = "<p>[foo<a href=\"https://example.com/?search=%5D(uri)\">https://example.com/?search=](uri)</a></p>";
+  assert_eq!(parse(example_string).as_html(), expected_html);
+}
+
+/// These cases illustrate the precedence of HTML tags, code spans, and autolinks over link grouping:
+#[test]
+fn example_538() {
+  let example_string = "[foo<https://example.com/?search=][ref]>\n\n[ref]: /uri";
+  let expected_html = "<p>[foo<a href=\"https://example.com/?search=%5D%5Bref%5D\">https://example.com/?search=][ref]</a></p>";
+  assert_eq!(parse(example_string).as_html(), expected_html);
+}
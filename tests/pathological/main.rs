@@ -0,0 +1,75 @@
+//! Inputs that take quadratic time or worse in a naive parser. Each has to be parsed and rendered well within the time
+//! a linear parser needs, so that untrusted markdown can't be used to hang the renderer.
+
+use std::time::{Duration, Instant};
+
+use smarkdown::parse;
+
+/// Far longer than any of these take when parsing is linear, even in a debug build
+const LIMIT: Duration = Duration::from_secs(2);
+
+fn assert_linear(markdown: &str) {
+  let start = Instant::now();
+  parse(markdown).as_html();
+  let elapsed = start.elapsed();
+  assert!(
+    elapsed < LIMIT,
+    "took {elapsed:?} for {} bytes starting {:?}",
+    markdown.len(),
+    &markdown[..20]
+  );
+}
+
+#[test]
+fn unmatched_emphasis_closers() {
+  assert_linear(&"*a_ ".repeat(20_000));
+}
+
+#[test]
+fn matched_emphasis() {
+  assert_linear(&"a**b".repeat(20_000));
+}
+
+#[test]
+fn deeply_nested_emphasis() {
+  assert_linear(&("*a ".repeat(20_000) + &" a*".repeat(20_000)));
+  assert_linear(&("*a **a ".repeat(10_000) + "b" + &" a** a*".repeat(10_000)));
+}
+
+#[test]
+fn unclosed_code_spans() {
+  let runs = (1..800).map(|length| "`".repeat(length) + "a");
+  assert_linear(&runs.collect::<String>());
+}
+
+#[test]
+fn unclosed_inline_links() {
+  assert_linear(&"[a](".repeat(10_000));
+  assert_linear(&format!("[a]({}", "(".repeat(50_000)));
+}
+
+#[test]
+fn unmatched_link_closers() {
+  assert_linear(&"[a]".repeat(10_000));
+  assert_linear(&"]".repeat(50_000));
+}
+
+#[test]
+fn unclosed_autolinks() {
+  assert_linear(&"<a".repeat(20_000));
+  assert_linear(&"<a:b".repeat(20_000));
+  assert_linear(&"<a@".repeat(20_000));
+}
+
+#[test]
+fn unclosed_raw_html() {
+  for opener in ["<!--", "<?", "<![CDATA[", "<!A", "<a b='"] {
+    assert_linear(&format!("a {}", opener.repeat(20_000)));
+  }
+}
+
+#[test]
+fn link_reference_definitions() {
+  let definitions = (0..4_000).map(|i| format!("[x{i}]: /u\n"));
+  assert_linear(&definitions.collect::<String>());
+}
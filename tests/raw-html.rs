@@ -0,0 +1,160 @@
+use smarkdown::parse;
+
+/// Here are some simple open tags:
+#[test]
+fn example_613() {
+  let example_string = "<a><bab><c2c>";
+  let expected_html = "<p><a><bab><c2c></p>";
+  assert_eq!(parse(example_string).as_html(), expected_html);
+}
+
+/// Empty elements:
+#[test]
+fn example_614() {
+  let example_string = "<a/><b2/>";
+  let expected_html = "<p><a/><b2/></p>";
+  assert_eq!(parse(example_string).as_html(), expected_html);
+}
+
+/// Whitespace is allowed:
+#[test]
+fn example_615() {
+  let example_string = "<a  /><b2\ndata=\"foo\" >";
+  let expected_html = "<p><a  /><b2\ndata=\"foo\" ></p>";
+  assert_eq!(parse(example_string).as_html(), expected_html);
+}
+
+/// With attributes:
+#[test]
+fn example_616() {
+  let example_string = "<a foo=\"bar\" bam = 'baz <em>\"</em>'\n_boolean zoop:33=zoop:33 />";
+  let expected_html = "<p><a foo=\"bar\" bam = 'baz <em>\"</em>'\n_boolean zoop:33=zoop:33 /></p>";
+  assert_eq!(parse(example_string).as_html(), expected_html);
+}
+
+/// Custom tag names can be used:
+#[test]
+fn example_617() {
+  let example_string = "Foo <responsive-image src=\"foo.jpg\" />";
+  let expected_html = "<p>Foo <responsive-image src=\"foo.jpg\" /></p>";
+  assert_eq!(parse(example_string).as_html(), expected_html);
+}
+
+/// Closing tags:
+#[test]
+fn example_623() {
+  let example_string = "</a></foo >";
+  let expected_html = "<p></a></foo ></p>";
+  assert_eq!(parse(example_string).as_html(), expected_html);
+}
+
+/// Comments:
+#[test]
+fn example_625() {
+  let example_string = "foo <!-- this is a --\ncomment - with hyphens -->";
+  let expected_html = "<p>foo <!-- this is a --\ncomment - with hyphens --></p>";
+  assert_eq!(parse(example_string).as_html(), expected_html);
+}
+
+/// Processing instructions:
+#[test]
+fn example_627() {
+  let example_string = "foo <?php echo $a; ?>";
+  let expected_html = "<p>foo <?php echo $a; ?></p>";
+  assert_eq!(parse(example_string).as_html(), expected_html);
+}
+
+/// Declarations:
+#[test]
+fn example_628() {
+  let example_string = "foo <!ELEMENT br EMPTY>";
+  let expected_html = "<p>foo <!ELEMENT br EMPTY></p>";
+  assert_eq!(parse(example_string).as_html(), expected_html);
+}
+
+/// CDATA sections:
+#[test]
+fn example_629() {
+  let example_string = "foo <![CDATA[>&<]]>";
+  let expected_html = "<p>foo <![CDATA[>&<]]></p>";
+  assert_eq!(parse(example_string).as_html(), expected_html);
+}
+
+/// Entity and numeric character references are preserved in HTML attributes:
+#[test]
+fn example_630() {
+  let example_string = "foo <a href=\"&ouml;\">";
+  let expected_html = "<p>foo <a href=\"&ouml;\"></p>";
+  assert_eq!(parse(example_string).as_html(), expected_html);
+}
+
+/// Backslash escapes do not work in HTML attributes:
+#[test]
+fn example_631() {
+  let example_string = "foo <a href=\"\\*\">";
+  let expected_html = "<p>foo <a href=\"\\*\"></p>";
+  assert_eq!(parse(example_string).as_html(), expected_html);
+}
+/// Illegal tag names, not parsed as HTML:
+#[test]
+fn example_618() {
+  let example_string = "<33> <__>";
+  let expected_html = "<p>&lt;33&gt; &lt;__&gt;</p>";
+  assert_eq!(parse(example_string).as_html(), expected_html);
+}
+
+/// Illegal attribute names:
+#[test]
+fn example_619() {
+  let example_string = "<a h*#ref=\"hi\">";
+  let expected_html = "<p>&lt;a h*#ref=&quot;hi&quot;&gt;</p>";
+  assert_eq!(parse(example_string).as_html(), expected_html);
+}
+
+/// Illegal attribute values:
+#[test]
+fn example_620() {
+  let example_string = "<a href=\"hi'> <a href=hi'>";
+  let expected_html = "<p>&lt;a href=&quot;hi'&gt; &lt;a href=hi'&gt;</p>";
+  assert_eq!(parse(example_string).as_html(), expected_html);
+}
+
+/// Illegal whitespace:
+#[test]
+fn example_621() {
+  let example_string = "< a><\nfoo><bar/ >\n<foo bar=baz\nbim!bop />";
+  let expected_html = "<p>&lt; a&gt;&lt;\nfoo&gt;&lt;bar/ &gt;\n&lt;foo bar=baz\nbim!bop /&gt;</p>";
+  assert_eq!(parse(example_string).as_html(), expected_html);
+}
+
+/// Missing whitespace:
+#[test]
+fn example_622() {
+  let example_string = "<a href='bar'title=title>";
+  let expected_html = "<p>&lt;a href='bar'title=title&gt;</p>";
+  assert_eq!(parse(example_string).as_html(), expected_html);
+}
+
+/// Illegal attributes in closing tag:
+#[test]
+fn example_624() {
+  let example_string = "</a href=\"foo\">";
+  let expected_html = "<p>&lt;/a href=&quot;foo&quot;&gt;</p>";
+  assert_eq!(parse(example_string).as_html(), expected_html);
+}
+
+/// Comments:
+#[test]
+fn example_626() {
+  let example_string = "foo <!--> foo -->\n\nfoo <!---> foo -->";
+  let expected_html = "<p>foo <!--> foo --&gt;</p>\n<p>foo <!---> foo --&gt;</p>";
+  assert_eq!(parse(example_string).as_html(), expected_html);
+}
+
+/// Backslash escapes do not work in HTML attributes:
+#[test]
+fn example_632() {
+  let example_string = "<a href=\"\\\"\">";
+  let expected_html = "<p>&lt;a href=&quot;&quot;&quot;&gt;</p>";
+  assert_eq!(parse(example_string).as_html(), expected_html);
+}
@@ -0,0 +1,149 @@
+//! Renders a corpus of XSS payloads in safe mode, checking that none of them make it into the HTML in a form that runs.
+//!
+//! Rather than comparing against expected HTML, the output is scanned and every tag must be one the renderer writes
+//! itself, with only the attributes it writes. Link and image destinations must not use a dangerous scheme once
+//! their entities have been decoded, the way a browser would read them.
+
+use smarkdown::{parse, HtmlRenderer, RenderOptions};
+
+const PAYLOADS: &str = include_str!("payloads.txt");
+
+/// The tags the HTML renderer writes for markdown itself
+const TAGS: &[&str] = &[
+  "p",
+  "a",
+  "img",
+  "em",
+  "strong",
+  "code",
+  "pre",
+  "ul",
+  "ol",
+  "li",
+  "blockquote",
+  "h1",
+  "h2",
+  "h3",
+  "h4",
+  "h5",
+  "h6",
+  "hr",
+  "br",
+];
+const ATTRIBUTES: &[&str] = &["href", "src", "alt", "title", "start", "class"];
+const OMITTED: &str = "<!-- raw HTML omitted -->";
+
+fn payloads() -> Vec<String> {
+  let mut payloads = vec![String::new()];
+  for line in PAYLOADS.lines().filter(|line| !line.starts_with("//")) {
+    match line {
+      "====" => payloads.push(String::new()),
+      line => {
+        let payload = payloads.last_mut().unwrap();
+        payload.push_str(line);
+        payload.push('\n');
+      }
+    }
+  }
+  payloads
+}
+
+fn render_safe(markdown: &str) -> String {
+  parse(markdown).as_html_with_options(&RenderOptions::default().safe(true))
+}
+
+/// Checks that `html` only contains markup the renderer writes itself, returning what was found if not
+fn check_inert(html: &str) -> Result<(), String> {
+  let mut rest = html;
+  while let Some(start) = rest.find('<') {
+    rest = &rest[start..];
+    if let Some(after) = rest.strip_prefix(OMITTED) {
+      rest = after;
+      continue;
+    }
+    let end = rest
+      .find('>')
+      .ok_or_else(|| format!("unclosed `<` in {rest:?}"))?;
+    check_tag(&rest[1..end])?;
+    rest = &rest[end + 1..];
+  }
+  Ok(())
+}
+
+/// Checks the inside of a tag, between its `<` and `>`
+fn check_tag(tag: &str) -> Result<(), String> {
+  let tag = tag.strip_prefix('/').unwrap_or(tag);
+  let tag = tag.strip_suffix(" /").unwrap_or(tag);
+  let (name, mut attributes) = tag.split_once(' ').unwrap_or((tag, ""));
+  if !TAGS.contains(&name) {
+    return Err(format!("unexpected tag `<{tag}>`"));
+  }
+
+  while !attributes.is_empty() {
+    let (attribute, value) = attributes
+      .split_once("=\"")
+      .ok_or_else(|| format!("malformed attributes in `<{tag}>`"))?;
+    let (value, after) = value
+      .split_once('"')
+      .ok_or_else(|| format!("unclosed attribute in `<{tag}>`"))?;
+    if !ATTRIBUTES.contains(&attribute) {
+      return Err(format!("unexpected attribute `{attribute}` in `<{tag}>`"));
+    }
+    if (attribute == "href" || attribute == "src") && is_dangerous(value) {
+      return Err(format!("dangerous url in `<{tag}>`"));
+    }
+    attributes = after.strip_prefix(' ').unwrap_or(after);
+  }
+  Ok(())
+}
+
+/// Whether a browser would run or load something local from `url`, an attribute value as it appears in the HTML
+fn is_dangerous(url: &str) -> bool {
+  let url: String = url
+    .replace("&amp;", "&")
+    .chars()
+    .filter(|char| !char.is_ascii_whitespace() && !char.is_control())
+    .collect::<String>()
+    .to_ascii_lowercase();
+  if let Some(data) = url.strip_prefix("data:") {
+    let images = ["image/png", "image/gif", "image/jpeg", "image/webp"];
+    return !images.iter().any(|image| data.starts_with(image));
+  }
+  ["javascript:", "vbscript:", "file:"]
+    .iter()
+    .any(|scheme| url.starts_with(scheme))
+}
+
+#[test]
+fn payloads_are_inert() {
+  let payloads = payloads();
+  assert!(
+    payloads.len() > 40,
+    "payloads.txt wasn't split into entries"
+  );
+
+  let failures: Vec<String> = payloads
+    .iter()
+    .filter_map(|payload| {
+      let html = render_safe(payload);
+      check_inert(&html)
+        .err()
+        .map(|error| format!("{payload:?} rendered as {html:?}: {error}"))
+    })
+    .collect();
+  assert!(failures.is_empty(), "{}", failures.join("\n"));
+}
+
+/// Makes sure the check would catch the payloads if safe mode did nothing
+#[test]
+fn payloads_are_caught_without_safe_mode() {
+  let caught = payloads()
+    .iter()
+    .filter(|payload| check_inert(&parse(payload).as_html()).is_err())
+    .count();
+  assert!(caught > 20, "only {caught} payloads were caught");
+
+  let unsafe_html = parse("[x](javascript:alert(1)) <b onclick=alert(1)>")
+    .render_to_string(&mut HtmlRenderer::default());
+  assert!(check_inert(&unsafe_html).is_err());
+}
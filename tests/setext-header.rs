@@ -20,7 +20,7 @@ fn example_81() {
 /// The heading’s raw content is formed by concatenating the lines and removing initial and final spaces or tabs.
 #[test]
 fn example_82() {
-  let example_string = "  Foo *bar\nbaz*→\n====";
+  let example_string = "  Foo *bar\nbaz*\t\n====";
   let expected_html = "<h1>Foo <em>bar\nbaz</em></h1>";
   assert_eq!(parse(example_string).as_html(), expected_html);
 }
@@ -74,7 +74,6 @@ fn example_88() {
 }
 
 /// Trailing spaces or tabs in the content line do not cause a hard line break:
-/// TODO: Fix handling of trimming with takeovers
 #[test]
 fn example_89() {
   let example_string = "Foo  \n-----";
@@ -91,7 +90,6 @@ fn example_90() {
 }
 
 /// Since indicators of block structure take precedence over indicators of inline structure, the following are setext headings:
-/// TODO: Fix handling of html &char syntax
 #[test]
 fn example_91() {
   let example_string = "`Foo\n----\n`\n\n<a title=\"a lot\n---\nof dashes\"/>";
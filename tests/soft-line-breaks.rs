@@ -0,0 +1,17 @@
+use smarkdown::parse;
+
+/// A regular line ending (not in a code span or HTML tag) that is not preceded by two or more spaces or a backslash is parsed as a softbreak.
+#[test]
+fn example_648() {
+  let example_string = "foo\nbaz";
+  let expected_html = "<p>foo\nbaz</p>";
+  assert_eq!(parse(example_string).as_html(), expected_html);
+}
+
+/// Spaces at the end of the line and beginning of the next line are removed:
+#[test]
+fn example_649() {
+  let example_string = "foo \n baz";
+  let expected_html = "<p>foo\nbaz</p>";
+  assert_eq!(parse(example_string).as_html(), expected_html);
+}
@@ -0,0 +1,58 @@
+/// An example taken from the spec, with the markdown it gives and the HTML it should become
+pub struct Example {
+  /// Examples are numbered from 1 in the order they appear, as they are on the spec's website
+  pub number: usize,
+  /// The heading the example appears under
+  pub section: String,
+  pub markdown: String,
+  pub html: String,
+}
+
+/// The line examples start and end with. The start is followed by ` example`, then the name of an extension if it needs one
+const FENCE: &str = "````````````````````````````````";
+
+/// Pulls every example out of the text of `spec.txt`.
+///
+/// Tabs are shown as `→` in the spec so that they can be seen, and are turned back into tabs here.
+/// Examples for extensions aren't part of CommonMark itself, so they are skipped, though they still count towards the numbering
+pub fn parse_examples(spec: &str) -> Vec<Example> {
+  let mut examples = vec![];
+  let mut section = String::new();
+  let mut number = 0;
+  let mut lines = spec.lines();
+
+  while let Some(line) = lines.next() {
+    if let Some(heading) = line.strip_prefix('#') {
+      section = heading.trim_start_matches('#').trim().to_string();
+      continue;
+    }
+    let Some(kind) = line
+      .strip_prefix(FENCE)
+      .and_then(|rest| rest.strip_prefix(" example"))
+    else {
+      continue;
+    };
+    number += 1;
+
+    let mut markdown = String::new();
+    for line in lines.by_ref().take_while(|line| *line != ".") {
+      markdown.push_str(line);
+      markdown.push('\n');
+    }
+    let mut html = String::new();
+    for line in lines.by_ref().take_while(|line| *line != FENCE) {
+      html.push_str(line);
+      html.push('\n');
+    }
+
+    if kind.trim().is_empty() {
+      examples.push(Example {
+        number,
+        section: section.clone(),
+        markdown: markdown.replace('→', "\t"),
+        html: html.replace('→', "\t"),
+      });
+    }
+  }
+  examples
+}
@@ -0,0 +1,137 @@
+//! Runs every example from the CommonMark spec, reporting how many pass in each section.
+//!
+//! Examples are read straight from `spec.txt`, and the HTML for each is normalised before being compared so that
+//! differences a browser wouldn't notice are ignored. Examples that are known to fail are listed in `known-failures.txt`
+//! so that the suite stays green while coverage grows. It fails if any other example fails, or if a listed example
+//! starts passing so that it can be removed from the list.
+//!
+//! Run with `cargo test --test spec -- --nocapture` to see the report for each section.
+
+mod examples;
+mod normalise;
+
+use std::collections::BTreeSet;
+
+use examples::{parse_examples, Example};
+use normalise::normalise;
+use smarkdown::parse;
+
+const SPEC: &str = include_str!("spec.txt");
+const KNOWN_FAILURES: &str = include_str!("known-failures.txt");
+
+/// The example numbers listed in `known-failures.txt`, one per line. Anything after a `#` is a comment
+fn known_failures() -> BTreeSet<usize> {
+  KNOWN_FAILURES
+    .lines()
+    .map(|line| line.split('#').next().unwrap_or_default().trim())
+    .filter(|line| !line.is_empty())
+    .map(|line| {
+      line
+        .parse()
+        .unwrap_or_else(|_| panic!("`{line}` in known-failures.txt isn't an example number"))
+    })
+    .collect()
+}
+
+fn passes(example: &Example) -> bool {
+  normalise(&parse(&example.markdown).as_html()) == normalise(&example.html)
+}
+
+#[test]
+fn spec() {
+  let examples = parse_examples(SPEC);
+  let known_failures = known_failures();
+  assert!(!examples.is_empty(), "no examples were found in spec.txt");
+
+  let mut sections: Vec<(&str, usize, usize)> = vec![];
+  let mut unexpected_failures = vec![];
+  let mut unexpected_passes = vec![];
+  for example in &examples {
+    let passed = passes(example);
+    match sections.last_mut() {
+      Some((section, total, passing)) if *section == example.section => {
+        *total += 1;
+        *passing += passed as usize;
+      }
+      _ => sections.push((&example.section, 1, passed as usize)),
+    }
+
+    let known = known_failures.contains(&example.number);
+    if !passed && !known {
+      unexpected_failures.push(example);
+    } else if passed && known {
+      unexpected_passes.push(example.number);
+    }
+  }
+
+  let total_passing: usize = sections.iter().map(|(_, _, passing)| passing).sum();
+  for (section, total, passing) in &sections {
+    println!("{section:<45} {passing:>3}/{total:<3}");
+  }
+  println!("{:<45} {total_passing:>3}/{}", "Total", examples.len());
+
+  for example in &unexpected_failures {
+    println!(
+      "\nexample {} ({}) failed\n--- markdown\n{}--- expected\n{}--- found\n{}",
+      example.number,
+      example.section,
+      example.markdown,
+      example.html,
+      parse(&example.markdown).as_html()
+    );
+  }
+  assert!(
+    unexpected_failures.is_empty(),
+    "{} examples failed that aren't in known-failures.txt: {:?}",
+    unexpected_failures.len(),
+    unexpected_failures
+      .iter()
+      .map(|example| example.number)
+      .collect::<Vec<_>>()
+  );
+  assert!(
+    unexpected_passes.is_empty(),
+    "these examples now pass, so should be removed from known-failures.txt: {unexpected_passes:?}"
+  );
+}
+
+/// Every example has to come back the same after being written as markdown and parsed again, whether or not it
+/// matches the spec's HTML
+#[test]
+fn commonmark_round_trip() {
+  let mut failures = vec![];
+  for example in parse_examples(SPEC) {
+    let document = parse(&example.markdown);
+    let markdown = document.as_commonmark();
+    let html = document.as_html();
+    let round_tripped = parse(&markdown).as_html();
+    if normalise(&round_tripped) != normalise(&html) {
+      println!(
+        "\nexample {} didn't round trip\n--- markdown\n{}--- written\n{markdown}\n--- expected\n{html}\n--- found\n{round_tripped}",
+        example.number, example.markdown
+      );
+      failures.push(example.number);
+    }
+  }
+  assert!(
+    failures.is_empty(),
+    "{} examples didn't round trip: {failures:?}",
+    failures.len()
+  );
+}
+
+#[test]
+fn normalisation() {
+  assert_eq!(
+    normalise("<p>a\n  b</p>\n<UL>\n<li>c</li>\n</UL>"),
+    "<p>a b</p><ul><li>c</li></ul>"
+  );
+  assert_eq!(
+    normalise("<a title='x' href=\"/&#x75;rl\">&quot;q&#34;</a><br />\nd"),
+    "<a href=\"/url\" title=\"x\">&quot;q&quot;</a><br>d"
+  );
+  assert_eq!(
+    normalise("<pre><code>a\n  b\n</code></pre>"),
+    "<pre><code>a\n  b\n</code></pre>"
+  );
+}
@@ -0,0 +1,335 @@
+/// Tags that whitespace next to is ignored, as it makes no difference to how the page is shown
+const BLOCK_TAGS: &[&str] = &[
+  "article",
+  "header",
+  "aside",
+  "hgroup",
+  "blockquote",
+  "hr",
+  "iframe",
+  "body",
+  "li",
+  "map",
+  "button",
+  "object",
+  "canvas",
+  "ol",
+  "caption",
+  "output",
+  "col",
+  "p",
+  "colgroup",
+  "pre",
+  "dd",
+  "progress",
+  "div",
+  "section",
+  "dl",
+  "table",
+  "td",
+  "dt",
+  "tbody",
+  "embed",
+  "textarea",
+  "fieldset",
+  "tfoot",
+  "figcaption",
+  "th",
+  "figure",
+  "thead",
+  "footer",
+  "tr",
+  "form",
+  "ul",
+  "h1",
+  "h2",
+  "h3",
+  "h4",
+  "h5",
+  "h6",
+  "video",
+  "script",
+  "style",
+];
+
+#[derive(PartialEq, Clone, Copy)]
+enum Last {
+  StartTag,
+  EndTag,
+  Other,
+}
+
+/// Normalises HTML the same way as the spec's own test runner (`normalize.py` in cmark), so that HTML which
+/// only differs in ways a browser wouldn't notice compares equal.
+///
+/// - Runs of whitespace outside of `<pre>` become a single space, and whitespace next to block level tags is removed
+/// - Tag names are lowercased and attributes are sorted, with their values escaped consistently
+/// - Self closing tags lose their `/`, so `<br />` becomes `<br>`
+/// - Numeric character references are decoded, and text is escaped consistently so `"` and `&quot;` compare equal
+pub fn normalise(html: &str) -> String {
+  let mut normaliser = Normaliser {
+    output: String::new(),
+    in_pre: false,
+    last: Last::Other,
+    last_tag: String::new(),
+  };
+  let mut rest = html;
+  while !rest.is_empty() {
+    let consumed = normaliser
+      .markup(rest)
+      .unwrap_or_else(|| normaliser.text(rest));
+    rest = &rest[consumed..];
+  }
+  normaliser.output.trim().to_string()
+}
+
+struct Normaliser {
+  output: String,
+  in_pre: bool,
+  last: Last,
+  last_tag: String,
+}
+
+impl Normaliser {
+  /// Handles a tag, comment or declaration at the start of `html`, returning how much of it was used
+  fn markup(&mut self, html: &str) -> Option<usize> {
+    if !html.starts_with('<') {
+      return None;
+    }
+    if let Some(comment) = html.strip_prefix("<!--") {
+      let end = comment.find("-->")? + 7;
+      return Some(self.verbatim(&html[..end]));
+    }
+    if html.starts_with("<!") || html.starts_with("<?") {
+      let end = html.find('>')? + 1;
+      return Some(self.verbatim(&html[..end]));
+    }
+    if let Some(tag) = html.strip_prefix("</") {
+      let name_length = tag_name_length(tag)?;
+      let end = tag.find('>')?;
+      self.end_tag(&tag[..name_length].to_lowercase());
+      return Some(end + 3);
+    }
+    let (name, attributes, length) = parse_start_tag(html)?;
+    self.start_tag(&name, attributes);
+    Some(length)
+  }
+
+  fn verbatim(&mut self, markup: &str) -> usize {
+    self.output.push_str(markup);
+    self.last = Last::Other;
+    markup.len()
+  }
+
+  fn start_tag(&mut self, name: &str, mut attributes: Vec<(String, Option<String>)>) {
+    if name == "pre" {
+      self.in_pre = true;
+    }
+    if is_block_tag(name) {
+      self.trim_output();
+    }
+    self.output.push('<');
+    self.output.push_str(name);
+    attributes.sort();
+    for (attribute, value) in attributes {
+      self.output.push(' ');
+      self.output.push_str(&attribute);
+      if let Some(value) = value {
+        self.output.push_str("=\"");
+        push_escaped(&mut self.output, &decode_references(&value), true);
+        self.output.push('"');
+      }
+    }
+    self.output.push('>');
+    self.last = Last::StartTag;
+    self.last_tag = name.to_string();
+  }
+
+  fn end_tag(&mut self, name: &str) {
+    if name == "pre" {
+      self.in_pre = false;
+    } else if is_block_tag(name) {
+      self.trim_output();
+    }
+    self.output.push_str("</");
+    self.output.push_str(name);
+    self.output.push('>');
+    self.last = Last::EndTag;
+    self.last_tag = name.to_string();
+  }
+
+  /// Handles the text up to the next `<` after the start of `html`, returning how much of it was used
+  fn text(&mut self, html: &str) -> usize {
+    let end = html
+      .char_indices()
+      .skip(1)
+      .find(|(_, char)| *char == '<')
+      .map_or(html.len(), |(end, _)| end);
+    let mut text = String::new();
+    push_escaped(&mut text, &decode_references(&html[..end]), false);
+
+    let after_tag = self.last != Last::Other;
+    let after_block_tag = after_tag && is_block_tag(&self.last_tag);
+    let mut text = text.as_str();
+    if after_tag && self.last_tag == "br" {
+      text = text.trim_start_matches('\n');
+    }
+    let mut collapsed;
+    if !self.in_pre {
+      collapsed = collapse_whitespace(text);
+      if after_block_tag {
+        collapsed = match self.last {
+          Last::StartTag => collapsed.trim_start().to_string(),
+          _ => collapsed.trim().to_string(),
+        };
+      }
+      text = &collapsed;
+    }
+    self.output.push_str(text);
+    self.last = Last::Other;
+    end
+  }
+
+  fn trim_output(&mut self) {
+    let trimmed = self.output.trim_end().len();
+    self.output.truncate(trimmed);
+  }
+}
+
+fn is_block_tag(name: &str) -> bool {
+  BLOCK_TAGS.contains(&name)
+}
+
+fn tag_name_length(tag: &str) -> Option<usize> {
+  if !tag.starts_with(|char: char| char.is_ascii_alphabetic()) {
+    return None;
+  }
+  Some(
+    tag
+      .find(|char: char| !char.is_ascii_alphanumeric() && char != '-')
+      .unwrap_or(tag.len()),
+  )
+}
+
+/// Reads a start tag at the start of `html` into its lowercased name, its attributes and its length
+#[allow(clippy::type_complexity)]
+fn parse_start_tag(html: &str) -> Option<(String, Vec<(String, Option<String>)>, usize)> {
+  let name_length = tag_name_length(&html[1..])?;
+  let name = html[1..=name_length].to_lowercase();
+  let mut attributes = vec![];
+  let mut position = name_length + 1;
+  loop {
+    let rest = &html[position..];
+    let trimmed = rest.trim_start();
+    position += rest.len() - trimmed.len();
+    if trimmed.starts_with("/>") {
+      return Some((name, attributes, position + 2));
+    }
+    if trimmed.starts_with('>') {
+      return Some((name, attributes, position + 1));
+    }
+    // Any attribute must have been separated from what came before
+    if rest.len() == trimmed.len() {
+      return None;
+    }
+
+    let attribute_length = trimmed
+      .find(|char: char| char.is_whitespace() || char == '=' || char == '>' || char == '/')?;
+    if attribute_length == 0 {
+      return None;
+    }
+    let attribute = trimmed[..attribute_length].to_lowercase();
+    position += attribute_length;
+
+    let rest = &html[position..];
+    let after_equals = rest.trim_start().strip_prefix('=');
+    let Some(after_equals) = after_equals else {
+      attributes.push((attribute, None));
+      continue;
+    };
+    let value_start = after_equals.trim_start();
+    position += rest.len() - value_start.len();
+    let (value, length) = match value_start.chars().next()? {
+      quote @ ('"' | '\'') => {
+        let end = value_start[1..].find(quote)?;
+        (&value_start[1..=end], end + 2)
+      }
+      _ => {
+        let end = value_start.find(|char: char| char.is_whitespace() || char == '>')?;
+        (&value_start[..end], end)
+      }
+    };
+    attributes.push((attribute, Some(value.to_string())));
+    position += length;
+  }
+}
+
+/// Decodes numeric character references and the entities that the normalised output uses
+fn decode_references(text: &str) -> String {
+  let mut decoded = String::new();
+  let mut rest = text;
+  while let Some(start) = rest.find('&') {
+    decoded.push_str(&rest[..start]);
+    rest = &rest[start..];
+    let reference = rest
+      .find(';')
+      .map(|end| &rest[1..end])
+      .filter(|name| name.len() <= 32);
+    let char = reference.and_then(|name| match name {
+      "amp" => Some('&'),
+      "lt" => Some('<'),
+      "gt" => Some('>'),
+      "quot" => Some('"'),
+      _ => {
+        let number = name.strip_prefix('#')?;
+        let code = match number.strip_prefix(['x', 'X']) {
+          Some(hex) => u32::from_str_radix(hex, 16).ok()?,
+          None => number.parse().ok()?,
+        };
+        char::from_u32(code).filter(|char| *char != '\0')
+      }
+    });
+    match (reference, char) {
+      (Some(name), Some(char)) => {
+        decoded.push(char);
+        rest = &rest[name.len() + 2..];
+      }
+      _ => {
+        decoded.push('&');
+        rest = &rest[1..];
+      }
+    }
+  }
+  decoded.push_str(rest);
+  decoded
+}
+
+fn push_escaped(output: &mut String, text: &str, attribute: bool) {
+  for char in text.chars() {
+    match char {
+      '&' => output.push_str("&amp;"),
+      '<' => output.push_str("&lt;"),
+      '>' => output.push_str("&gt;"),
+      '"' => output.push_str("&quot;"),
+      '\'' if attribute => output.push_str("&#x27;"),
+      char => output.push(char),
+    }
+  }
+}
+
+fn collapse_whitespace(text: &str) -> String {
+  let mut collapsed = String::new();
+  let mut in_whitespace = false;
+  for char in text.chars() {
+    if char.is_whitespace() {
+      if !in_whitespace {
+        collapsed.push(' ');
+      }
+      in_whitespace = true;
+    } else {
+      collapsed.push(char);
+      in_whitespace = false;
+    }
+  }
+  collapsed
+}